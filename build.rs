@@ -0,0 +1,25 @@
+use std::process::Command;
+
+fn main() {
+    // Dockerビルドなど.gitがない環境ではGIT_SHAを環境変数で渡す
+    let git_sha = std::env::var("GIT_SHA").ok().or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .ok()
+            .filter(|v| v.status.success())
+            .and_then(|v| String::from_utf8(v.stdout).ok())
+            .map(|v| v.trim().to_string())
+    });
+    println!(
+        "cargo:rustc-env=GIT_SHA={}",
+        git_sha.unwrap_or("unknown".to_string())
+    );
+    println!(
+        "cargo:rustc-env=BUILD_TIME={}",
+        chrono::Utc::now().to_rfc3339()
+    );
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
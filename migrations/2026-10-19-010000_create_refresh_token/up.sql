@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS refresh_token (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  family_id varchar(255) NOT NULL,
+  token_hash varchar(255) NOT NULL,
+  expires_at datetime NOT NULL,
+  used_at datetime DEFAULT NULL,
+  revoked_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY token_hash (token_hash),
+  KEY family_id (family_id),
+  CONSTRAINT refresh_token_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
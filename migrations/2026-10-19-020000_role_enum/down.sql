@@ -0,0 +1,3 @@
+ALTER TABLE user DROP CHECK user_role_check;
+ALTER TABLE user MODIFY COLUMN role varchar(255) NOT NULL;
+UPDATE user SET role = 'user' WHERE role IN ('viewer', 'editor');
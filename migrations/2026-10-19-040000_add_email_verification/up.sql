@@ -0,0 +1,14 @@
+ALTER TABLE user ADD COLUMN verified_at datetime DEFAULT NULL;
+-- 既存のユーザーは確認済みとして扱う
+UPDATE user SET verified_at = CURRENT_TIMESTAMP;
+CREATE TABLE IF NOT EXISTS email_verification_token (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  token_hash varchar(255) NOT NULL,
+  expires_at datetime NOT NULL,
+  used_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY token_hash (token_hash),
+  CONSTRAINT email_verification_token_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS signin_audit_log (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  email varchar(255) NOT NULL,
+  ip_address varchar(45) DEFAULT NULL,
+  reason varchar(255) NOT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY email (email),
+  KEY created_at (created_at)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE refresh_token DROP COLUMN mfa;
+DROP TABLE IF EXISTS two_factor_challenge;
+DROP TABLE IF EXISTS totp_backup_code;
+DROP TABLE IF EXISTS user_totp;
@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS user_totp (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  secret varchar(255) NOT NULL,
+  enabled_at datetime DEFAULT NULL,
+  last_used_step bigint DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY user_id (user_id),
+  CONSTRAINT user_totp_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS totp_backup_code (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  code_hash varchar(255) NOT NULL,
+  used_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_id (user_id),
+  CONSTRAINT totp_backup_code_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS two_factor_challenge (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  token_hash varchar(255) NOT NULL,
+  attempts int NOT NULL DEFAULT 0,
+  expires_at datetime NOT NULL,
+  used_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY token_hash (token_hash),
+  CONSTRAINT two_factor_challenge_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+ALTER TABLE refresh_token ADD COLUMN mfa boolean NOT NULL DEFAULT false;
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS api_key (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  name varchar(255) NOT NULL,
+  key_prefix varchar(255) NOT NULL,
+  key_hash varchar(255) NOT NULL,
+  scopes varchar(1024) NOT NULL,
+  mfa boolean NOT NULL DEFAULT false,
+  expires_at datetime DEFAULT NULL,
+  last_used_at datetime DEFAULT NULL,
+  revoked_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY key_hash (key_hash),
+  KEY user_id (user_id),
+  CONSTRAINT api_key_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
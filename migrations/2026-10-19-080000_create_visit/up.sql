@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS visit (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  onsen_id int unsigned NOT NULL,
+  visited_on date NOT NULL,
+  time_of_day varchar(255) DEFAULT NULL,
+  weather varchar(255) DEFAULT NULL,
+  crowd_level varchar(255) DEFAULT NULL,
+  water_temperature varchar(255) DEFAULT NULL,
+  note text NOT NULL,
+  is_public boolean NOT NULL DEFAULT false,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_id (user_id, visited_on),
+  KEY onsen_id (onsen_id),
+  CONSTRAINT visit_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT visit_ibfk_2 FOREIGN KEY (onsen_id) REFERENCES onsen (id) ON DELETE CASCADE
+);
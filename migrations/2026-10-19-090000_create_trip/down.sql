@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS trip_day_area;
+DROP TABLE IF EXISTS trip_day_visit;
+DROP TABLE IF EXISTS trip_day;
+DROP TABLE IF EXISTS trip;
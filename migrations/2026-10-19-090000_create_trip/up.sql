@@ -0,0 +1,45 @@
+CREATE TABLE IF NOT EXISTS trip (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  title varchar(255) NOT NULL,
+  start_on date NOT NULL,
+  end_on date NOT NULL,
+  note text NOT NULL,
+  is_public boolean NOT NULL DEFAULT false,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_id (user_id, start_on),
+  CONSTRAINT trip_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS trip_day (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  trip_id int unsigned NOT NULL,
+  day_number int NOT NULL,
+  hotel_id int unsigned DEFAULT NULL,
+  note text NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY trip_id (trip_id, day_number),
+  CONSTRAINT trip_day_ibfk_1 FOREIGN KEY (trip_id) REFERENCES trip (id) ON DELETE CASCADE,
+  CONSTRAINT trip_day_ibfk_2 FOREIGN KEY (hotel_id) REFERENCES hotel (id) ON DELETE SET NULL
+);
+CREATE TABLE IF NOT EXISTS trip_day_visit (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  trip_day_id int unsigned NOT NULL,
+  visit_id int unsigned NOT NULL,
+  position int NOT NULL,
+  PRIMARY KEY (id),
+  KEY trip_day_id (trip_day_id),
+  CONSTRAINT trip_day_visit_ibfk_1 FOREIGN KEY (trip_day_id) REFERENCES trip_day (id) ON DELETE CASCADE,
+  CONSTRAINT trip_day_visit_ibfk_2 FOREIGN KEY (visit_id) REFERENCES visit (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS trip_day_area (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  trip_day_id int unsigned NOT NULL,
+  area_id int unsigned NOT NULL,
+  position int NOT NULL,
+  PRIMARY KEY (id),
+  KEY trip_day_id (trip_day_id),
+  CONSTRAINT trip_day_area_ibfk_1 FOREIGN KEY (trip_day_id) REFERENCES trip_day (id) ON DELETE CASCADE,
+  CONSTRAINT trip_day_area_ibfk_2 FOREIGN KEY (area_id) REFERENCES area (id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS hotel_review;
+DROP TABLE IF EXISTS onsen_review;
@@ -0,0 +1,38 @@
+CREATE TABLE IF NOT EXISTS onsen_review (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  onsen_id int unsigned NOT NULL,
+  overall int NOT NULL,
+  water_quality int NOT NULL,
+  cleanliness int NOT NULL,
+  view int NOT NULL,
+  crowding int NOT NULL,
+  comment text NOT NULL,
+  status varchar(255) NOT NULL,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY user_id (user_id, onsen_id),
+  KEY onsen_id (onsen_id),
+  KEY status (status),
+  CONSTRAINT onsen_review_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT onsen_review_ibfk_2 FOREIGN KEY (onsen_id) REFERENCES onsen (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS hotel_review (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  hotel_id int unsigned NOT NULL,
+  room int NOT NULL,
+  food int NOT NULL,
+  bath int NOT NULL,
+  comment text NOT NULL,
+  status varchar(255) NOT NULL,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY user_id (user_id, hotel_id),
+  KEY hotel_id (hotel_id),
+  KEY status (status),
+  CONSTRAINT hotel_review_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT hotel_review_ibfk_2 FOREIGN KEY (hotel_id) REFERENCES hotel (id) ON DELETE CASCADE
+);
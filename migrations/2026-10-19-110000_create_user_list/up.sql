@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS user_list (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  kind varchar(255) NOT NULL,
+  name varchar(255) NOT NULL,
+  share_token varchar(255) DEFAULT NULL,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_id (user_id),
+  UNIQUE KEY share_token (share_token),
+  CONSTRAINT user_list_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS user_list_item (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_list_id int unsigned NOT NULL,
+  target varchar(255) NOT NULL,
+  target_id int unsigned NOT NULL,
+  position int NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_list_id (user_list_id),
+  CONSTRAINT user_list_item_ibfk_1 FOREIGN KEY (user_list_id) REFERENCES user_list (id) ON DELETE CASCADE
+);
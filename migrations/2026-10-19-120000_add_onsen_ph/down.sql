@@ -0,0 +1 @@
+ALTER TABLE onsen DROP COLUMN ph;
@@ -0,0 +1 @@
+ALTER TABLE onsen ADD COLUMN ph double DEFAULT NULL AFTER liquid;
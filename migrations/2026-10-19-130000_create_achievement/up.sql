@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS achievement (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  name varchar(255) NOT NULL,
+  description text NOT NULL,
+  condition_kind varchar(255) NOT NULL,
+  condition_target varchar(255) NOT NULL DEFAULT '',
+  condition_value double NOT NULL DEFAULT 0,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id)
+);
+CREATE TABLE IF NOT EXISTS user_achievement (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  achievement_id int unsigned NOT NULL,
+  achieved_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY user_id (user_id, achievement_id),
+  CONSTRAINT user_achievement_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT user_achievement_ibfk_2 FOREIGN KEY (achievement_id) REFERENCES achievement (id) ON DELETE CASCADE
+);
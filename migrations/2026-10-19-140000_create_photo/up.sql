@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS photo (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  target varchar(255) NOT NULL,
+  target_id int unsigned NOT NULL,
+  user_id int unsigned NOT NULL,
+  blob_key varchar(255) NOT NULL,
+  content_type varchar(255) NOT NULL,
+  byte_size int unsigned NOT NULL,
+  caption varchar(255) NOT NULL DEFAULT '',
+  position int NOT NULL,
+  is_cover tinyint(1) NOT NULL DEFAULT 0,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY target (target, target_id),
+  UNIQUE KEY blob_key (blob_key),
+  CONSTRAINT photo_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE photo DROP COLUMN thumbnail_status;
@@ -0,0 +1 @@
+ALTER TABLE photo ADD COLUMN thumbnail_status varchar(255) NOT NULL DEFAULT 'pending' AFTER is_cover, ADD KEY thumbnail_status (thumbnail_status);
@@ -0,0 +1,30 @@
+CREATE TABLE IF NOT EXISTS user_profile (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  handle varchar(30) NOT NULL,
+  display_name varchar(255) NOT NULL,
+  bio text NOT NULL,
+  profile_visibility varchar(255) NOT NULL DEFAULT 'public',
+  visits_visibility varchar(255) NOT NULL DEFAULT 'public',
+  trips_visibility varchar(255) NOT NULL DEFAULT 'public',
+  reviews_visibility varchar(255) NOT NULL DEFAULT 'public',
+  approve_followers tinyint(1) NOT NULL DEFAULT 0,
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY user_id (user_id),
+  UNIQUE KEY handle (handle),
+  CONSTRAINT user_profile_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS follow (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  follower_id int unsigned NOT NULL,
+  followee_id int unsigned NOT NULL,
+  status varchar(255) NOT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY follower_followee (follower_id, followee_id),
+  KEY followee_id (followee_id),
+  CONSTRAINT follow_ibfk_1 FOREIGN KEY (follower_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT follow_ibfk_2 FOREIGN KEY (followee_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1,41 @@
+CREATE TABLE IF NOT EXISTS comment (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  target varchar(255) NOT NULL,
+  target_id int unsigned NOT NULL,
+  user_id int unsigned NOT NULL,
+  parent_id int unsigned DEFAULT NULL,
+  body text NOT NULL,
+  status varchar(255) NOT NULL DEFAULT 'visible',
+  created_at datetime NOT NULL,
+  updated_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY target (target, target_id),
+  CONSTRAINT comment_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT comment_ibfk_2 FOREIGN KEY (parent_id) REFERENCES comment (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS comment_report (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  comment_id int unsigned NOT NULL,
+  user_id int unsigned NOT NULL,
+  reason varchar(255) NOT NULL,
+  note text NOT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  UNIQUE KEY comment_user (comment_id, user_id),
+  CONSTRAINT comment_report_ibfk_1 FOREIGN KEY (comment_id) REFERENCES comment (id) ON DELETE CASCADE,
+  CONSTRAINT comment_report_ibfk_2 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS notification (
+  id int unsigned NOT NULL AUTO_INCREMENT,
+  user_id int unsigned NOT NULL,
+  kind varchar(255) NOT NULL,
+  actor_id int unsigned NOT NULL,
+  comment_id int unsigned NOT NULL,
+  read_at datetime DEFAULT NULL,
+  created_at datetime NOT NULL,
+  PRIMARY KEY (id),
+  KEY user_id (user_id),
+  CONSTRAINT notification_ibfk_1 FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT notification_ibfk_2 FOREIGN KEY (actor_id) REFERENCES user (id) ON DELETE CASCADE,
+  CONSTRAINT notification_ibfk_3 FOREIGN KEY (comment_id) REFERENCES comment (id) ON DELETE CASCADE
+);
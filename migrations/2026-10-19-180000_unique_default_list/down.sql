@@ -0,0 +1 @@
+ALTER TABLE user_list DROP INDEX user_id_default_kind, DROP COLUMN default_kind;
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS "user";
+DROP TABLE IF EXISTS onsen;
+DROP TABLE IF EXISTS chemicals;
+DROP TABLE IF EXISTS hotel;
+DROP TABLE IF EXISTS area;
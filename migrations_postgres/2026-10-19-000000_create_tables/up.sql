@@ -0,0 +1,69 @@
+-- MySQLのmigrationsを適用した後と同じテーブル構成
+-- int unsignedはinteger + CHECK制約で表現する
+CREATE TABLE IF NOT EXISTS area (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  name varchar(255) NOT NULL,
+  kana varchar(255) NOT NULL DEFAULT '',
+  prefecture varchar(255) NOT NULL DEFAULT '',
+  national_resort boolean NOT NULL DEFAULT false,
+  village varchar(255),
+  url varchar(255) NOT NULL DEFAULT '',
+  description text NOT NULL,
+  access text NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS hotel (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  name varchar(255) NOT NULL,
+  has_washitsu boolean NOT NULL DEFAULT false,
+  solo_available boolean NOT NULL DEFAULT false,
+  url varchar(255) NOT NULL DEFAULT '',
+  description text NOT NULL,
+  area_id integer REFERENCES area (id)
+);
+
+CREATE TABLE IF NOT EXISTS chemicals (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  na_ion integer NOT NULL DEFAULT 0 CHECK (na_ion >= 0),
+  ca_ion integer NOT NULL DEFAULT 0 CHECK (ca_ion >= 0),
+  mg_ion integer NOT NULL DEFAULT 0 CHECK (mg_ion >= 0),
+  cl_ion integer NOT NULL DEFAULT 0 CHECK (cl_ion >= 0),
+  hco3_ion integer NOT NULL DEFAULT 0 CHECK (hco3_ion >= 0),
+  so4_ion integer NOT NULL DEFAULT 0 CHECK (so4_ion >= 0),
+  co2_ion integer NOT NULL DEFAULT 0 CHECK (co2_ion >= 0),
+  fe_ion integer NOT NULL DEFAULT 0 CHECK (fe_ion >= 0),
+  al_ion integer NOT NULL DEFAULT 0 CHECK (al_ion >= 0),
+  cu_ion integer NOT NULL DEFAULT 0 CHECK (cu_ion >= 0),
+  h_ion integer NOT NULL DEFAULT 0 CHECK (h_ion >= 0),
+  i_ion integer NOT NULL DEFAULT 0 CHECK (i_ion >= 0),
+  s integer NOT NULL DEFAULT 0 CHECK (s >= 0),
+  rn integer NOT NULL DEFAULT 0 CHECK (rn >= 0),
+  strong_na_cl boolean NOT NULL DEFAULT false,
+  fe_type varchar(255) NOT NULL DEFAULT '',
+  weak_rn boolean NOT NULL DEFAULT false
+);
+
+CREATE TABLE IF NOT EXISTS onsen (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  name varchar(255) NOT NULL,
+  spring_quality varchar(255) NOT NULL,
+  liquid varchar(255),
+  osmotic_pressure varchar(255),
+  temperature varchar(255),
+  category varchar(255) NOT NULL,
+  day_use boolean NOT NULL,
+  url varchar(255) NOT NULL DEFAULT '',
+  img_url varchar(255),
+  description text NOT NULL,
+  chemical_id integer REFERENCES chemicals (id),
+  hotel_id integer REFERENCES hotel (id) ON DELETE RESTRICT,
+  area_id integer REFERENCES area (id)
+);
+CREATE INDEX IF NOT EXISTS onsen_hotel_id ON onsen (hotel_id);
+
+CREATE TABLE IF NOT EXISTS "user" (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  email varchar(255) NOT NULL,
+  hashed_password varchar(255) NOT NULL,
+  role varchar(255) NOT NULL
+);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS refresh_token (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  family_id varchar(255) NOT NULL,
+  token_hash varchar(255) NOT NULL UNIQUE,
+  expires_at timestamp NOT NULL,
+  used_at timestamp,
+  revoked_at timestamp,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS refresh_token_family_id ON refresh_token (family_id);
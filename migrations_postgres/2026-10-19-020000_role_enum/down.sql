@@ -0,0 +1,3 @@
+ALTER TABLE "user" DROP CONSTRAINT IF EXISTS user_role_check;
+ALTER TABLE "user" ALTER COLUMN role DROP DEFAULT;
+UPDATE "user" SET role = 'user' WHERE role IN ('viewer', 'editor');
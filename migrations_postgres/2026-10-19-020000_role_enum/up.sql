@@ -0,0 +1,3 @@
+UPDATE "user" SET role = 'viewer' WHERE role NOT IN ('viewer', 'editor', 'admin');
+ALTER TABLE "user" ALTER COLUMN role SET DEFAULT 'viewer';
+ALTER TABLE "user" ADD CONSTRAINT user_role_check CHECK (role IN ('viewer', 'editor', 'admin'));
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS password_reset_token (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  token_hash varchar(255) NOT NULL UNIQUE,
+  expires_at timestamp NOT NULL,
+  used_at timestamp,
+  created_at timestamp NOT NULL
+);
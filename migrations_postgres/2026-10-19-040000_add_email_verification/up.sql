@@ -0,0 +1,11 @@
+ALTER TABLE "user" ADD COLUMN verified_at timestamp;
+-- 既存のユーザーは確認済みとして扱う
+UPDATE "user" SET verified_at = CURRENT_TIMESTAMP;
+CREATE TABLE IF NOT EXISTS email_verification_token (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  token_hash varchar(255) NOT NULL UNIQUE,
+  expires_at timestamp NOT NULL,
+  used_at timestamp,
+  created_at timestamp NOT NULL
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS signin_audit_log (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  email varchar(255) NOT NULL,
+  ip_address varchar(45),
+  reason varchar(255) NOT NULL,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS signin_audit_log_email ON signin_audit_log (email);
+CREATE INDEX IF NOT EXISTS signin_audit_log_created_at ON signin_audit_log (created_at);
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS user_totp (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL UNIQUE REFERENCES "user" (id) ON DELETE CASCADE,
+  secret varchar(255) NOT NULL,
+  enabled_at timestamp,
+  last_used_step bigint,
+  created_at timestamp NOT NULL
+);
+CREATE TABLE IF NOT EXISTS totp_backup_code (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  code_hash varchar(255) NOT NULL,
+  used_at timestamp,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS totp_backup_code_user_id ON totp_backup_code (user_id);
+CREATE TABLE IF NOT EXISTS two_factor_challenge (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  token_hash varchar(255) NOT NULL UNIQUE,
+  attempts integer NOT NULL DEFAULT 0,
+  expires_at timestamp NOT NULL,
+  used_at timestamp,
+  created_at timestamp NOT NULL
+);
+ALTER TABLE refresh_token ADD COLUMN mfa boolean NOT NULL DEFAULT false;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS api_key (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  name varchar(255) NOT NULL,
+  key_prefix varchar(255) NOT NULL,
+  key_hash varchar(255) NOT NULL UNIQUE,
+  scopes varchar(1024) NOT NULL,
+  mfa boolean NOT NULL DEFAULT false,
+  expires_at timestamp,
+  last_used_at timestamp,
+  revoked_at timestamp,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS api_key_user_id ON api_key (user_id);
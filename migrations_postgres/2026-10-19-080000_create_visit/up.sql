@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS visit (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  onsen_id integer NOT NULL REFERENCES onsen (id) ON DELETE CASCADE,
+  visited_on date NOT NULL,
+  time_of_day varchar(255),
+  weather varchar(255),
+  crowd_level varchar(255),
+  water_temperature varchar(255),
+  note text NOT NULL,
+  is_public boolean NOT NULL DEFAULT false,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS visit_user_id ON visit (user_id, visited_on);
+CREATE INDEX IF NOT EXISTS visit_onsen_id ON visit (onsen_id);
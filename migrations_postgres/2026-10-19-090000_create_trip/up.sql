@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS trip (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  title varchar(255) NOT NULL,
+  start_on date NOT NULL,
+  end_on date NOT NULL,
+  note text NOT NULL,
+  is_public boolean NOT NULL DEFAULT false,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS trip_user_id ON trip (user_id, start_on);
+CREATE TABLE IF NOT EXISTS trip_day (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  trip_id integer NOT NULL REFERENCES trip (id) ON DELETE CASCADE,
+  day_number integer NOT NULL,
+  hotel_id integer REFERENCES hotel (id) ON DELETE SET NULL,
+  note text NOT NULL,
+  UNIQUE (trip_id, day_number)
+);
+CREATE TABLE IF NOT EXISTS trip_day_visit (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  trip_day_id integer NOT NULL REFERENCES trip_day (id) ON DELETE CASCADE,
+  visit_id integer NOT NULL REFERENCES visit (id) ON DELETE CASCADE,
+  position integer NOT NULL
+);
+CREATE INDEX IF NOT EXISTS trip_day_visit_trip_day_id ON trip_day_visit (trip_day_id);
+CREATE TABLE IF NOT EXISTS trip_day_area (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  trip_day_id integer NOT NULL REFERENCES trip_day (id) ON DELETE CASCADE,
+  area_id integer NOT NULL REFERENCES area (id) ON DELETE CASCADE,
+  position integer NOT NULL
+);
+CREATE INDEX IF NOT EXISTS trip_day_area_trip_day_id ON trip_day_area (trip_day_id);
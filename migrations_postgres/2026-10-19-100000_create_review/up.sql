@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS onsen_review (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  onsen_id integer NOT NULL REFERENCES onsen (id) ON DELETE CASCADE,
+  overall integer NOT NULL,
+  water_quality integer NOT NULL,
+  cleanliness integer NOT NULL,
+  view integer NOT NULL,
+  crowding integer NOT NULL,
+  comment text NOT NULL,
+  status varchar(255) NOT NULL,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL,
+  UNIQUE (user_id, onsen_id)
+);
+CREATE INDEX IF NOT EXISTS onsen_review_onsen_id ON onsen_review (onsen_id);
+CREATE INDEX IF NOT EXISTS onsen_review_status ON onsen_review (status);
+CREATE TABLE IF NOT EXISTS hotel_review (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  hotel_id integer NOT NULL REFERENCES hotel (id) ON DELETE CASCADE,
+  room integer NOT NULL,
+  food integer NOT NULL,
+  bath integer NOT NULL,
+  comment text NOT NULL,
+  status varchar(255) NOT NULL,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL,
+  UNIQUE (user_id, hotel_id)
+);
+CREATE INDEX IF NOT EXISTS hotel_review_hotel_id ON hotel_review (hotel_id);
+CREATE INDEX IF NOT EXISTS hotel_review_status ON hotel_review (status);
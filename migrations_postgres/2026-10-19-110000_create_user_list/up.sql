@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS user_list (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  kind varchar(255) NOT NULL,
+  name varchar(255) NOT NULL,
+  share_token varchar(255) UNIQUE,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS user_list_user_id ON user_list (user_id);
+CREATE TABLE IF NOT EXISTS user_list_item (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_list_id integer NOT NULL REFERENCES user_list (id) ON DELETE CASCADE,
+  target varchar(255) NOT NULL,
+  target_id integer NOT NULL,
+  position integer NOT NULL
+);
+CREATE INDEX IF NOT EXISTS user_list_item_user_list_id ON user_list_item (user_list_id);
@@ -0,0 +1 @@
+ALTER TABLE onsen DROP COLUMN IF EXISTS ph;
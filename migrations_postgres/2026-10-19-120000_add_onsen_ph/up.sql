@@ -0,0 +1 @@
+ALTER TABLE onsen ADD COLUMN IF NOT EXISTS ph double precision;
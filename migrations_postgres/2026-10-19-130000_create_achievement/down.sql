@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS user_achievement;
+DROP TABLE IF EXISTS achievement;
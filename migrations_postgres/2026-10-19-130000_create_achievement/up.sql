@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS achievement (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  name varchar(255) NOT NULL,
+  description text NOT NULL,
+  condition_kind varchar(255) NOT NULL,
+  condition_target varchar(255) NOT NULL DEFAULT '',
+  condition_value double precision NOT NULL DEFAULT 0,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE TABLE IF NOT EXISTS user_achievement (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  achievement_id integer NOT NULL REFERENCES achievement (id) ON DELETE CASCADE,
+  achieved_at timestamp NOT NULL,
+  UNIQUE (user_id, achievement_id)
+);
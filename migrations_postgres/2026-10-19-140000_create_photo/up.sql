@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS photo (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  target varchar(255) NOT NULL,
+  target_id integer NOT NULL,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  blob_key varchar(255) NOT NULL UNIQUE,
+  content_type varchar(255) NOT NULL,
+  byte_size integer NOT NULL,
+  caption varchar(255) NOT NULL DEFAULT '',
+  position integer NOT NULL,
+  is_cover boolean NOT NULL DEFAULT false,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS photo_target ON photo (target, target_id);
@@ -0,0 +1 @@
+ALTER TABLE photo DROP COLUMN IF EXISTS thumbnail_status;
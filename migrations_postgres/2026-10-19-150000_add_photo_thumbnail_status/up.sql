@@ -0,0 +1,2 @@
+ALTER TABLE photo ADD COLUMN IF NOT EXISTS thumbnail_status varchar(255) NOT NULL DEFAULT 'pending';
+CREATE INDEX IF NOT EXISTS photo_thumbnail_status ON photo (thumbnail_status);
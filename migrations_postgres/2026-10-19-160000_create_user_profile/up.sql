@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS user_profile (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL UNIQUE REFERENCES "user" (id) ON DELETE CASCADE,
+  handle varchar(30) NOT NULL UNIQUE,
+  display_name varchar(255) NOT NULL,
+  bio text NOT NULL,
+  profile_visibility varchar(255) NOT NULL DEFAULT 'public',
+  visits_visibility varchar(255) NOT NULL DEFAULT 'public',
+  trips_visibility varchar(255) NOT NULL DEFAULT 'public',
+  reviews_visibility varchar(255) NOT NULL DEFAULT 'public',
+  approve_followers boolean NOT NULL DEFAULT false,
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE TABLE IF NOT EXISTS follow (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  follower_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  followee_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  status varchar(255) NOT NULL,
+  created_at timestamp NOT NULL,
+  UNIQUE (follower_id, followee_id)
+);
+CREATE INDEX IF NOT EXISTS follow_followee_id ON follow (followee_id);
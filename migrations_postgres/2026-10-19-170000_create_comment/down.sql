@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS notification;
+DROP TABLE IF EXISTS comment_report;
+DROP TABLE IF EXISTS comment;
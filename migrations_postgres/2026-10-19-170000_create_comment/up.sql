@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS comment (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  target varchar(255) NOT NULL,
+  target_id integer NOT NULL,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  parent_id integer REFERENCES comment (id) ON DELETE CASCADE,
+  body text NOT NULL,
+  status varchar(255) NOT NULL DEFAULT 'visible',
+  created_at timestamp NOT NULL,
+  updated_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS comment_target ON comment (target, target_id);
+CREATE TABLE IF NOT EXISTS comment_report (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  comment_id integer NOT NULL REFERENCES comment (id) ON DELETE CASCADE,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  reason varchar(255) NOT NULL,
+  note text NOT NULL,
+  created_at timestamp NOT NULL,
+  UNIQUE (comment_id, user_id)
+);
+CREATE TABLE IF NOT EXISTS notification (
+  id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  user_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  kind varchar(255) NOT NULL,
+  actor_id integer NOT NULL REFERENCES "user" (id) ON DELETE CASCADE,
+  comment_id integer NOT NULL REFERENCES comment (id) ON DELETE CASCADE,
+  read_at timestamp,
+  created_at timestamp NOT NULL
+);
+CREATE INDEX IF NOT EXISTS notification_user_id ON notification (user_id);
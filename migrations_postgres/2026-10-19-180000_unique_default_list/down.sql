@@ -0,0 +1,2 @@
+ALTER TABLE user_list DROP CONSTRAINT IF EXISTS user_list_user_id_default_kind;
+ALTER TABLE user_list DROP COLUMN IF EXISTS default_kind;
@@ -0,0 +1,14 @@
+-- お気に入りと行きたいはユーザーごとに1つだけ。重複していたものは名前をつけたリストにする
+UPDATE user_list SET kind = 'custom'
+WHERE kind <> 'custom' AND id NOT IN (
+  SELECT MIN(id) FROM user_list WHERE kind <> 'custom' GROUP BY user_id, kind
+);
+ALTER TABLE user_list
+  ADD COLUMN default_kind varchar(255) GENERATED ALWAYS AS (CASE WHEN kind = 'custom' THEN NULL ELSE kind END) STORED;
+ALTER TABLE user_list ADD CONSTRAINT user_list_user_id_default_kind UNIQUE (user_id, default_kind);
+INSERT INTO user_list (user_id, kind, name, created_at, updated_at)
+SELECT id, 'favorite', 'お気に入り', now() AT TIME ZONE 'utc', now() AT TIME ZONE 'utc' FROM "user"
+WHERE id NOT IN (SELECT user_id FROM user_list WHERE kind = 'favorite');
+INSERT INTO user_list (user_id, kind, name, created_at, updated_at)
+SELECT id, 'want_to_go', '行きたい', now() AT TIME ZONE 'utc', now() AT TIME ZONE 'utc' FROM "user"
+WHERE id NOT IN (SELECT user_id FROM user_list WHERE kind = 'want_to_go');
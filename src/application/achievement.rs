@@ -0,0 +1,53 @@
+use crate::domain::achievement_entity::{AchievementCondition, UserAchievementEntity};
+use crate::domain::visit_stats::VisitStats;
+use crate::infrastructure::repository::{
+    achievement_repository, area_repository, onsen_repository, visit_repository,
+};
+use chrono::Utc;
+use tracing::instrument;
+
+/// まだ獲得していない実績を入浴記録で判定し、達成したものをバッジとして記録する。
+/// 獲得済みのバッジは入浴記録を消しても取り消さない
+#[instrument]
+pub fn award_achievements(user_id: u32) -> Vec<UserAchievementEntity> {
+    let user_achievements = achievement_repository::get_user_achievements(user_id);
+    let pending: Vec<_> = achievement_repository::get_achievements()
+        .into_iter()
+        .filter(|v| {
+            !user_achievements
+                .iter()
+                .any(|achieved| achieved.achievement_id == v.id)
+        })
+        .collect();
+    if pending.is_empty() {
+        return user_achievements;
+    }
+    let visits = visit_repository::get_visits(user_id);
+    let mut onsen_ids: Vec<u32> = visits.iter().map(|v| v.onsen_id).collect();
+    onsen_ids.sort();
+    onsen_ids.dedup();
+    let onsens = onsen_repository::get_onsens_by_ids(&onsen_ids);
+    let area_ids: Vec<u32> = onsens.iter().filter_map(|v| v.area_id).collect();
+    let mut areas = area_repository::get_areas_by_ids(&area_ids);
+    let prefectures: Vec<String> = pending
+        .iter()
+        .filter_map(|v| match &v.condition {
+            AchievementCondition::PrefectureComplete(prefecture) => Some(prefecture.clone()),
+            _ => None,
+        })
+        .collect();
+    if !prefectures.is_empty() {
+        areas.extend(area_repository::get_areas_by_prefectures(&prefectures));
+    }
+    let stats = VisitStats::compute(&visits, &onsens, &areas);
+    let achieved_ids: Vec<u32> = pending
+        .iter()
+        .filter(|v| v.condition.is_satisfied(&stats, &onsens, &areas))
+        .map(|v| v.id)
+        .collect();
+    if achieved_ids.is_empty() {
+        return user_achievements;
+    }
+    achievement_repository::post_user_achievements(user_id, &achieved_ids, Utc::now().naive_utc());
+    achievement_repository::get_user_achievements(user_id)
+}
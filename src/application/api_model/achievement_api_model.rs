@@ -0,0 +1,104 @@
+use crate::domain::achievement_entity::{
+    AchievementCondition, AchievementEntity, UserAchievementEntity,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub condition: AchievementConditionModel,
+}
+
+/// `kind`ごとに`target`か`value`のどちらかを使う
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementConditionModel {
+    pub kind: String,
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub value: f64,
+}
+
+impl AchievementRequest {
+    pub fn create_entity(&self, id: u32) -> Option<AchievementEntity> {
+        let condition = AchievementCondition::new(
+            &self.condition.kind,
+            &self.condition.target,
+            self.condition.value,
+        )?;
+        AchievementEntity::new(id, &self.name, &self.description, condition)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AchievementResponse {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementConditionModel,
+}
+
+impl From<AchievementEntity> for AchievementResponse {
+    fn from(value: AchievementEntity) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            condition: AchievementConditionModel {
+                kind: value.condition.kind().to_string(),
+                target: value.condition.target(),
+                value: value.condition.value(),
+            },
+        }
+    }
+}
+
+/// `GET /me/achievements`で返す。獲得していなければ`achievedAt`はnull
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAchievementResponse {
+    #[serde(flatten)]
+    pub achievement: AchievementResponse,
+    pub achieved_at: Option<String>,
+}
+
+impl UserAchievementResponse {
+    pub fn create(
+        achievement: AchievementEntity,
+        achieved: Option<&UserAchievementEntity>,
+    ) -> Self {
+        Self {
+            achievement: AchievementResponse::from(achievement),
+            achieved_at: achieved.map(|v| v.achieved_at.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AchievementConditionModel, AchievementRequest, AchievementResponse};
+    use crate::domain::achievement_entity::AchievementCondition;
+
+    #[test]
+    fn test_create_entity() {
+        let request = AchievementRequest {
+            name: "強酸性".to_string(),
+            description: "pH2未満の温泉に入る".to_string(),
+            condition: AchievementConditionModel {
+                kind: "ph_below".to_string(),
+                target: "".to_string(),
+                value: 2.0,
+            },
+        };
+        let entity = request.create_entity(1).unwrap();
+        assert_eq!(entity.condition, AchievementCondition::PhBelow(2.0));
+        let response = AchievementResponse::from(entity);
+        assert_eq!(response.condition.kind, "ph_below");
+        assert_eq!(response.condition.value, 2.0);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::application::auth::api_key::parse_scopes;
+use crate::infrastructure::rdb::diesel_model::diesel_api_key::ApiKey;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// APIキーの有効期限は最長10年
+const MAX_EXPIRES_IN_DAYS: u32 = 3650;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRequest {
+    pub name: String,
+    /// `onsen:write`などのパーミッション。ロールが持っていないものは指定できない
+    pub scopes: Vec<String>,
+    /// 省略すると期限なし
+    pub expires_in_days: Option<u32>,
+}
+
+impl ApiKeyRequest {
+    /// 期限なしならOk(None)。日数が0か長すぎればErr
+    pub fn expires_at(&self, now: DateTime<Utc>) -> Result<Option<NaiveDateTime>, ()> {
+        let Some(days) = self.expires_in_days else {
+            return Ok(None);
+        };
+        if days == 0 || days > MAX_EXPIRES_IN_DAYS {
+            return Err(());
+        }
+        now.checked_add_signed(Duration::days(days.into()))
+            .map(|v| Some(v.naive_utc()))
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyResponse {
+    pub id: u32,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(value: ApiKey) -> Self {
+        ApiKeyResponse {
+            id: value.id as u32,
+            name: value.name,
+            key_prefix: value.key_prefix,
+            scopes: parse_scopes(&value.scopes)
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            expires_at: value.expires_at.map(format_timestamp),
+            last_used_at: value.last_used_at.map(format_timestamp),
+            created_at: format_timestamp(value.created_at),
+        }
+    }
+}
+
+/// 作成したときだけ平文のキーを返す
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiKeyResponse {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+}
+
+/// UTCのRFC 3339
+fn format_timestamp(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiKeyRequest;
+    use chrono::{TimeZone, Utc};
+
+    fn request(expires_in_days: Option<u32>) -> ApiKeyRequest {
+        ApiKeyRequest {
+            name: "import".to_string(),
+            scopes: vec![],
+            expires_in_days,
+        }
+    }
+
+    #[test]
+    fn test_expires_at() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        assert_eq!(request(None).expires_at(now), Ok(None));
+        assert_eq!(
+            request(Some(90)).expires_at(now),
+            Ok(Some(
+                Utc.with_ymd_and_hms(2024, 4, 1, 3, 4, 5)
+                    .unwrap()
+                    .naive_utc()
+            ))
+        );
+        assert!(request(Some(3650)).expires_at(now).is_ok());
+        assert_eq!(request(Some(3651)).expires_at(now), Err(()));
+        assert_eq!(request(Some(0)).expires_at(now), Err(()));
+        assert_eq!(request(Some(u32::MAX)).expires_at(now), Err(()));
+    }
+}
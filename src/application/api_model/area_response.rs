@@ -1,4 +1,6 @@
+use crate::application::api_model::photo_api_model::PhotoResponse;
 use crate::domain::area_entity::AreaEntity;
+use crate::domain::photo_entity::PhotoEntity;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -14,6 +16,18 @@ pub struct AreaResponse {
     pub description: String,
     pub access: String,
     pub onsen_ids: Vec<u32>,
+    /// 一覧に表示するカバー写真。`thumbnails`を`srcset`に使う
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<PhotoResponse>,
+}
+
+impl AreaResponse {
+    pub fn with_cover(self, cover: Option<PhotoEntity>) -> Self {
+        Self {
+            cover: cover.map(PhotoResponse::from),
+            ..self
+        }
+    }
 }
 
 impl From<AreaEntity> for AreaResponse {
@@ -29,6 +43,7 @@ impl From<AreaEntity> for AreaResponse {
             description: value.description,
             access: value.access,
             onsen_ids: value.onsens.iter().map(|v| v.id).collect(),
+            cover: None,
         }
     }
 }
@@ -42,6 +57,7 @@ mod tests {
     use crate::domain::onsen::chemical::Chemical::*;
     use crate::domain::onsen::onsen_entity::OnsenEntity;
     use crate::domain::onsen::onsen_quality::OnsenQuality;
+    use crate::domain::photo_entity::{ImageFormat, PhotoEntity, PhotoTarget, ThumbnailStatus};
 
     const COMMON_ONSEN_QUALITY: Lazy<OnsenQuality> =
         Lazy::new(|| OnsenQuality::new(&vec![NaIon, CaIon, SO4Ion], None));
@@ -54,6 +70,7 @@ mod tests {
             Some(COMMON_ONSEN_QUALITY.clone()),
             "",
             Some("neutral"),
+            None,
             Some("hypotonic"),
             Some("hot"),
             "uchiyu",
@@ -88,5 +105,22 @@ mod tests {
         assert_eq!(response.description, "");
         assert_eq!(response.access, "");
         assert_eq!(response.onsen_ids, vec![2]);
+        assert!(response.cover.is_none());
+        let cover = PhotoEntity {
+            id: 9,
+            target: PhotoTarget::Area,
+            target_id: 1,
+            user_id: 1,
+            blob_key: "photos/abc.jpg".to_string(),
+            format: ImageFormat::Jpeg,
+            byte_size: 100,
+            caption: "".to_string(),
+            position: 0,
+            is_cover: true,
+            thumbnail_status: ThumbnailStatus::Ready,
+        };
+        let cover = response.with_cover(Some(cover)).cover.unwrap();
+        assert_eq!(cover.url, "/images/9");
+        assert_eq!(cover.thumbnails[0].url, "/images/9/320");
     }
 }
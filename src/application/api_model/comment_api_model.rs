@@ -0,0 +1,207 @@
+use crate::application::api_model::profile_api_model::ProfileSummaryResponse;
+use crate::domain::comment_entity::{
+    CommentEntity, CommentReportEntity, CommentStatus, CommentTarget, ReportReason,
+};
+use crate::domain::notification_entity::NotificationEntity;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentRequest {
+    pub body: String,
+    /// 返信のときに返信先のコメント
+    pub parent_id: Option<u32>,
+}
+
+impl CommentRequest {
+    pub fn create_entity(
+        &self,
+        target: CommentTarget,
+        target_id: u32,
+        user_id: u32,
+        now: NaiveDateTime,
+    ) -> Option<CommentEntity> {
+        CommentEntity::new(target, target_id, user_id, self.parent_id, &self.body, now)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentEditRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentReportRequest {
+    /// `spam`・`harassment`・`inappropriate`・`other`
+    pub reason: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl CommentReportRequest {
+    pub fn create_entity(&self, comment_id: u32, user_id: u32) -> Option<CommentReportEntity> {
+        let reason = ReportReason::from_str(&self.reason).ok()?;
+        CommentReportEntity::new(comment_id, user_id, reason, &self.note)
+    }
+}
+
+/// `visible`か`hidden`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentStatusRequest {
+    pub status: String,
+}
+
+impl CommentStatusRequest {
+    pub fn status(&self) -> Option<CommentStatus> {
+        CommentStatus::from_str(&self.status)
+            .ok()
+            .filter(|v| *v != CommentStatus::Deleted)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentResponse {
+    pub id: u32,
+    pub target: String,
+    pub target_id: u32,
+    pub parent_id: Option<u32>,
+    /// プロフィールがなくなっていればnull
+    pub author: Option<ProfileSummaryResponse>,
+    /// 非表示・削除済みのコメントは空
+    pub body: String,
+    pub status: String,
+    pub created_at: String,
+    /// 書き換えていなければnull
+    pub edited_at: Option<String>,
+}
+
+impl CommentResponse {
+    /// `reveal`は非表示にしたコメントの本文も返すとき。管理者向け
+    pub fn new(
+        comment: CommentEntity,
+        author: Option<ProfileSummaryResponse>,
+        reveal: bool,
+    ) -> Self {
+        let format = |v: NaiveDateTime| v.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let is_shown = comment.status == CommentStatus::Visible || reveal;
+        CommentResponse {
+            id: comment.id,
+            target: comment.target.to_string(),
+            target_id: comment.target_id,
+            parent_id: comment.parent_id,
+            author,
+            body: if is_shown {
+                comment.body
+            } else {
+                String::new()
+            },
+            status: comment.status.to_string(),
+            created_at: format(comment.created_at),
+            edited_at: (comment.updated_at != comment.created_at)
+                .then(|| format(comment.updated_at)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentThreadResponse {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    pub replies: Vec<CommentResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentReportResponse {
+    pub reason: String,
+    pub note: String,
+}
+
+impl From<CommentReportEntity> for CommentReportResponse {
+    fn from(value: CommentReportEntity) -> Self {
+        CommentReportResponse {
+            reason: value.reason.to_string(),
+            note: value.note,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedCommentResponse {
+    pub comment: CommentResponse,
+    pub reports: Vec<CommentReportResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationResponse {
+    pub id: u32,
+    /// `comment`・`reply`
+    pub kind: String,
+    pub actor: Option<ProfileSummaryResponse>,
+    pub comment: CommentResponse,
+    pub is_read: bool,
+}
+
+impl NotificationResponse {
+    pub fn new(
+        notification: NotificationEntity,
+        actor: Option<ProfileSummaryResponse>,
+        comment: CommentResponse,
+    ) -> Self {
+        NotificationResponse {
+            id: notification.id,
+            kind: notification.kind.to_string(),
+            actor,
+            comment,
+            is_read: notification.is_read,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommentResponse, CommentStatusRequest};
+    use crate::domain::comment_entity::{CommentEntity, CommentStatus, CommentTarget};
+    use chrono::{Duration, NaiveDate};
+
+    #[test]
+    fn test_comment_response() {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let comment =
+            CommentEntity::new(CommentTarget::Visit, 1, 2, None, "いいお湯", now).unwrap();
+        let response = CommentResponse::new(comment.clone(), None, false);
+        assert_eq!(response.body, "いいお湯");
+        assert_eq!(response.created_at, "2026-10-01T09:00:00Z");
+        assert_eq!(response.edited_at, None);
+        let hidden = CommentEntity {
+            status: CommentStatus::Hidden,
+            updated_at: now + Duration::minutes(5),
+            ..comment
+        };
+        let response = CommentResponse::new(hidden.clone(), None, false);
+        assert_eq!(response.body, "");
+        assert_eq!(response.edited_at.as_deref(), Some("2026-10-01T09:05:00Z"));
+        assert_eq!(CommentResponse::new(hidden, None, true).body, "いいお湯");
+    }
+
+    #[test]
+    fn test_comment_status_request() {
+        let request = |status: &str| CommentStatusRequest {
+            status: status.to_string(),
+        };
+        assert_eq!(request("hidden").status(), Some(CommentStatus::Hidden));
+        assert_eq!(request("deleted").status(), None);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::infrastructure::repository::health_repository::Readiness;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub database: bool,
+    pub migrations: bool,
+}
+
+impl ReadinessResponse {
+    pub fn is_ready(&self) -> bool {
+        self.database && self.migrations
+    }
+}
+
+impl From<Readiness> for ReadinessResponse {
+    fn from(value: Readiness) -> Self {
+        let status = if value.database && value.migrations {
+            "ok"
+        } else {
+            "unavailable"
+        };
+        Self {
+            status: status.to_string(),
+            database: value.database,
+            migrations: value.migrations,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionResponse {
+    pub version: String,
+    pub git_sha: String,
+    pub build_time: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::api_model::health_response::ReadinessResponse;
+    use crate::infrastructure::repository::health_repository::Readiness;
+
+    #[test]
+    fn test_readiness_response_ready() {
+        let response = ReadinessResponse::from(Readiness {
+            database: true,
+            migrations: true,
+        });
+        assert_eq!(response.status, "ok");
+        assert!(response.is_ready());
+    }
+
+    #[test]
+    fn test_readiness_response_pending_migrations() {
+        let response = ReadinessResponse::from(Readiness {
+            database: true,
+            migrations: false,
+        });
+        assert_eq!(response.status, "unavailable");
+        assert!(response.database);
+        assert!(!response.migrations);
+        assert!(!response.is_ready());
+    }
+}
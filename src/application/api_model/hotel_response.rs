@@ -1,5 +1,7 @@
 use crate::application::api_model::onsen_response::OnsenResponse;
+use crate::application::api_model::review_api_model::HotelRatingResponseModel;
 use crate::domain::hotel_entity::HotelEntity;
+use crate::domain::review_entity::HotelRatingSummary;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -12,6 +14,9 @@ pub struct HotelResponse {
     pub url: String,
     pub description: String,
     pub onsens: Vec<OnsenResponse>,
+    /// 評価を集計したときだけ返す
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<HotelRatingResponseModel>,
 }
 
 impl From<HotelEntity> for HotelResponse {
@@ -28,6 +33,16 @@ impl From<HotelEntity> for HotelResponse {
                 .iter()
                 .map(|v| OnsenResponse::create(v.clone(), None))
                 .collect(),
+            rating: None,
+        }
+    }
+}
+
+impl HotelResponse {
+    pub fn with_rating(self, rating: HotelRatingSummary) -> Self {
+        Self {
+            rating: Some(HotelRatingResponseModel::from(rating)),
+            ..self
         }
     }
 }
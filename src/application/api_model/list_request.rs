@@ -0,0 +1,71 @@
+use crate::domain::list_entity::{ListItemEntity, ListItemTarget};
+use serde::Deserialize;
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListItemRequest {
+    /// `onsen`・`hotel`・`area`
+    pub target: String,
+    pub target_id: u32,
+}
+
+impl ListItemRequest {
+    pub fn create_entity(&self) -> Option<ListItemEntity> {
+        Some(ListItemEntity {
+            target: ListItemTarget::from_str(&self.target).ok()?,
+            target_id: self.target_id,
+        })
+    }
+}
+
+/// 並べ替えたあとの項目をすべて送る
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListItemsRequest {
+    pub items: Vec<ListItemRequest>,
+}
+
+impl ListItemsRequest {
+    pub fn create_entities(&self) -> Option<Vec<ListItemEntity>> {
+        self.items.iter().map(|v| v.create_entity()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListItemRequest, ListItemsRequest};
+    use crate::domain::list_entity::ListItemTarget;
+
+    #[test]
+    fn test_create_entities() {
+        let request = ListItemsRequest {
+            items: vec![
+                ListItemRequest {
+                    target: "area".to_string(),
+                    target_id: 1,
+                },
+                ListItemRequest {
+                    target: "onsen".to_string(),
+                    target_id: 2,
+                },
+            ],
+        };
+        let items = request.create_entities().unwrap();
+        assert_eq!(items[0].target, ListItemTarget::Area);
+        assert_eq!(items[1].target_id, 2);
+        let request = ListItemsRequest {
+            items: vec![ListItemRequest {
+                target: "spa".to_string(),
+                target_id: 1,
+            }],
+        };
+        assert!(request.create_entities().is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::application::api_model::summary_response::*;
+use crate::domain::list_entity::{ListEntity, ListItemEntity};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResponse {
+    pub id: u32,
+    pub kind: String,
+    pub name: String,
+    /// 共有していなければnull
+    pub share_token: Option<String>,
+    pub items: Vec<ListItemResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListItemResponse {
+    pub target: String,
+    pub target_id: u32,
+}
+
+impl From<ListItemEntity> for ListItemResponse {
+    fn from(value: ListItemEntity) -> Self {
+        Self {
+            target: value.target.to_string(),
+            target_id: value.target_id,
+        }
+    }
+}
+
+impl From<ListEntity> for ListResponse {
+    fn from(value: ListEntity) -> Self {
+        Self {
+            id: value.id,
+            kind: value.kind.to_string(),
+            name: value.name,
+            share_token: value.share_token,
+            items: value
+                .items
+                .into_iter()
+                .map(ListItemResponse::from)
+                .collect(),
+        }
+    }
+}
+
+/// `GET /me/lists/<id>`などで返す、温泉・宿・温泉地の概要を展開したリスト
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDetailResponse {
+    pub id: u32,
+    pub kind: String,
+    pub name: String,
+    /// 共有リンクから見たときは含めない
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_token: Option<String>,
+    pub items: Vec<ListItemDetailResponse>,
+}
+
+/// `target`に応じて`onsen`・`hotel`・`area`のどれか1つが入る
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListItemDetailResponse {
+    pub target: String,
+    pub target_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onsen: Option<OnsenSummaryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotel: Option<HotelSummaryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<AreaSummaryResponse>,
+}
@@ -1,7 +1,22 @@
+pub mod achievement_api_model;
+pub mod api_key_api_model;
 pub mod area_request;
 pub mod area_response;
+pub mod comment_api_model;
+pub mod health_response;
 pub mod hotel_request;
 pub mod hotel_response;
+pub mod list_request;
+pub mod list_response;
 pub mod onsen_request;
 pub mod onsen_response;
+pub mod photo_api_model;
+pub mod profile_api_model;
+pub mod review_api_model;
+pub mod stats_response;
+pub mod summary_response;
+pub mod trip_request;
+pub mod trip_response;
 pub mod user_api_model;
+pub mod visit_request;
+pub mod visit_response;
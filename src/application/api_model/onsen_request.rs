@@ -15,6 +15,7 @@ pub struct OnsenRequest {
     pub other_spring_quality: String,
     pub osmotic_pressure: Option<String>,
     pub liquid: Option<String>,
+    pub ph: Option<f64>,
     pub temperature: Option<String>,
     pub form: String,
     pub is_day_use: bool,
@@ -106,6 +107,7 @@ impl OnsenRequest {
             quality,
             self.other_spring_quality.as_str(),
             self.liquid.as_deref(),
+            self.ph,
             self.osmotic_pressure.as_deref(),
             self.temperature.as_deref(),
             self.form.as_str(),
@@ -150,6 +152,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -205,6 +208,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -254,6 +258,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -303,6 +308,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -352,6 +358,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -401,6 +408,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -453,6 +461,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
@@ -506,6 +515,7 @@ mod tests {
             }),
             other_spring_quality: "温泉法の温泉".to_string(),
             liquid: Some("neutral".to_string()),
+            ph: None,
             osmotic_pressure: Some("hypotonic".to_string()),
             temperature: Some("hot".to_string()),
             form: "uchiyu".to_string(),
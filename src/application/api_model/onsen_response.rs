@@ -1,3 +1,7 @@
+use crate::application::api_model::photo_api_model::PhotoResponse;
+use crate::application::api_model::review_api_model::OnsenRatingResponseModel;
+use crate::domain::photo_entity::PhotoEntity;
+use crate::domain::review_entity::OnsenRatingSummary;
 use crate::domain::{area_entity::AreaEntity, onsen::onsen_entity::OnsenEntity};
 use serde::Serialize;
 
@@ -9,6 +13,7 @@ pub struct OnsenResponse {
     pub quality: Option<OnsenQualityResponseModel>,
     pub other_spring_quality: String,
     pub liquid: Option<String>,
+    pub ph: Option<f64>,
     pub osmotic_pressure: Option<String>,
     pub temperature: Option<String>,
     pub form: String,
@@ -17,6 +22,12 @@ pub struct OnsenResponse {
     pub img_url: Option<String>,
     pub description: String,
     pub area: Option<OnsenAreaResponseModel>,
+    /// 評価を集計したときだけ返す
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<OnsenRatingResponseModel>,
+    /// 一覧に表示するカバー写真。`thumbnails`を`srcset`に使う
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<PhotoResponse>,
 }
 
 #[derive(Debug, Serialize)]
@@ -50,6 +61,7 @@ impl OnsenResponse {
             }),
             other_spring_quality: onsen.spring_quality.clone(),
             liquid: onsen.liquid.as_ref().map(|v| v.to_string()),
+            ph: onsen.ph,
             osmotic_pressure: onsen.osmotic_pressure.as_ref().map(|v| v.to_string()),
             temperature: onsen.temperature.as_ref().map(|v| v.to_string()),
             form: onsen.form.to_string(),
@@ -61,6 +73,22 @@ impl OnsenResponse {
                 id: v.id,
                 name: v.name.clone(),
             }),
+            rating: None,
+            cover: None,
+        }
+    }
+
+    pub fn with_rating(self, rating: OnsenRatingSummary) -> Self {
+        Self {
+            rating: Some(OnsenRatingResponseModel::from(rating)),
+            ..self
+        }
+    }
+
+    pub fn with_cover(self, cover: Option<PhotoEntity>) -> Self {
+        Self {
+            cover: cover.map(PhotoResponse::from),
+            ..self
         }
     }
 }
@@ -85,6 +113,7 @@ mod tests {
             Some(COMMON_ONSEN_QUALITY.clone()),
             "",
             Some("neutral"),
+            None,
             Some("hypotonic"),
             Some("hot"),
             "uchiyu",
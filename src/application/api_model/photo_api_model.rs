@@ -0,0 +1,140 @@
+// `FromForm`の展開したコードにclippyが反応する
+#![allow(clippy::blocks_in_conditions)]
+
+use crate::domain::photo_entity::{PhotoEntity, PhotoTarget, THUMBNAIL_WIDTHS};
+use rocket::data::Capped;
+use rocket::fs::TempFile;
+use rocket::http::{ContentType, Header};
+use rocket::response::Redirect;
+use serde::{Deserialize, Serialize};
+
+/// multipart/form-dataで受け取る。サイズの上限はRocket.tomlの`limits.file`
+#[derive(Debug, FromForm)]
+pub struct PhotoUploadForm<'r> {
+    pub file: Capped<TempFile<'r>>,
+    pub caption: Option<String>,
+    #[field(name = "isCover")]
+    pub is_cover: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoRequest {
+    #[serde(default)]
+    pub caption: String,
+    #[serde(default)]
+    pub is_cover: bool,
+    /// 0始まりの並び順
+    pub position: u32,
+}
+
+impl PhotoRequest {
+    pub fn apply(&self, photo: PhotoEntity) -> Option<PhotoEntity> {
+        if !PhotoEntity::is_valid_caption(&self.caption) {
+            return None;
+        }
+        Some(PhotoEntity {
+            caption: self.caption.clone(),
+            is_cover: self.is_cover,
+            position: self.position,
+            ..photo
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoResponse {
+    pub id: u32,
+    pub url: String,
+    pub content_type: String,
+    pub byte_size: u32,
+    pub caption: String,
+    pub position: u32,
+    pub is_cover: bool,
+    pub thumbnails: Vec<ThumbnailResponse>,
+}
+
+/// `srcset`用。できるまでは元の画像にリダイレクトする
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailResponse {
+    pub width: u32,
+    pub url: String,
+    pub webp_url: String,
+}
+
+impl From<PhotoEntity> for PhotoResponse {
+    fn from(value: PhotoEntity) -> Self {
+        PhotoResponse {
+            id: value.id,
+            url: format!("/images/{}", value.id),
+            content_type: value.format.to_string(),
+            byte_size: value.byte_size,
+            caption: value.caption,
+            position: value.position,
+            is_cover: value.is_cover,
+            thumbnails: THUMBNAIL_WIDTHS
+                .iter()
+                .map(|width| ThumbnailResponse {
+                    width: *width,
+                    url: format!("/images/{}/{}", value.id, width),
+                    webp_url: format!("/images/{}/{}.webp", value.id, width),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 画像のバイナリ。キーは変わらないので長くキャッシュさせる
+#[derive(Debug, Responder)]
+pub struct ImageResponse {
+    pub body: Vec<u8>,
+    pub content_type: ContentType,
+    pub cache_control: Header<'static>,
+}
+
+impl ImageResponse {
+    pub fn new(body: Vec<u8>, content_type: ContentType, target: PhotoTarget) -> Self {
+        ImageResponse {
+            body,
+            content_type,
+            cache_control: Header::new("Cache-Control", cache_control(target)),
+        }
+    }
+}
+
+/// 温泉と地域の写真は変わらないので長くキャッシュする。入浴記録の写真とアイコンは
+/// あとから非公開にできるので、共有キャッシュに載せず短い時間だけにする
+fn cache_control(target: PhotoTarget) -> &'static str {
+    match target {
+        PhotoTarget::Onsen | PhotoTarget::Area => "public, max-age=31536000, immutable",
+        PhotoTarget::Visit | PhotoTarget::Avatar => "private, max-age=300",
+    }
+}
+
+#[derive(Debug, Responder)]
+pub enum ThumbnailImageResponse {
+    Image(ImageResponse),
+    /// サムネイルがまだないか作れなかったときは元の画像
+    Original(Redirect),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::api_model::photo_api_model::*;
+
+    #[test]
+    fn test_cache_control() {
+        assert_eq!(
+            cache_control(PhotoTarget::Onsen),
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(
+            cache_control(PhotoTarget::Area),
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(cache_control(PhotoTarget::Visit), "private, max-age=300");
+        assert_eq!(cache_control(PhotoTarget::Avatar), "private, max-age=300");
+    }
+}
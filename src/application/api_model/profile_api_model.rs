@@ -0,0 +1,223 @@
+use crate::application::api_model::review_api_model::{HotelReviewResponse, OnsenReviewResponse};
+use crate::application::api_model::trip_response::TripResponse;
+use crate::application::api_model::visit_response::VisitResponse;
+use crate::domain::feed::{FeedContent, FeedItem};
+use crate::domain::photo_entity::PhotoEntity;
+use crate::domain::profile_entity::{PrivacySettings, ProfileEntity, Visibility};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// 公開範囲は`public`・`followers`・`private`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrivacyModel {
+    pub profile: String,
+    pub visits: String,
+    pub trips: String,
+    pub reviews: String,
+    pub approve_followers: bool,
+}
+
+impl Default for PrivacyModel {
+    fn default() -> Self {
+        PrivacyModel::from(PrivacySettings::default())
+    }
+}
+
+impl From<PrivacySettings> for PrivacyModel {
+    fn from(value: PrivacySettings) -> Self {
+        PrivacyModel {
+            profile: value.profile.to_string(),
+            visits: value.visits.to_string(),
+            trips: value.trips.to_string(),
+            reviews: value.reviews.to_string(),
+            approve_followers: value.approve_followers,
+        }
+    }
+}
+
+impl PrivacyModel {
+    fn create_settings(&self) -> Option<PrivacySettings> {
+        Some(PrivacySettings {
+            profile: Visibility::from_str(&self.profile).ok()?,
+            visits: Visibility::from_str(&self.visits).ok()?,
+            trips: Visibility::from_str(&self.trips).ok()?,
+            reviews: Visibility::from_str(&self.reviews).ok()?,
+            approve_followers: self.approve_followers,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileRequest {
+    pub handle: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub bio: String,
+    #[serde(default)]
+    pub privacy: PrivacyModel,
+}
+
+impl ProfileRequest {
+    pub fn create_entity(&self, user_id: u32) -> Option<ProfileEntity> {
+        ProfileEntity::new(
+            user_id,
+            &self.handle,
+            &self.display_name,
+            &self.bio,
+            self.privacy.create_settings()?,
+        )
+    }
+}
+
+/// 一覧やタイムラインに載せる最小限のプロフィール
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSummaryResponse {
+    pub handle: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+}
+
+impl ProfileSummaryResponse {
+    pub fn new(profile: ProfileEntity, avatar: Option<&PhotoEntity>) -> Self {
+        ProfileSummaryResponse {
+            handle: profile.handle,
+            display_name: profile.display_name,
+            avatar_url: avatar.map(avatar_url),
+        }
+    }
+}
+
+fn avatar_url(avatar: &PhotoEntity) -> String {
+    format!("/images/{}", avatar.id)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileResponse {
+    pub handle: String,
+    pub display_name: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    pub follower_count: u32,
+    pub following_count: u32,
+    /// 本人にだけ返す
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<PrivacyModel>,
+}
+
+impl ProfileResponse {
+    pub fn new(
+        profile: ProfileEntity,
+        avatar: Option<&PhotoEntity>,
+        (follower_count, following_count): (u32, u32),
+        is_owner: bool,
+    ) -> Self {
+        ProfileResponse {
+            avatar_url: avatar.map(avatar_url),
+            follower_count,
+            following_count,
+            privacy: is_owner.then(|| PrivacyModel::from(profile.privacy)),
+            handle: profile.handle,
+            display_name: profile.display_name,
+            bio: profile.bio,
+        }
+    }
+}
+
+/// 公開範囲の外にある項目はnull
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDetailResponse {
+    #[serde(flatten)]
+    pub profile: ProfileResponse,
+    /// ログインしていてフォローしていれば`pending`か`accepted`
+    pub follow_status: Option<String>,
+    pub visits: Option<Vec<VisitResponse>>,
+    pub trips: Option<Vec<TripResponse>>,
+    pub onsen_reviews: Option<Vec<OnsenReviewResponse>>,
+    pub hotel_reviews: Option<Vec<HotelReviewResponse>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowResponse {
+    pub profile: ProfileSummaryResponse,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedItemResponse {
+    /// `visit`・`onsenReview`・`hotelReview`
+    pub kind: String,
+    pub posted_at: String,
+    pub profile: ProfileSummaryResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visit: Option<VisitResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onsen_review: Option<OnsenReviewResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotel_review: Option<HotelReviewResponse>,
+}
+
+impl FeedItemResponse {
+    pub fn new(item: FeedItem, profile: ProfileSummaryResponse) -> Self {
+        let mut response = FeedItemResponse {
+            kind: item.content.kind().to_string(),
+            posted_at: item.posted_at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            profile,
+            visit: None,
+            onsen_review: None,
+            hotel_review: None,
+        };
+        match item.content {
+            FeedContent::Visit(visit) => {
+                response.visit = Some(VisitResponse::from(visit));
+            }
+            FeedContent::OnsenReview(review) => {
+                response.onsen_review = Some(OnsenReviewResponse::from(review));
+            }
+            FeedContent::HotelReview(review) => {
+                response.hotel_review = Some(HotelReviewResponse::from(review));
+            }
+        }
+        response
+    }
+}
+
+/// 続きは`nextBefore`を`before`に指定して読み込む
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedResponse {
+    pub items: Vec<FeedItemResponse>,
+    pub next_before: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrivacyModel, ProfileRequest};
+    use crate::domain::profile_entity::Visibility;
+
+    #[test]
+    fn test_profile_request_create_entity() {
+        let request: ProfileRequest = serde_json::from_str(
+            r#"{"handle":"onsen","displayName":"温泉","privacy":{"visits":"followers"}}"#,
+        )
+        .unwrap();
+        let profile = request.create_entity(1).unwrap();
+        assert_eq!(profile.privacy.visits, Visibility::Followers);
+        assert_eq!(profile.privacy.profile, Visibility::Public);
+        assert_eq!(profile.bio, "");
+        let request = ProfileRequest {
+            privacy: PrivacyModel {
+                trips: "friends".to_string(),
+                ..PrivacyModel::default()
+            },
+            ..request
+        };
+        assert!(request.create_entity(1).is_none());
+    }
+}
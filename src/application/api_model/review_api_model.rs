@@ -0,0 +1,219 @@
+use crate::domain::review_entity::{
+    HotelRatingSummary, HotelReviewEntity, OnsenRatingSummary, OnsenReviewEntity, ReviewStatus,
+};
+use serde::{Deserialize, Serialize};
+
+/// 評価は1から5
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnsenReviewRequest {
+    pub overall: u8,
+    pub water_quality: u8,
+    pub cleanliness: u8,
+    pub view: u8,
+    /// 5が空いている
+    pub crowding: u8,
+    #[serde(default)]
+    pub comment: String,
+}
+
+impl OnsenReviewRequest {
+    /// 本文が変わっていなければ今の公開状態を引き継ぐ
+    pub fn create_entity(
+        &self,
+        user_id: u32,
+        onsen_id: u32,
+        current: Option<OnsenReviewEntity>,
+    ) -> Option<OnsenReviewEntity> {
+        let comment = self.comment.trim();
+        let status = match current {
+            Some(current) if current.comment == comment => current.status,
+            _ => ReviewStatus::for_comment(comment),
+        };
+        OnsenReviewEntity::new(
+            0,
+            user_id,
+            onsen_id,
+            self.overall,
+            self.water_quality,
+            self.cleanliness,
+            self.view,
+            self.crowding,
+            comment,
+            status,
+        )
+    }
+}
+
+/// 評価は1から5
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelReviewRequest {
+    pub room: u8,
+    pub food: u8,
+    pub bath: u8,
+    #[serde(default)]
+    pub comment: String,
+}
+
+impl HotelReviewRequest {
+    /// 本文が変わっていなければ今の公開状態を引き継ぐ
+    pub fn create_entity(
+        &self,
+        user_id: u32,
+        hotel_id: u32,
+        current: Option<HotelReviewEntity>,
+    ) -> Option<HotelReviewEntity> {
+        let comment = self.comment.trim();
+        let status = match current {
+            Some(current) if current.comment == comment => current.status,
+            _ => ReviewStatus::for_comment(comment),
+        };
+        HotelReviewEntity::new(
+            0, user_id, hotel_id, self.room, self.food, self.bath, comment, status,
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewStatusRequest {
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnsenReviewResponse {
+    pub id: u32,
+    pub user_id: u32,
+    pub onsen_id: u32,
+    pub overall: u8,
+    pub water_quality: u8,
+    pub cleanliness: u8,
+    pub view: u8,
+    pub crowding: u8,
+    pub comment: String,
+    pub status: String,
+}
+
+impl From<OnsenReviewEntity> for OnsenReviewResponse {
+    fn from(value: OnsenReviewEntity) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            onsen_id: value.onsen_id,
+            overall: value.overall,
+            water_quality: value.water_quality,
+            cleanliness: value.cleanliness,
+            view: value.view,
+            crowding: value.crowding,
+            comment: value.comment,
+            status: value.status.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelReviewResponse {
+    pub id: u32,
+    pub user_id: u32,
+    pub hotel_id: u32,
+    pub room: u8,
+    pub food: u8,
+    pub bath: u8,
+    pub comment: String,
+    pub status: String,
+}
+
+impl From<HotelReviewEntity> for HotelReviewResponse {
+    fn from(value: HotelReviewEntity) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            hotel_id: value.hotel_id,
+            room: value.room,
+            food: value.food,
+            bath: value.bath,
+            comment: value.comment,
+            status: value.status.to_string(),
+        }
+    }
+}
+
+/// 承認待ちなどの口コミの一覧
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewQueueResponse {
+    pub onsen_reviews: Vec<OnsenReviewResponse>,
+    pub hotel_reviews: Vec<HotelReviewResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnsenRatingResponseModel {
+    pub count: u32,
+    pub overall: Option<f64>,
+    pub water_quality: Option<f64>,
+    pub cleanliness: Option<f64>,
+    pub view: Option<f64>,
+    pub crowding: Option<f64>,
+}
+
+impl From<OnsenRatingSummary> for OnsenRatingResponseModel {
+    fn from(value: OnsenRatingSummary) -> Self {
+        Self {
+            count: value.count,
+            overall: value.overall,
+            water_quality: value.water_quality,
+            cleanliness: value.cleanliness,
+            view: value.view,
+            crowding: value.crowding,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelRatingResponseModel {
+    pub count: u32,
+    pub room: Option<f64>,
+    pub food: Option<f64>,
+    pub bath: Option<f64>,
+}
+
+impl From<HotelRatingSummary> for HotelRatingResponseModel {
+    fn from(value: HotelRatingSummary) -> Self {
+        Self {
+            count: value.count,
+            room: value.room,
+            food: value.food,
+            bath: value.bath,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OnsenReviewRequest;
+    use crate::domain::review_entity::ReviewStatus;
+
+    #[test]
+    fn test_onsen_review_request_create_entity() {
+        let request = OnsenReviewRequest {
+            overall: 5,
+            water_quality: 4,
+            cleanliness: 3,
+            view: 2,
+            crowding: 1,
+            comment: " 最高 ".to_string(),
+        };
+        let review = request.create_entity(1, 2, None).unwrap();
+        assert_eq!(review.comment, "最高");
+        assert_eq!(review.status, ReviewStatus::Pending);
+        let mut approved = review.clone();
+        approved.status = ReviewStatus::Approved;
+        let review = request.create_entity(1, 2, Some(approved)).unwrap();
+        assert_eq!(review.status, ReviewStatus::Approved);
+    }
+}
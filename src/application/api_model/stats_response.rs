@@ -0,0 +1,77 @@
+use crate::application::api_model::summary_response::AreaSummaryResponse;
+use crate::domain::area_entity::AreaEntity;
+use crate::domain::onsen::spring_category::SpringCategory;
+use crate::domain::visit_stats::{VisitStats, PREFECTURE_COUNT};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsResponse {
+    pub visit_count: usize,
+    pub onsen_count: usize,
+    pub area_count: usize,
+    pub prefecture_count: usize,
+    /// 47
+    pub prefecture_total: usize,
+    pub prefectures: Vec<String>,
+    pub spring_category_count: usize,
+    /// 10
+    pub spring_category_total: usize,
+    /// 療養泉の10の分類すべて。入ったことがあるかどうか
+    pub spring_categories: Vec<SpringCategoryResponse>,
+    pub national_resort_count: usize,
+    pub national_resorts: Vec<AreaSummaryResponse>,
+    /// 古い年から
+    pub visits_per_year: Vec<YearCountResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpringCategoryResponse {
+    pub category: String,
+    pub name: String,
+    pub experienced: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YearCountResponse {
+    pub year: i32,
+    pub count: usize,
+}
+
+impl StatsResponse {
+    /// `areas`は`stats.national_resort_ids`の温泉地を含むこと
+    pub fn create(stats: VisitStats, areas: &[AreaEntity]) -> Self {
+        Self {
+            visit_count: stats.visit_count,
+            onsen_count: stats.onsen_ids.len(),
+            area_count: stats.area_ids.len(),
+            prefecture_count: stats.prefectures.len(),
+            prefecture_total: PREFECTURE_COUNT,
+            prefectures: stats.prefectures,
+            spring_category_count: stats.spring_categories.len(),
+            spring_category_total: SpringCategory::ALL.len(),
+            spring_categories: SpringCategory::ALL
+                .into_iter()
+                .map(|v| SpringCategoryResponse {
+                    category: v.to_string(),
+                    name: v.jp().to_string(),
+                    experienced: stats.spring_categories.contains(&v),
+                })
+                .collect(),
+            national_resort_count: stats.national_resort_ids.len(),
+            national_resorts: stats
+                .national_resort_ids
+                .iter()
+                .filter_map(|id| areas.iter().find(|v| v.id == *id))
+                .map(|v| AreaSummaryResponse::from(v.clone()))
+                .collect(),
+            visits_per_year: stats
+                .visits_per_year
+                .into_iter()
+                .map(|(year, count)| YearCountResponse { year, count })
+                .collect(),
+        }
+    }
+}
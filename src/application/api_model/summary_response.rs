@@ -0,0 +1,87 @@
+use crate::domain::{
+    area_entity::AreaEntity, hotel_entity::HotelEntity, onsen::onsen_entity::OnsenEntity,
+};
+use serde::Serialize;
+
+/// 一覧に埋め込む温泉の概要
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnsenSummaryResponse {
+    pub id: u32,
+    pub name: String,
+    pub quality: Option<String>,
+    pub img_url: Option<String>,
+    pub area_id: Option<u32>,
+}
+
+impl From<OnsenEntity> for OnsenSummaryResponse {
+    fn from(value: OnsenEntity) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            quality: value.quality.map(|v| v.to_string()),
+            img_url: value.img_url,
+            area_id: value.area_id,
+        }
+    }
+}
+
+/// 一覧に埋め込む宿の概要
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotelSummaryResponse {
+    pub id: u32,
+    pub name: String,
+    pub url: String,
+}
+
+impl From<HotelEntity> for HotelSummaryResponse {
+    fn from(value: HotelEntity) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            url: value.url,
+        }
+    }
+}
+
+/// 一覧に埋め込む温泉地の概要
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AreaSummaryResponse {
+    pub id: u32,
+    pub name: String,
+    pub prefecture: String,
+    pub national_resort: bool,
+}
+
+impl From<AreaEntity> for AreaSummaryResponse {
+    fn from(value: AreaEntity) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefecture: value.prefecture,
+            national_resort: value.national_resort,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AreaSummaryResponse, HotelSummaryResponse};
+    use crate::domain::{area_entity::AreaEntity, hotel_entity::HotelEntity};
+
+    #[test]
+    fn test_summary_response_from() {
+        let hotel =
+            HotelEntity::new(1, "積善館", true, false, "https://example.com", "", &[]).unwrap();
+        let response = HotelSummaryResponse::from(hotel);
+        assert_eq!(response.id, 1);
+        assert_eq!(response.name, "積善館");
+        let area =
+            AreaEntity::new(2, "四万", "しま", "群馬県", true, None, "", "", "", vec![]).unwrap();
+        let response = AreaSummaryResponse::from(area);
+        assert_eq!(response.prefecture, "群馬県");
+        assert!(response.national_resort);
+    }
+}
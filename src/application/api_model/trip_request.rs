@@ -0,0 +1,86 @@
+use crate::domain::trip_entity::{TripDayEntity, TripEntity};
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripRequest {
+    pub title: String,
+    /// `2024-01-02`
+    pub start_on: String,
+    pub end_on: String,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub is_public: bool,
+    /// 1日目から順に並べる
+    #[serde(default)]
+    pub days: Vec<TripDayRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDayRequest {
+    pub hotel_id: Option<u32>,
+    #[serde(default)]
+    pub visit_ids: Vec<u32>,
+    #[serde(default)]
+    pub area_ids: Vec<u32>,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl TripRequest {
+    pub fn create_entity(&self, id: u32, user_id: u32) -> Option<TripEntity> {
+        let start_on = NaiveDate::parse_from_str(&self.start_on, "%Y-%m-%d").ok()?;
+        let end_on = NaiveDate::parse_from_str(&self.end_on, "%Y-%m-%d").ok()?;
+        let days = self
+            .days
+            .iter()
+            .map(|v| TripDayEntity {
+                hotel_id: v.hotel_id,
+                visit_ids: v.visit_ids.clone(),
+                area_ids: v.area_ids.clone(),
+                note: v.note.clone(),
+            })
+            .collect();
+        TripEntity::new(
+            id,
+            user_id,
+            self.title.as_str(),
+            start_on,
+            end_on,
+            self.note.as_str(),
+            self.is_public,
+            days,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TripDayRequest, TripRequest};
+
+    #[test]
+    fn test_trip_request_create_entity() {
+        let request = TripRequest {
+            title: "草津".to_string(),
+            start_on: "2024-01-02".to_string(),
+            end_on: "2024-01-03".to_string(),
+            note: "".to_string(),
+            is_public: true,
+            days: vec![TripDayRequest {
+                hotel_id: Some(1),
+                visit_ids: vec![2, 3],
+                area_ids: vec![4],
+                note: "湯畑".to_string(),
+            }],
+        };
+        let entity = request.create_entity(1, 2).unwrap();
+        assert_eq!(entity.title, "草津");
+        assert_eq!(entity.day_count(), 2);
+        assert_eq!(entity.days[0].hotel_id, Some(1));
+        assert_eq!(entity.days[0].visit_ids, vec![2, 3]);
+        assert_eq!(entity.days[0].note, "湯畑");
+    }
+}
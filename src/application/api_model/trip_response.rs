@@ -0,0 +1,118 @@
+use crate::application::api_model::summary_response::*;
+use crate::application::api_model::visit_response::VisitResponse;
+use crate::domain::trip_entity::TripEntity;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripResponse {
+    pub id: u32,
+    pub title: String,
+    pub start_on: String,
+    pub end_on: String,
+    pub note: String,
+    pub is_public: bool,
+    pub days: Vec<TripDayResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDayResponse {
+    pub day_number: u32,
+    pub date: String,
+    pub hotel_id: Option<u32>,
+    pub visit_ids: Vec<u32>,
+    pub area_ids: Vec<u32>,
+    pub note: String,
+}
+
+impl From<TripEntity> for TripResponse {
+    fn from(value: TripEntity) -> Self {
+        Self {
+            id: value.id,
+            title: value.title.clone(),
+            start_on: value.start_on.format("%Y-%m-%d").to_string(),
+            end_on: value.end_on.format("%Y-%m-%d").to_string(),
+            note: value.note.clone(),
+            is_public: value.is_public,
+            days: value
+                .days
+                .iter()
+                .enumerate()
+                .map(|(index, day)| TripDayResponse {
+                    day_number: index as u32 + 1,
+                    date: value.date_of(index).format("%Y-%m-%d").to_string(),
+                    hotel_id: day.hotel_id,
+                    visit_ids: day.visit_ids.clone(),
+                    area_ids: day.area_ids.clone(),
+                    note: day.note.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `GET /trips/<id>`で返す、宿・温泉地・温泉の概要を展開した旅行
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDetailResponse {
+    pub id: u32,
+    pub title: String,
+    pub start_on: String,
+    pub end_on: String,
+    pub note: String,
+    pub days: Vec<TripDayDetailResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripDayDetailResponse {
+    pub day_number: u32,
+    pub date: String,
+    pub hotel: Option<HotelSummaryResponse>,
+    pub areas: Vec<AreaSummaryResponse>,
+    pub visits: Vec<TripVisitResponse>,
+    pub note: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripVisitResponse {
+    #[serde(flatten)]
+    pub visit: VisitResponse,
+    pub onsen: Option<OnsenSummaryResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TripResponse;
+    use crate::domain::trip_entity::{TripDayEntity, TripEntity};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_trip_response_from() {
+        let day = TripDayEntity {
+            hotel_id: Some(1),
+            visit_ids: vec![2],
+            area_ids: vec![],
+            note: "".to_string(),
+        };
+        let trip = TripEntity::new(
+            1,
+            2,
+            "四万",
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            "",
+            false,
+            vec![day.clone(), day],
+        )
+        .unwrap();
+        let response = TripResponse::from(trip);
+        assert_eq!(response.start_on, "2024-01-31");
+        assert_eq!(response.days.len(), 2);
+        assert_eq!(response.days[1].day_number, 2);
+        assert_eq!(response.days[1].date, "2024-02-01");
+        assert_eq!(response.days[1].hotel_id, Some(1));
+    }
+}
@@ -1,4 +1,7 @@
+use rocket::http::{Header, Status};
+use rocket::serde::json::Json;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,4 +14,118 @@ pub struct AuthRequest {
 #[serde(rename_all = "camelCase")]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
+    /// 2段階認証が必要なロールなのにまだ有効にしていない。
+    /// このトークンでは2段階認証の設定しかできないので、`POST /me/2fa/totp`から有効にしてサインインし直す
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub two_factor_enrollment_required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRoleRequest {
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordForgotRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordResetRequest {
+    pub token: String,
+    pub password: String,
+}
+
+#[derive(Debug, Responder)]
+pub enum SigninError {
+    #[response(status = 401)]
+    Unauthorized(()),
+    /// Retry-Afterヘッダーに再試行できるまでの秒数を入れる
+    #[response(status = 429)]
+    TooManyRequests((), Header<'static>),
+}
+
+impl SigninError {
+    pub fn too_many_requests(retry_after: Duration) -> Self {
+        SigninError::TooManyRequests((), retry_after_header(retry_after))
+    }
+}
+
+/// IPアドレスごとに試行回数を制限するリクエストのエラー
+#[derive(Debug, Responder)]
+pub enum RateLimitedError {
+    Status(Status),
+    /// Retry-Afterヘッダーに再試行できるまでの秒数を入れる
+    #[response(status = 429)]
+    TooManyRequests((), Header<'static>),
+}
+
+impl RateLimitedError {
+    pub fn too_many_requests(retry_after: Duration) -> Self {
+        RateLimitedError::TooManyRequests((), retry_after_header(retry_after))
+    }
+}
+
+impl From<Status> for RateLimitedError {
+    fn from(status: Status) -> Self {
+        RateLimitedError::Status(status)
+    }
+}
+
+fn retry_after_header(retry_after: Duration) -> Header<'static> {
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    Header::new("Retry-After", seconds.to_string())
+}
+
+#[derive(Debug, Responder)]
+pub enum SigninResponse {
+    #[response(status = 200)]
+    Authenticated(Json<AuthResponse>),
+    /// パスワードは正しいので、`POST /signin/2fa`で確認コードを送る
+    #[response(status = 202)]
+    TwoFactorRequired(Json<TwoFactorChallengeResponse>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorChallengeResponse {
+    pub two_factor_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorSigninRequest {
+    pub two_factor_token: String,
+    /// 認証アプリの確認コードかバックアップコード
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollmentResponse {
+    pub secret: String,
+    /// QRコードにする`otpauth://`のURI
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupCodesResponse {
+    pub backup_codes: Vec<String>,
 }
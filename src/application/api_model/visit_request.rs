@@ -0,0 +1,66 @@
+use crate::domain::visit_entity::VisitEntity;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitRequest {
+    pub onsen_id: u32,
+    /// `2024-01-02`
+    pub visited_on: String,
+    pub time_of_day: Option<String>,
+    pub weather: Option<String>,
+    pub crowd_level: Option<String>,
+    pub water_temperature: Option<String>,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+impl VisitRequest {
+    pub fn create_entity(&self, id: u32, user_id: u32) -> Option<VisitEntity> {
+        let visited_on = NaiveDate::parse_from_str(&self.visited_on, "%Y-%m-%d").ok()?;
+        VisitEntity::new(
+            id,
+            user_id,
+            self.onsen_id,
+            visited_on,
+            self.time_of_day.as_deref(),
+            self.weather.as_deref(),
+            self.crowd_level.as_deref(),
+            self.water_temperature.as_deref(),
+            self.note.as_str(),
+            self.is_public,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VisitRequest;
+    use crate::domain::visit_entity::Weather;
+
+    #[test]
+    fn test_visit_request_create_entity() {
+        let mut request = VisitRequest {
+            onsen_id: 3,
+            visited_on: "2024-01-02".to_string(),
+            time_of_day: None,
+            weather: Some("rainy".to_string()),
+            crowd_level: None,
+            water_temperature: None,
+            note: "".to_string(),
+            is_public: true,
+        };
+        let entity = request.create_entity(1, 2).unwrap();
+        assert_eq!(entity.id, 1);
+        assert_eq!(entity.user_id, 2);
+        assert_eq!(entity.onsen_id, 3);
+        assert_eq!(entity.visited_on.to_string(), "2024-01-02");
+        assert_eq!(entity.weather, Some(Weather::Rainy));
+        assert!(entity.is_public);
+        request.visited_on = "2024/01/02".to_string();
+        assert!(request.create_entity(1, 2).is_none());
+    }
+}
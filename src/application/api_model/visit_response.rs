@@ -0,0 +1,65 @@
+use crate::domain::visit_entity::VisitEntity;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitResponse {
+    pub id: u32,
+    pub onsen_id: u32,
+    pub visited_on: String,
+    pub time_of_day: Option<String>,
+    pub weather: Option<String>,
+    pub crowd_level: Option<String>,
+    pub water_temperature: Option<String>,
+    pub note: String,
+    pub is_public: bool,
+}
+
+impl From<VisitEntity> for VisitResponse {
+    fn from(value: VisitEntity) -> Self {
+        Self {
+            id: value.id,
+            onsen_id: value.onsen_id,
+            visited_on: value.visited_on.format("%Y-%m-%d").to_string(),
+            time_of_day: value.time_of_day.map(|v| v.to_string()),
+            weather: value.weather.map(|v| v.to_string()),
+            crowd_level: value.crowd_level.map(|v| v.to_string()),
+            water_temperature: value.water_temperature.map(|v| v.to_string()),
+            note: value.note,
+            is_public: value.is_public,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VisitResponse;
+    use crate::domain::visit_entity::VisitEntity;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_visit_response_from() {
+        let visit = VisitEntity::new(
+            1,
+            2,
+            3,
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            Some("night"),
+            None,
+            Some("crowded"),
+            None,
+            "星がきれい",
+            false,
+        )
+        .unwrap();
+        let response = VisitResponse::from(visit);
+        assert_eq!(response.id, 1);
+        assert_eq!(response.onsen_id, 3);
+        assert_eq!(response.visited_on, "2024-01-02");
+        assert_eq!(response.time_of_day, Some("night".to_string()));
+        assert_eq!(response.weather, None);
+        assert_eq!(response.crowd_level, Some("crowded".to_string()));
+        assert_eq!(response.note, "星がきれい");
+        assert!(!response.is_public);
+    }
+}
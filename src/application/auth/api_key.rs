@@ -0,0 +1,72 @@
+use super::refresh_token::{hash_refresh_token, random_string};
+use crate::domain::role::Permission;
+use std::str::FromStr;
+
+/// `Authorization: ApiKey ...`で送るキーの接頭辞
+const API_KEY_PREFIX: &str = "onsen_";
+/// 一覧で見分けられるようにDBに平文で残す先頭の文字数
+const KEY_PREFIX_LENGTH: usize = 12;
+
+pub struct NewApiKey {
+    /// 作成したときだけクライアントに返す平文のキー
+    pub key: String,
+    pub key_prefix: String,
+    /// DBに保存するハッシュ
+    pub key_hash: String,
+}
+
+pub fn create_api_key() -> NewApiKey {
+    let key = format!("{}{}", API_KEY_PREFIX, random_string(40));
+    NewApiKey {
+        key_prefix: key[..KEY_PREFIX_LENGTH].to_string(),
+        key_hash: hash_api_key(&key),
+        key,
+    }
+}
+
+/// リフレッシュトークンと同じくソルトなしのSHA-256で保存する
+pub fn hash_api_key(key: &str) -> String {
+    hash_refresh_token(key)
+}
+
+pub fn format_scopes(scopes: &[Permission]) -> String {
+    scopes
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// 知らないスコープは無視する
+pub fn parse_scopes(scopes: &str) -> Vec<Permission> {
+    scopes
+        .split_whitespace()
+        .filter_map(|v| Permission::from_str(v).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::api_key::*;
+
+    #[test]
+    fn test_create_api_key() {
+        let api_key = create_api_key();
+        assert!(api_key.key.starts_with("onsen_"));
+        assert_eq!(api_key.key.len(), 46);
+        assert!(api_key.key.starts_with(&api_key.key_prefix));
+        assert_eq!(api_key.key_hash, hash_api_key(&api_key.key));
+    }
+
+    #[test]
+    fn test_scopes() {
+        let scopes = [Permission::OnsenWrite, Permission::AreaWrite];
+        assert_eq!(format_scopes(&scopes), "onsen:write area:write");
+        assert_eq!(parse_scopes("onsen:write area:write"), scopes);
+        assert_eq!(
+            parse_scopes("onsen:write unknown"),
+            [Permission::OnsenWrite]
+        );
+        assert!(parse_scopes("").is_empty());
+    }
+}
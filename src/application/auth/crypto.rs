@@ -1,27 +1,96 @@
 use argon2::{Config, Variant, Version};
 use rand::{distributions::Alphanumeric, Rng};
+use serde::Deserialize;
 
-pub fn create_hash(plain_password: &str) -> String {
+/// Argon2idのコスト。Rocket.tomlの`password.argon2`
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct HashParams {
+    /// KiB
+    pub mem_cost: u32,
+    pub time_cost: u32,
+    pub lanes: u32,
+}
+
+pub fn create_hash(plain_password: &str, params: HashParams) -> String {
     let salt: String = rand::thread_rng()
         .sample_iter(&Alphanumeric)
         .take(32)
         .map(char::from)
         .collect();
     let config = Config {
-        variant: Variant::Argon2i,
+        variant: Variant::Argon2id,
         version: Version::Version13,
-        mem_cost: 65536,
-        time_cost: 1,
-        lanes: 4,
+        mem_cost: params.mem_cost,
+        time_cost: params.time_cost,
+        lanes: params.lanes,
         secret: &[],
         ad: &[],
         hash_length: 32,
     };
-    argon2::hash_encoded(plain_password.as_bytes(), &salt.as_bytes(), &config).unwrap()
+    argon2::hash_encoded(plain_password.as_bytes(), salt.as_bytes(), &config).unwrap()
 }
 
+/// ハッシュのアルゴリズムとコストはハッシュに含まれているので、古い設定のハッシュも検証できる
 pub fn verify_hash(plain_password: &str, hashed_password: &str) -> bool {
-    let matches =
-        argon2::verify_encoded(hashed_password, plain_password.as_bytes()).unwrap_or(false);
-    return matches;
+    argon2::verify_encoded(hashed_password, plain_password.as_bytes()).unwrap_or(false)
+}
+
+/// 今の設定と違うアルゴリズムやコストで作ったハッシュならtrue
+pub fn needs_rehash(hashed_password: &str, params: HashParams) -> bool {
+    // $argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>
+    let parts: Vec<&str> = hashed_password.split('$').collect();
+    if parts.len() != 6 || parts[1] != "argon2id" || parts[2] != "v=19" {
+        return true;
+    }
+    let expected = format!(
+        "m={},t={},p={}",
+        params.mem_cost, params.time_cost, params.lanes
+    );
+    parts[3] != expected
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::crypto::*;
+
+    const PARAMS: HashParams = HashParams {
+        mem_cost: 1024,
+        time_cost: 1,
+        lanes: 1,
+    };
+
+    #[test]
+    fn test_create_and_verify_hash() {
+        let hashed_password = create_hash("password", PARAMS);
+        assert!(hashed_password.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(verify_hash("password", &hashed_password));
+        assert!(!verify_hash("Password", &hashed_password));
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let hashed_password = create_hash("password", PARAMS);
+        assert!(!needs_rehash(&hashed_password, PARAMS));
+        assert!(needs_rehash(
+            &hashed_password,
+            HashParams {
+                time_cost: 2,
+                ..PARAMS
+            }
+        ));
+        let argon2i = argon2::hash_encoded(
+            b"password",
+            b"saltsaltsaltsalt",
+            &argon2::Config {
+                variant: Variant::Argon2i,
+                mem_cost: 1024,
+                time_cost: 1,
+                lanes: 1,
+                ..argon2::Config::default()
+            },
+        )
+        .unwrap();
+        assert!(verify_hash("password", &argon2i));
+        assert!(needs_rehash(&argon2i, PARAMS));
+    }
 }
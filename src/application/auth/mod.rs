@@ -1,2 +1,7 @@
+pub mod api_key;
 pub mod crypto;
 pub mod jwt;
+pub mod one_time_token;
+pub mod refresh_token;
+pub mod share_token;
+pub mod totp;
@@ -0,0 +1,61 @@
+use super::refresh_token::{hash_refresh_token, random_string};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 30;
+const EMAIL_VERIFICATION_TOKEN_HOURS: i64 = 24;
+const TWO_FACTOR_CHALLENGE_MINUTES: i64 = 5;
+
+/// メールなどで渡す1回限りのトークン
+pub struct NewOneTimeToken {
+    /// クライアントに渡す平文のトークン
+    pub token: String,
+    /// DBに保存するハッシュ
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+}
+
+pub fn create_password_reset_token() -> NewOneTimeToken {
+    create_one_time_token(Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES))
+}
+
+pub fn create_email_verification_token() -> NewOneTimeToken {
+    create_one_time_token(Duration::hours(EMAIL_VERIFICATION_TOKEN_HOURS))
+}
+
+/// パスワードが正しかったときに返し、2段階認証の確認コードと一緒に送ってもらう
+pub fn create_two_factor_challenge_token() -> NewOneTimeToken {
+    create_one_time_token(Duration::minutes(TWO_FACTOR_CHALLENGE_MINUTES))
+}
+
+/// リフレッシュトークンと同じくソルトなしのSHA-256で保存する
+pub fn hash_one_time_token(token: &str) -> String {
+    hash_refresh_token(token)
+}
+
+fn create_one_time_token(lifetime: Duration) -> NewOneTimeToken {
+    let token = random_string(48);
+    NewOneTimeToken {
+        token_hash: hash_one_time_token(&token),
+        token,
+        expires_at: (Utc::now() + lifetime).naive_utc(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::one_time_token::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_create_one_time_token() {
+        let reset_token = create_password_reset_token();
+        assert_eq!(reset_token.token.len(), 48);
+        assert_eq!(
+            reset_token.token_hash,
+            hash_one_time_token(&reset_token.token)
+        );
+        assert!(reset_token.expires_at > Utc::now().naive_utc());
+        let verification_token = create_email_verification_token();
+        assert!(verification_token.expires_at > (Utc::now() + Duration::hours(1)).naive_utc());
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+
+const REFRESH_TOKEN_DAYS: i64 = 30;
+
+pub struct NewRefreshToken {
+    /// クライアントに返す平文のトークン
+    pub token: String,
+    /// DBに保存するハッシュ
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+}
+
+pub fn create_refresh_token() -> NewRefreshToken {
+    let token = random_string(64);
+    NewRefreshToken {
+        token_hash: hash_refresh_token(&token),
+        token,
+        expires_at: (Utc::now() + Duration::days(REFRESH_TOKEN_DAYS)).naive_utc(),
+    }
+}
+
+/// サインインごとに新しいファミリーを作り、ローテーションしたトークンは同じファミリーにする
+pub fn create_family_id() -> String {
+    random_string(32)
+}
+
+/// 十分にランダムなトークンなのでソルトなしのSHA-256で保存する
+pub fn hash_refresh_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub(super) fn random_string(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::refresh_token::*;
+
+    #[test]
+    fn test_create_refresh_token() {
+        let refresh_token = create_refresh_token();
+        assert_eq!(refresh_token.token.len(), 64);
+        assert_eq!(
+            refresh_token.token_hash,
+            hash_refresh_token(&refresh_token.token)
+        );
+        assert_ne!(create_refresh_token().token, refresh_token.token);
+    }
+
+    #[test]
+    fn test_hash_refresh_token() {
+        assert_eq!(
+            hash_refresh_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
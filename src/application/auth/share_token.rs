@@ -0,0 +1,18 @@
+use super::refresh_token::random_string;
+
+/// 共有リンクに使う推測できないトークン。リンクそのものなので平文で保存する
+pub fn create_share_token() -> String {
+    random_string(32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::share_token::*;
+
+    #[test]
+    fn test_create_share_token() {
+        let token = create_share_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, create_share_token());
+    }
+}
@@ -0,0 +1,193 @@
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// RFC 6238の既定値。Google Authenticatorなどが対応しているのはこの組み合わせ
+const TIME_STEP_SECONDS: i64 = 30;
+const DIGITS: u32 = 6;
+/// 端末の時計のずれを考えて、前後1ステップまで受け付ける
+const ALLOWED_STEP_DRIFT: i64 = 1;
+const SECRET_BYTES: usize = 20;
+const BACKUP_CODE_COUNT: usize = 10;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Base32(RFC 4648、パディングなし)の秘密鍵を作る
+pub fn create_totp_secret() -> String {
+    let secret: Vec<u8> = (0..SECRET_BYTES).map(|_| rand::random::<u8>()).collect();
+    base32_encode(&secret)
+}
+
+/// 認証アプリに読み込ませる`otpauth://`のURI。QRコードにはこの文字列を入れる
+pub fn provisioning_uri(secret: &str, issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        secret,
+        percent_encode(issuer),
+        DIGITS,
+        TIME_STEP_SECONDS
+    )
+}
+
+/// コードが正しければ一致したタイムステップを返す。
+/// 同じコードを使い回せないように、`last_used_step`以前のステップは受け付けない
+pub fn verify_totp(
+    secret: &str,
+    code: &str,
+    unix_time: i64,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    let key = base32_decode(secret)?;
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let current_step = unix_time / TIME_STEP_SECONDS;
+    (current_step - ALLOWED_STEP_DRIFT..=current_step + ALLOWED_STEP_DRIFT)
+        .filter(|step| last_used_step.map_or(true, |last| *step > last))
+        .find(|step| hotp(&key, *step as u64) == code)
+}
+
+/// `xxxx-xxxx`形式のバックアップコードを作る
+pub fn create_backup_codes() -> Vec<String> {
+    const CHARSET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+    let mut rng = rand::thread_rng();
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| {
+            let code: String = (0..8)
+                .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+                .collect();
+            format!("{}-{}", &code[..4], &code[4..])
+        })
+        .collect()
+}
+
+/// 入力の揺れを吸収するため、ハイフンと空白を除いて小文字にしてからハッシュにする
+pub fn hash_backup_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+fn hotp(key: &[u8], counter: u64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in encoded.trim_end_matches('=').bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|v| *v == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::auth::totp::*;
+
+    /// RFC 6238 Appendix BのSHA1の秘密鍵"12345678901234567890"
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_base32() {
+        assert_eq!(base32_encode(b"12345678901234567890"), RFC_SECRET);
+        assert_eq!(
+            base32_decode(RFC_SECRET),
+            Some(b"12345678901234567890".to_vec())
+        );
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_decode("MY======"), Some(b"f".to_vec()));
+        assert_eq!(base32_decode("M1"), None);
+        assert_eq!(create_totp_secret().len(), 32);
+    }
+
+    #[test]
+    fn test_verify_totp() {
+        // RFC 6238の8桁のテストベクタの下6桁
+        assert_eq!(verify_totp(RFC_SECRET, "287082", 59, None), Some(1));
+        assert_eq!(
+            verify_totp(RFC_SECRET, "005924", 1234567890, None),
+            Some(41152263)
+        );
+        assert_eq!(verify_totp(RFC_SECRET, "287082", 59 + 30, None), Some(1));
+        assert_eq!(verify_totp(RFC_SECRET, "287082", 59 + 60, None), None);
+        assert_eq!(verify_totp(RFC_SECRET, "287082", 59, Some(1)), None);
+        assert_eq!(verify_totp(RFC_SECRET, "28708", 59, None), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        assert_eq!(
+            provisioning_uri("ABC", "onsen tabi", "a@example.com"),
+            "otpauth://totp/onsen%20tabi:a%40example.com?secret=ABC&issuer=onsen%20tabi&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_backup_codes() {
+        let codes = create_backup_codes();
+        assert_eq!(codes.len(), 10);
+        assert_eq!(codes[0].len(), 9);
+        assert_eq!(hash_backup_code("ABCD-efgh"), hash_backup_code("abcdefgh"));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::application::api_model::achievement_api_model::*;
+use crate::application::controller::request_guard::{AchievementManage, Authorized, ValidatedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::infrastructure::repository::achievement_repository;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use tracing::instrument;
+
+/// 定義されている実績
+#[get("/achievements")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_achievements(request_id: RequestId) -> Json<Vec<AchievementResponse>> {
+    let achievements = achievement_repository::get_achievements();
+    Json(
+        achievements
+            .into_iter()
+            .map(AchievementResponse::from)
+            .collect(),
+    )
+}
+
+/// すべての実績と獲得した日時。判定は入浴記録の登録・更新時に行い、ここでは記録しない
+#[get("/me/achievements")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_my_achievements(
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Json<Vec<UserAchievementResponse>> {
+    let user_achievements = achievement_repository::get_user_achievements(user.id as u32);
+    let achievements = achievement_repository::get_achievements();
+    Json(
+        achievements
+            .into_iter()
+            .map(|achievement| {
+                let achieved = user_achievements
+                    .iter()
+                    .find(|v| v.achievement_id == achievement.id);
+                UserAchievementResponse::create(achievement, achieved)
+            })
+            .collect(),
+    )
+}
+
+#[post("/admin/achievements", format = "json", data = "<achievement_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_achievement(
+    achievement_req: Json<AchievementRequest>,
+    _user: Authorized<AchievementManage>,
+    request_id: RequestId,
+) -> Result<Json<AchievementResponse>, Status> {
+    let achievement = achievement_req.create_entity(0).ok_or(Status::BadRequest)?;
+    let created_achievement = achievement_repository::post_achievement(achievement);
+    Ok(Json(AchievementResponse::from(created_achievement)))
+}
+
+#[put(
+    "/admin/achievements/<achievement_id>",
+    format = "json",
+    data = "<achievement_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_achievement(
+    achievement_id: u32,
+    achievement_req: Json<AchievementRequest>,
+    _user: Authorized<AchievementManage>,
+    request_id: RequestId,
+) -> Result<Json<AchievementResponse>, Status> {
+    let achievement = achievement_req
+        .create_entity(achievement_id)
+        .ok_or(Status::BadRequest)?;
+    if !achievement_repository::put_achievement(&achievement) {
+        return Err(Status::NotFound);
+    }
+    Ok(Json(AchievementResponse::from(achievement)))
+}
+
+#[delete("/admin/achievements/<achievement_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_achievement(
+    achievement_id: u32,
+    _user: Authorized<AchievementManage>,
+    request_id: RequestId,
+) -> Status {
+    if !achievement_repository::delete_achievement(achievement_id) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
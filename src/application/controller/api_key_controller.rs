@@ -0,0 +1,84 @@
+use crate::application::api_model::api_key_api_model::*;
+use crate::application::auth::api_key::{create_api_key, format_scopes};
+use crate::application::controller::request_guard::ValidatedUser;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::role::Permission;
+use crate::infrastructure::repository::api_key_repository;
+use chrono::Utc;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::str::FromStr;
+use tracing::instrument;
+
+/// スクリプトなどから使う長期間有効なAPIキーを発行する。平文のキーはこのレスポンスでしか返さない
+#[post("/me/api-keys", format = "json", data = "<api_key_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_api_key(
+    api_key_req: Json<ApiKeyRequest>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<CreatedApiKeyResponse>, Status> {
+    // APIキーでAPIキーを作れるとスコープを広げられてしまう
+    if user.is_api_key() {
+        return Err(Status::Forbidden);
+    }
+    let name = api_key_req.name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(Status::BadRequest);
+    }
+    let mut scopes: Vec<Permission> = vec![];
+    for scope in &api_key_req.scopes {
+        let permission = Permission::from_str(scope).map_err(|_| Status::BadRequest)?;
+        if !user.role.has_permission(permission) {
+            return Err(Status::BadRequest);
+        }
+        if !scopes.contains(&permission) {
+            scopes.push(permission);
+        }
+    }
+    let expires_at = api_key_req
+        .expires_at(Utc::now())
+        .map_err(|_| Status::BadRequest)?;
+    let new_api_key = create_api_key();
+    let api_key = api_key_repository::post_api_key(
+        user.id,
+        name,
+        &new_api_key.key_prefix,
+        &new_api_key.key_hash,
+        &format_scopes(&scopes),
+        user.mfa,
+        expires_at,
+    );
+    Ok(Json(CreatedApiKeyResponse {
+        key: new_api_key.key,
+        api_key: ApiKeyResponse::from(api_key),
+    }))
+}
+
+#[get("/me/api-keys")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_api_keys(
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<Vec<ApiKeyResponse>>, Status> {
+    if user.is_api_key() {
+        return Err(Status::Forbidden);
+    }
+    let response: Vec<ApiKeyResponse> = api_key_repository::get_api_keys(user.id)
+        .into_iter()
+        .map(ApiKeyResponse::from)
+        .collect();
+    Ok(Json(response))
+}
+
+#[delete("/me/api-keys/<api_key_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_api_key(api_key_id: u32, user: ValidatedUser, request_id: RequestId) -> Status {
+    if user.is_api_key() {
+        return Status::Forbidden;
+    }
+    if !api_key_repository::revoke_api_key(user.id, api_key_id as i32) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
@@ -1,37 +1,47 @@
 use crate::application::api_model::{area_request::*, area_response::*};
-use crate::application::controller::request_guard::ValidatedUser;
+use crate::application::controller::photo_controller::cover_photos;
+use crate::application::controller::request_guard::{AreaWrite, Authorized};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::photo_entity::PhotoTarget;
 use crate::infrastructure::repository::area_repository;
 use rocket::http::Status;
 use rocket::serde::json::Json;
+use tracing::instrument;
 
 #[get("/area")]
-pub fn get_areas() -> Json<Vec<AreaResponse>> {
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_areas(request_id: RequestId) -> Json<Vec<AreaResponse>> {
     let areas = area_repository::get_areas_with_onsen();
+    let area_ids: Vec<u32> = areas.iter().map(|v| v.id).collect();
+    let mut covers = cover_photos(PhotoTarget::Area, &area_ids);
     let response: Vec<AreaResponse> = areas
         .iter()
-        .map(|v| AreaResponse::from(v.clone()))
+        .map(|v| AreaResponse::from(v.clone()).with_cover(covers.remove(&v.id)))
         .collect();
     Json(response)
 }
 
 #[get("/area/<area_id>")]
-pub fn get_area(area_id: u32) -> Result<Json<AreaResponse>, Status> {
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_area(area_id: u32, request_id: RequestId) -> Result<Json<AreaResponse>, Status> {
     let area = area_repository::get_area(area_id);
     match &area {
-        Some(area) => Ok(Json(AreaResponse::from(area.clone()))),
+        Some(area) => {
+            let cover = cover_photos(PhotoTarget::Area, &[area_id]).remove(&area_id);
+            Ok(Json(AreaResponse::from(area.clone()).with_cover(cover)))
+        }
         None => Err(Status::NotFound),
     }
 }
 
 #[put("/area/<area_id>", format = "json", data = "<area_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn put_area(
     area_id: u32,
     area_req: Json<AreaRequest>,
-    user: ValidatedUser,
+    _user: Authorized<AreaWrite>,
+    request_id: RequestId,
 ) -> Result<(), Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let area_entity = area_req.create_entity(area_id);
     if let Some(area_entity) = area_entity {
         let _ = area_repository::put_area(area_entity);
@@ -42,13 +52,12 @@ pub fn put_area(
 }
 
 #[post("/area", format = "json", data = "<area_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn post_area(
     area_req: Json<AreaRequest>,
-    user: ValidatedUser,
+    _user: Authorized<AreaWrite>,
+    request_id: RequestId,
 ) -> Result<Json<AreaResponse>, Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let area_entity = area_req.create_entity(0);
     if let Some(area_entity) = area_entity {
         let created_area = area_repository::post_area(area_entity);
@@ -0,0 +1,324 @@
+use crate::application::api_model::comment_api_model::*;
+use crate::application::controller::profile_controller::{owner_privacy, profile_summaries};
+use crate::application::controller::request_guard::{
+    Authorized, CommentModerate, ValidatedUser, VerifiedUser,
+};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::comment_entity::{build_threads, CommentEntity, CommentTarget};
+use crate::domain::notification_entity::comment_notifications;
+use crate::infrastructure::repository::{
+    comment_repository, notification_repository, trip_repository, visit_repository,
+};
+use chrono::Utc;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::collections::HashMap;
+use tracing::{info, instrument};
+
+/// 通知は新しいものから返す件数
+const NOTIFICATION_LIMIT: u32 = 100;
+
+#[get("/trips/<trip_id>/comments")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_trip_comments(
+    trip_id: u32,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<Json<Vec<CommentThreadResponse>>, Status> {
+    comments_response(CommentTarget::Trip, trip_id, user)
+}
+
+/// 公開している旅行にだけコメントできる。コメントするには自分のプロフィールが必要
+#[post("/trips/<trip_id>/comments", format = "json", data = "<comment_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_trip_comment(
+    trip_id: u32,
+    comment_req: Json<CommentRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<CommentResponse>, Status> {
+    save_comment(CommentTarget::Trip, trip_id, &comment_req, user.0.id as u32)
+}
+
+#[get("/visits/<visit_id>/comments")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_visit_comments(
+    visit_id: u32,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<Json<Vec<CommentThreadResponse>>, Status> {
+    comments_response(CommentTarget::Visit, visit_id, user)
+}
+
+/// 公開している入浴記録にだけコメントできる。コメントするには自分のプロフィールが必要
+#[post("/visits/<visit_id>/comments", format = "json", data = "<comment_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_visit_comment(
+    visit_id: u32,
+    comment_req: Json<CommentRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<CommentResponse>, Status> {
+    save_comment(
+        CommentTarget::Visit,
+        visit_id,
+        &comment_req,
+        user.0.id as u32,
+    )
+}
+
+/// 書いた人だけが書き換えられる。非表示にされたコメントは書き換えられない
+#[put("/comments/<comment_id>", format = "json", data = "<comment_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_comment(
+    comment_id: u32,
+    comment_req: Json<CommentEditRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<CommentResponse>, Status> {
+    let body = CommentEntity::valid_body(&comment_req.body).ok_or(Status::BadRequest)?;
+    if !comment_repository::put_comment_body(comment_id, user.0.id as u32, &body) {
+        return Err(Status::NotFound);
+    }
+    let comment = comment_repository::get_comment(comment_id).ok_or(Status::NotFound)?;
+    let mut authors = profile_summaries(&[comment.user_id]);
+    let author = authors.remove(&comment.user_id);
+    Ok(Json(CommentResponse::new(comment, author, false)))
+}
+
+/// 書いた人だけが削除できる。返信のあるコメントは本文だけ消して`deleted`として残す
+#[delete("/comments/<comment_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_comment(comment_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    if !comment_repository::delete_comment(comment_id, user.0.id as u32) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// 同じコメントをもう一度通報すると内容を更新する。自分のコメントは通報できない
+#[post(
+    "/comments/<comment_id>/reports",
+    format = "json",
+    data = "<report_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_comment_report(
+    comment_id: u32,
+    report_req: Json<CommentReportRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Status {
+    let user_id = user.0.id as u32;
+    let Some(comment) = comment_repository::get_comment(comment_id) else {
+        return Status::NotFound;
+    };
+    if find_target(comment.target, comment.target_id, Some(user_id)).is_none() {
+        return Status::NotFound;
+    }
+    if comment.user_id == user_id {
+        return Status::BadRequest;
+    }
+    let Some(report) = report_req.create_entity(comment_id, user_id) else {
+        return Status::BadRequest;
+    };
+    comment_repository::put_comment_report(&report);
+    Status::NoContent
+}
+
+/// 未対応の通報があるコメント。通報の多い順
+#[get("/admin/comments/reports")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_comment_reports(
+    _user: Authorized<CommentModerate>,
+    request_id: RequestId,
+) -> Json<Vec<ReportedCommentResponse>> {
+    let mut reports: HashMap<u32, Vec<CommentReportResponse>> = HashMap::new();
+    for report in comment_repository::get_comment_reports() {
+        reports
+            .entry(report.comment_id)
+            .or_default()
+            .push(CommentReportResponse::from(report));
+    }
+    let comment_ids: Vec<u32> = reports.keys().copied().collect();
+    let comments = comment_repository::get_comments_by_ids(&comment_ids);
+    let user_ids: Vec<u32> = comments.iter().map(|v| v.user_id).collect();
+    let authors = profile_summaries(&user_ids);
+    let mut responses: Vec<ReportedCommentResponse> = comments
+        .into_iter()
+        .map(|comment| ReportedCommentResponse {
+            reports: reports.remove(&comment.id).unwrap_or_default(),
+            comment: {
+                let author = authors.get(&comment.user_id).cloned();
+                CommentResponse::new(comment, author, true)
+            },
+        })
+        .collect();
+    responses.sort_by(|a, b| {
+        b.reports
+            .len()
+            .cmp(&a.reports.len())
+            .then(a.comment.id.cmp(&b.comment.id))
+    });
+    Json(responses)
+}
+
+/// コメントを`visible`か`hidden`にして、そのコメントへの通報を対応済みにする
+#[put("/admin/comments/<comment_id>", format = "json", data = "<status_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_comment_status(
+    comment_id: u32,
+    status_req: Json<CommentStatusRequest>,
+    _user: Authorized<CommentModerate>,
+    request_id: RequestId,
+) -> Status {
+    let Some(status) = status_req.status() else {
+        return Status::BadRequest;
+    };
+    if !comment_repository::put_comment_status(comment_id, status) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// 自分の旅行・入浴記録へのコメントと、自分のコメントへの返信。`unread=true`で未読だけ。
+/// 通知のあとで非公開になった旅行・入浴記録へのコメントは返さない
+#[get("/me/notifications?<unread>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_notifications(
+    unread: Option<bool>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Json<Vec<NotificationResponse>> {
+    let notifications = notification_repository::get_notifications(
+        user.id as u32,
+        unread.unwrap_or(false),
+        NOTIFICATION_LIMIT,
+    );
+    let comment_ids: Vec<u32> = notifications.iter().map(|v| v.comment_id).collect();
+    let comments: HashMap<u32, CommentEntity> =
+        comment_repository::get_comments_by_ids(&comment_ids)
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect();
+    let mut visible_targets: HashMap<(CommentTarget, u32), bool> = HashMap::new();
+    for comment in comments.values() {
+        visible_targets
+            .entry((comment.target, comment.target_id))
+            .or_insert_with(|| {
+                find_target(comment.target, comment.target_id, Some(user.id as u32)).is_some()
+            });
+    }
+    let actor_ids: Vec<u32> = notifications.iter().map(|v| v.actor_id).collect();
+    let actors = profile_summaries(&actor_ids);
+    Json(
+        notifications
+            .into_iter()
+            .filter_map(|notification| {
+                let comment = comments.get(&notification.comment_id)?.clone();
+                if !visible_targets[&(comment.target, comment.target_id)] {
+                    return None;
+                }
+                let actor = actors.get(&notification.actor_id).cloned();
+                let comment = CommentResponse::new(comment, actor.clone(), false);
+                Some(NotificationResponse::new(notification, actor, comment))
+            })
+            .collect(),
+    )
+}
+
+/// 通知をすべて既読にする
+#[put("/me/notifications/read")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_notifications_read(user: VerifiedUser, request_id: RequestId) -> Status {
+    notification_repository::put_notifications_read(user.0.id as u32);
+    Status::NoContent
+}
+
+/// 見られる旅行・入浴記録なら、持ち主と公開しているかを返す
+fn find_target(target: CommentTarget, target_id: u32, user_id: Option<u32>) -> Option<(u32, bool)> {
+    let (owner_id, is_public) = match target {
+        CommentTarget::Trip => {
+            trip_repository::get_trip(target_id).map(|v| (v.user_id, v.is_public))?
+        }
+        CommentTarget::Visit => visit_repository::get_visits_by_ids(&[target_id])
+            .into_iter()
+            .next()
+            .map(|v| (v.user_id, v.is_public))?,
+    };
+    let (privacy, viewer) = owner_privacy(owner_id, user_id);
+    let item = match target {
+        CommentTarget::Trip => privacy.trips,
+        CommentTarget::Visit => privacy.visits,
+    };
+    privacy
+        .shows_record(item, is_public, viewer)
+        .then_some((owner_id, is_public))
+}
+
+fn comments_response(
+    target: CommentTarget,
+    target_id: u32,
+    user: Option<ValidatedUser>,
+) -> Result<Json<Vec<CommentThreadResponse>>, Status> {
+    find_target(target, target_id, user.map(|v| v.id as u32)).ok_or(Status::NotFound)?;
+    let comments = comment_repository::get_comments(target, target_id);
+    let user_ids: Vec<u32> = comments.iter().map(|v| v.user_id).collect();
+    let authors = profile_summaries(&user_ids);
+    let response = |comment: CommentEntity| {
+        let author = authors.get(&comment.user_id).cloned();
+        CommentResponse::new(comment, author, false)
+    };
+    Ok(Json(
+        build_threads(comments)
+            .into_iter()
+            .map(|thread| CommentThreadResponse {
+                comment: response(thread.comment),
+                replies: thread.replies.into_iter().map(response).collect(),
+            })
+            .collect(),
+    ))
+}
+
+fn save_comment(
+    target: CommentTarget,
+    target_id: u32,
+    comment_req: &CommentRequest,
+    user_id: u32,
+) -> Result<Json<CommentResponse>, Status> {
+    let (owner_id, is_public) =
+        find_target(target, target_id, Some(user_id)).ok_or(Status::NotFound)?;
+    if !is_public {
+        return Err(Status::NotFound);
+    }
+    let comment = comment_req
+        .create_entity(target, target_id, user_id, Utc::now().naive_utc())
+        .ok_or(Status::BadRequest)?;
+    let parent = match comment.parent_id {
+        Some(parent_id) => Some(
+            comment_repository::get_comment(parent_id)
+                .filter(|v| v.accepts_reply(target, target_id))
+                .ok_or(Status::BadRequest)?,
+        ),
+        None => None,
+    };
+    let mut authors = profile_summaries(&[user_id]);
+    let author = authors.remove(&user_id).ok_or(Status::Forbidden)?;
+    let comment = comment_repository::post_comment(comment);
+    notify_comment(&comment, owner_id, parent.as_ref());
+    Ok(Json(CommentResponse::new(comment, Some(author), false)))
+}
+
+/// 旅行・入浴記録の持ち主と返信先のコメントを書いた人に知らせる
+fn notify_comment(comment: &CommentEntity, owner_id: u32, parent: Option<&CommentEntity>) {
+    let notifications = comment_notifications(comment, owner_id, parent.map(|v| v.user_id));
+    if notifications.is_empty() {
+        return;
+    }
+    notification_repository::post_notifications(&notifications);
+    info!(
+        comment_id = comment.id,
+        recipients = notifications.len(),
+        "Comment notifications are created."
+    );
+}
@@ -0,0 +1,33 @@
+use crate::application::api_model::health_response::*;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::infrastructure::repository::health_repository;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use tracing::instrument;
+
+#[get("/healthz")]
+pub fn get_healthz() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+    })
+}
+
+#[get("/readyz")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_readyz(request_id: RequestId) -> (Status, Json<ReadinessResponse>) {
+    let response = ReadinessResponse::from(health_repository::get_readiness());
+    if response.is_ready() {
+        (Status::Ok, Json(response))
+    } else {
+        (Status::ServiceUnavailable, Json(response))
+    }
+}
+
+#[get("/version")]
+pub fn get_version() -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: env!("GIT_SHA").to_string(),
+        build_time: env!("BUILD_TIME").to_string(),
+    })
+}
@@ -1,39 +1,51 @@
-use super::request_guard::ValidatedUser;
+use super::request_guard::{Authorized, HotelWrite};
 use crate::application::api_model::hotel_request::*;
 use crate::application::api_model::hotel_response::*;
-use crate::infrastructure::repository::hotel_repository;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::infrastructure::repository::{hotel_repository, review_repository};
 use rocket::http::Status;
 use rocket::serde::json::Json;
+use tracing::instrument;
 
 #[get("/hotel?<area_id>")]
-pub fn get_hotels(area_id: Option<String>) -> Json<Vec<HotelResponse>> {
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_hotels(area_id: Option<String>, request_id: RequestId) -> Json<Vec<HotelResponse>> {
     let area_id: Option<u32> = area_id.and_then(|v| v.parse().ok());
     let hotels = hotel_repository::get_hotels(area_id);
+    let hotel_ids: Vec<u32> = hotels.iter().map(|v| v.id).collect();
+    let mut ratings = review_repository::get_hotel_rating_summaries(&hotel_ids);
     let response = hotels
         .iter()
-        .map(|v| HotelResponse::from(v.clone()))
+        .map(|v| {
+            HotelResponse::from(v.clone()).with_rating(ratings.remove(&v.id).unwrap_or_default())
+        })
         .collect();
     Json(response)
 }
 
 #[get("/hotel/<hotel_id>")]
-pub fn get_hotel(hotel_id: u32) -> Result<Json<HotelResponse>, Status> {
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_hotel(hotel_id: u32, request_id: RequestId) -> Result<Json<HotelResponse>, Status> {
     let hotel = hotel_repository::get_hotel_with_onsen(hotel_id);
     match &hotel {
-        Some(hotel) => Ok(Json(HotelResponse::from(hotel.clone()))),
+        Some(hotel) => {
+            let rating = review_repository::get_hotel_rating_summaries(&[hotel.id])
+                .remove(&hotel.id)
+                .unwrap_or_default();
+            Ok(Json(HotelResponse::from(hotel.clone()).with_rating(rating)))
+        }
         None => Err(Status::NotFound),
     }
 }
 
 #[put("/hotel/<hotel_id>", format = "json", data = "<hotel_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn put_hotel(
     hotel_id: u32,
     hotel_req: Json<HotelRequest>,
-    user: ValidatedUser,
+    _user: Authorized<HotelWrite>,
+    request_id: RequestId,
 ) -> Result<(), Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let hotel_entity = hotel_req.create_entity(hotel_id);
     if let Some(hotel_entity) = hotel_entity {
         hotel_repository::put_hotel(hotel_entity);
@@ -44,13 +56,12 @@ pub fn put_hotel(
 }
 
 #[post("/hotel", format = "json", data = "<hotel_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn post_hotel(
     hotel_req: Json<HotelRequest>,
-    user: ValidatedUser,
+    _user: Authorized<HotelWrite>,
+    request_id: RequestId,
 ) -> Result<Json<HotelResponse>, Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let hotel_entity = hotel_req.create_entity(0);
     if let Some(hotel_entity) = hotel_entity {
         let created_hotel = hotel_repository::post_hotel(hotel_entity);
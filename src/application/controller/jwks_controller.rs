@@ -0,0 +1,17 @@
+use crate::application::auth::jwt::{Jwk, JwtKeys};
+use rocket::serde::json::Json;
+use rocket::State;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct JwksResponse {
+    pub keys: Vec<Jwk>,
+}
+
+/// ほかのサービスがアクセストークンを検証するための公開鍵
+#[get("/.well-known/jwks.json")]
+pub fn get_jwks(jwt_keys: &State<JwtKeys>) -> Json<JwksResponse> {
+    Json(JwksResponse {
+        keys: jwt_keys.jwks().to_vec(),
+    })
+}
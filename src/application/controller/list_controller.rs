@@ -0,0 +1,293 @@
+use crate::application::api_model::list_request::*;
+use crate::application::api_model::list_response::*;
+use crate::application::api_model::summary_response::*;
+use crate::application::auth::share_token::create_share_token;
+use crate::application::controller::request_guard::{ValidatedUser, VerifiedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::area_entity::AreaEntity;
+use crate::domain::hotel_entity::HotelEntity;
+use crate::domain::list_entity::{ListEntity, ListItemEntity, ListItemTarget, ListKind};
+use crate::domain::onsen::onsen_entity::OnsenEntity;
+use crate::infrastructure::repository::{
+    area_repository, hotel_repository, list_repository, onsen_repository,
+};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::instrument;
+
+/// お気に入りと行きたいのリストはサインアップしたときに作っている
+#[get("/me/lists")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_lists(user: ValidatedUser, request_id: RequestId) -> Json<Vec<ListResponse>> {
+    let lists = list_repository::get_lists(user.id as u32);
+    Json(lists.into_iter().map(ListResponse::from).collect())
+}
+
+#[post("/me/lists", format = "json", data = "<list_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_list(
+    list_req: Json<ListRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ListResponse>, Status> {
+    let list = ListEntity::new(
+        0,
+        user.0.id as u32,
+        ListKind::Custom,
+        &list_req.name,
+        None,
+        vec![],
+    )
+    .ok_or(Status::BadRequest)?;
+    let created_list = list_repository::post_list(list);
+    Ok(Json(ListResponse::from(created_list)))
+}
+
+#[get("/me/lists/<list_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_list(
+    list_id: u32,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<ListDetailResponse>, Status> {
+    let list = get_own_list(list_id, user.id as u32)?;
+    let share_token = list.share_token.clone();
+    Ok(Json(ListDetailResponse {
+        share_token,
+        ..expand_list(list)
+    }))
+}
+
+/// 名前を変えられるのは自分で作ったリストだけ
+#[put("/me/lists/<list_id>", format = "json", data = "<list_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_list(
+    list_id: u32,
+    list_req: Json<ListRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ListResponse>, Status> {
+    let list = get_own_list(list_id, user.0.id as u32)?;
+    if !list.is_custom() {
+        return Err(Status::BadRequest);
+    }
+    let list = ListEntity::new(
+        list.id,
+        list.user_id,
+        list.kind,
+        &list_req.name,
+        list.share_token,
+        list.items,
+    )
+    .ok_or(Status::BadRequest)?;
+    save_list(list)
+}
+
+/// 削除できるのは自分で作ったリストだけ
+#[delete("/me/lists/<list_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_list(list_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    let list = match get_own_list(list_id, user.0.id as u32) {
+        Ok(list) => list,
+        Err(status) => return status,
+    };
+    if !list.is_custom() {
+        return Status::BadRequest;
+    }
+    if !list_repository::delete_list(list.user_id, list.id) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// 末尾に追加する。既に入っていれば409
+#[post("/me/lists/<list_id>/items", format = "json", data = "<item_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_list_item(
+    list_id: u32,
+    item_req: Json<ListItemRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ListResponse>, Status> {
+    let mut list = get_own_list(list_id, user.0.id as u32)?;
+    let item = item_req.create_entity().ok_or(Status::BadRequest)?;
+    if !exists_target(&item) {
+        return Err(Status::BadRequest);
+    }
+    if !list.add_item(item) {
+        return Err(Status::Conflict);
+    }
+    save_list(list)
+}
+
+/// 今の項目をすべて並べ替えた順で送る
+#[put("/me/lists/<list_id>/items", format = "json", data = "<items_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_list_items(
+    list_id: u32,
+    items_req: Json<ListItemsRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ListResponse>, Status> {
+    let mut list = get_own_list(list_id, user.0.id as u32)?;
+    let items = items_req.create_entities().ok_or(Status::BadRequest)?;
+    if !list.reorder(items) {
+        return Err(Status::BadRequest);
+    }
+    save_list(list)
+}
+
+#[delete("/me/lists/<list_id>/items/<target>/<target_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_list_item(
+    list_id: u32,
+    target: &str,
+    target_id: u32,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Status {
+    let mut list = match get_own_list(list_id, user.0.id as u32) {
+        Ok(list) => list,
+        Err(status) => return status,
+    };
+    let Ok(target) = ListItemTarget::from_str(target) else {
+        return Status::NotFound;
+    };
+    if !list.remove_item(&ListItemEntity { target, target_id }) {
+        return Status::NotFound;
+    }
+    match save_list(list) {
+        Ok(_) => Status::NoContent,
+        Err(status) => status,
+    }
+}
+
+/// 共有リンクのトークンを発行する。発行し直すと前のリンクは使えなくなる
+#[post("/me/lists/<list_id>/share")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_list_share(
+    list_id: u32,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ListResponse>, Status> {
+    let list = get_own_list(list_id, user.0.id as u32)?;
+    save_list(ListEntity {
+        share_token: Some(create_share_token()),
+        ..list
+    })
+}
+
+#[delete("/me/lists/<list_id>/share")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_list_share(list_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    let list = match get_own_list(list_id, user.0.id as u32) {
+        Ok(list) => list,
+        Err(status) => return status,
+    };
+    match save_list(ListEntity {
+        share_token: None,
+        ..list
+    }) {
+        Ok(_) => Status::NoContent,
+        Err(status) => status,
+    }
+}
+
+/// 共有リンクから見るリスト。ログインは不要
+#[get("/lists/shared/<share_token>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_shared_list(
+    share_token: &str,
+    request_id: RequestId,
+) -> Result<Json<ListDetailResponse>, Status> {
+    let list = list_repository::get_list_by_share_token(share_token).ok_or(Status::NotFound)?;
+    Ok(Json(expand_list(list)))
+}
+
+fn get_own_list(list_id: u32, user_id: u32) -> Result<ListEntity, Status> {
+    list_repository::get_list(list_id)
+        .filter(|v| v.user_id == user_id)
+        .ok_or(Status::NotFound)
+}
+
+fn save_list(list: ListEntity) -> Result<Json<ListResponse>, Status> {
+    if !list_repository::put_list(&list) {
+        return Err(Status::NotFound);
+    }
+    Ok(Json(ListResponse::from(list)))
+}
+
+fn exists_target(item: &ListItemEntity) -> bool {
+    match item.target {
+        ListItemTarget::Onsen => onsen_repository::get_onsen(item.target_id).is_some(),
+        ListItemTarget::Hotel => hotel_repository::get_hotel_with_onsen(item.target_id).is_some(),
+        ListItemTarget::Area => area_repository::get_area(item.target_id).is_some(),
+    }
+}
+
+/// 項目ごとに概要を展開する。削除された温泉などは含めない。共有リンクのトークンは含めない。
+/// 温泉・宿・温泉地は種類ごとにまとめて読み込む
+fn expand_list(list: ListEntity) -> ListDetailResponse {
+    let target_ids = |target: ListItemTarget| -> Vec<u32> {
+        list.items
+            .iter()
+            .filter(|v| v.target == target)
+            .map(|v| v.target_id)
+            .collect()
+    };
+    let onsens: HashMap<u32, OnsenEntity> =
+        onsen_repository::get_onsens_by_ids(&target_ids(ListItemTarget::Onsen))
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect();
+    let hotels: HashMap<u32, HotelEntity> =
+        hotel_repository::get_hotels_by_ids(&target_ids(ListItemTarget::Hotel))
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect();
+    let areas: HashMap<u32, AreaEntity> =
+        area_repository::get_areas_by_ids(&target_ids(ListItemTarget::Area))
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect();
+    let items = list
+        .items
+        .iter()
+        .filter_map(|item| {
+            let mut detail = ListItemDetailResponse {
+                target: item.target.to_string(),
+                target_id: item.target_id,
+                onsen: None,
+                hotel: None,
+                area: None,
+            };
+            match item.target {
+                ListItemTarget::Onsen => {
+                    detail.onsen = Some(OnsenSummaryResponse::from(
+                        onsens.get(&item.target_id)?.clone(),
+                    ))
+                }
+                ListItemTarget::Hotel => {
+                    detail.hotel = Some(HotelSummaryResponse::from(
+                        hotels.get(&item.target_id)?.clone(),
+                    ))
+                }
+                ListItemTarget::Area => {
+                    detail.area = Some(AreaSummaryResponse::from(
+                        areas.get(&item.target_id)?.clone(),
+                    ))
+                }
+            }
+            Some(detail)
+        })
+        .collect();
+    ListDetailResponse {
+        id: list.id,
+        kind: list.kind.to_string(),
+        name: list.name,
+        share_token: None,
+        items,
+    }
+}
@@ -0,0 +1,12 @@
+use crate::application::controller::request_guard::{Authorized, MetricsRead};
+use crate::infrastructure::metrics;
+use rocket::http::ContentType;
+
+/// Prometheusのスクレイプには`metrics:read`をスコープにしたAPIキーを使う
+#[get("/metrics")]
+pub fn get_metrics(_user: Authorized<MetricsRead>) -> (ContentType, String) {
+    (
+        ContentType::new("text", "plain").with_params(("version", "0.0.4")),
+        metrics::gather(),
+    )
+}
@@ -1,5 +1,19 @@
+pub mod achievement_controller;
+pub mod api_key_controller;
 pub mod area_controller;
+pub mod comment_controller;
+pub mod health_controller;
 pub mod hotel_controller;
+pub mod jwks_controller;
+pub mod list_controller;
+pub mod metrics_controller;
 pub mod onsen_controller;
+pub mod photo_controller;
+pub mod profile_controller;
 pub mod request_guard;
+pub mod review_controller;
+pub mod stats_controller;
+pub mod trip_controller;
+pub mod two_factor_controller;
 pub mod user_controller;
+pub mod visit_controller;
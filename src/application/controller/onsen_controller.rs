@@ -1,46 +1,84 @@
-use super::request_guard::ValidatedUser;
+use super::request_guard::{Authorized, OnsenWrite};
 use crate::application::api_model::onsen_request::OnsenRequest;
 use crate::application::api_model::onsen_response::*;
-use crate::infrastructure::repository::{area_repository, onsen_repository};
+use crate::application::controller::photo_controller::cover_photos;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::photo_entity::PhotoTarget;
+use crate::infrastructure::repository::{area_repository, onsen_repository, review_repository};
 use rocket::http::Status;
 use rocket::serde::json::Json;
+use tracing::instrument;
 
-#[get("/onsen?<area_id>&<hotel_id>")]
-pub fn get_onsens(area_id: Option<String>, hotel_id: Option<String>) -> Json<Vec<OnsenResponse>> {
+/// `sort=rating`で総合評価の高い順に並べる。評価のない温泉は最後
+#[get("/onsen?<area_id>&<hotel_id>&<sort>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_onsens(
+    area_id: Option<String>,
+    hotel_id: Option<String>,
+    sort: Option<String>,
+    request_id: RequestId,
+) -> Json<Vec<OnsenResponse>> {
     let area_id: Option<u32> = area_id.and_then(|v| v.parse().ok());
     let hotel_id: Option<u32> = hotel_id.and_then(|v| v.parse().ok());
     let onsens = onsen_repository::get_onsens(area_id, hotel_id);
-    let response = onsens
+    let onsen_ids: Vec<u32> = onsens.iter().map(|v| v.id).collect();
+    let mut ratings = review_repository::get_onsen_rating_summaries(&onsen_ids);
+    let mut covers = cover_photos(PhotoTarget::Onsen, &onsen_ids);
+    let mut response: Vec<OnsenResponse> = onsens
         .iter()
-        .map(|v| OnsenResponse::create(v.clone(), None))
+        .map(|v| {
+            OnsenResponse::create(v.clone(), None)
+                .with_rating(ratings.remove(&v.id).unwrap_or_default())
+                .with_cover(covers.remove(&v.id))
+        })
         .collect();
+    if sort.as_deref() == Some("rating") {
+        let overall = |v: &OnsenResponse| v.rating.as_ref().and_then(|v| v.overall);
+        // 評価のない温泉は最後に並べる
+        response.sort_by(|a, b| match (overall(a), overall(b)) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+    }
     Json(response)
 }
 
 #[get("/onsen/<onsen_id>")]
-pub fn get_onsen(onsen_id: u32) -> Result<Json<OnsenResponse>, Status> {
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_onsen(onsen_id: u32, request_id: RequestId) -> Result<Json<OnsenResponse>, Status> {
     let onsen = onsen_repository::get_onsen(onsen_id);
+    let rating = review_repository::get_onsen_rating_summaries(&[onsen_id])
+        .remove(&onsen_id)
+        .unwrap_or_default();
+    let cover = cover_photos(PhotoTarget::Onsen, &[onsen_id]).remove(&onsen_id);
     match onsen {
         Some(onsen) => match onsen.area_id {
             Some(area_id) => {
                 let area = area_repository::get_area(area_id);
-                Ok(Json(OnsenResponse::create(onsen, area)))
+                Ok(Json(
+                    OnsenResponse::create(onsen, area)
+                        .with_rating(rating)
+                        .with_cover(cover),
+                ))
             }
-            None => Ok(Json(OnsenResponse::create(onsen, None))),
+            None => Ok(Json(
+                OnsenResponse::create(onsen, None)
+                    .with_rating(rating)
+                    .with_cover(cover),
+            )),
         },
         None => Err(Status::NotFound),
     }
 }
 
 #[put("/onsen/<onsen_id>", format = "json", data = "<onsen_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn put_onsen(
     onsen_id: u32,
     onsen_req: Json<OnsenRequest>,
-    user: ValidatedUser,
+    _user: Authorized<OnsenWrite>,
+    request_id: RequestId,
 ) -> Result<(), Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let onsen_entity = onsen_req.create_entity(onsen_id);
     if let Some(onsen_entity) = onsen_entity {
         let _ = onsen_repository::put_onsen(onsen_entity);
@@ -51,13 +89,12 @@ pub fn put_onsen(
 }
 
 #[post("/onsen", format = "json", data = "<onsen_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
 pub fn post_onsen(
     onsen_req: Json<OnsenRequest>,
-    user: ValidatedUser,
+    _user: Authorized<OnsenWrite>,
+    request_id: RequestId,
 ) -> Result<Json<OnsenResponse>, Status> {
-    if user.role != "admin" {
-        return Err(Status::Forbidden);
-    }
     let onsen_entity = onsen_req.create_entity(0);
     if let Some(onsen_entity) = onsen_entity {
         let created_onsen = onsen_repository::post_onsen(onsen_entity);
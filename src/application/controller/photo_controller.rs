@@ -0,0 +1,490 @@
+use crate::application::api_model::photo_api_model::*;
+use crate::application::controller::profile_controller::{owner_privacy, viewer_of};
+use crate::application::controller::request_guard::{
+    AreaWrite, Authorized, OnsenWrite, ValidatedUser, VerifiedUser,
+};
+use crate::application::exif::strip_gps;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::application::thumbnail::ThumbnailQueue;
+use crate::domain::photo_entity::{
+    ImageFormat, PhotoEntity, PhotoTarget, ThumbnailSize, ThumbnailStatus,
+};
+use crate::infrastructure::blob_store::{new_blob_key, BlobStore};
+use crate::infrastructure::repository::{
+    area_repository, onsen_repository, photo_repository, profile_repository, visit_repository,
+};
+use rocket::form::Form;
+use rocket::http::{ContentType, Status};
+use rocket::response::Redirect;
+use rocket::serde::json::Json;
+use rocket::tokio::io::AsyncReadExt;
+use rocket::tokio::task;
+use rocket::State;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{instrument, Span};
+
+#[get("/onsen/<onsen_id>/photos")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_onsen_photos(onsen_id: u32, request_id: RequestId) -> Json<Vec<PhotoResponse>> {
+    photos_response(PhotoTarget::Onsen, onsen_id)
+}
+
+#[post("/onsen/<onsen_id>/photos", data = "<form>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn post_onsen_photo(
+    onsen_id: u32,
+    form: Form<PhotoUploadForm<'_>>,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    queue: &State<Arc<ThumbnailQueue>>,
+    user: Authorized<OnsenWrite>,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    onsen_repository::get_onsen(onsen_id).ok_or(Status::NotFound)?;
+    let (format, bytes) = read_upload(&form).await?;
+    save_photo(
+        blob_store,
+        queue,
+        PhotoTarget::Onsen,
+        onsen_id,
+        user.user.id as u32,
+        format,
+        bytes,
+        form.into_inner(),
+    )
+    .await
+}
+
+#[put(
+    "/onsen/<onsen_id>/photos/<photo_id>",
+    format = "json",
+    data = "<photo_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_onsen_photo(
+    onsen_id: u32,
+    photo_id: u32,
+    photo_req: Json<PhotoRequest>,
+    _user: Authorized<OnsenWrite>,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    update_photo(PhotoTarget::Onsen, onsen_id, photo_id, &photo_req)
+}
+
+#[delete("/onsen/<onsen_id>/photos/<photo_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_onsen_photo(
+    onsen_id: u32,
+    photo_id: u32,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    _user: Authorized<OnsenWrite>,
+    request_id: RequestId,
+) -> Status {
+    remove_photo(blob_store, PhotoTarget::Onsen, onsen_id, photo_id)
+}
+
+#[get("/area/<area_id>/photos")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_area_photos(area_id: u32, request_id: RequestId) -> Json<Vec<PhotoResponse>> {
+    photos_response(PhotoTarget::Area, area_id)
+}
+
+#[post("/area/<area_id>/photos", data = "<form>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn post_area_photo(
+    area_id: u32,
+    form: Form<PhotoUploadForm<'_>>,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    queue: &State<Arc<ThumbnailQueue>>,
+    user: Authorized<AreaWrite>,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    area_repository::get_area(area_id).ok_or(Status::NotFound)?;
+    let (format, bytes) = read_upload(&form).await?;
+    save_photo(
+        blob_store,
+        queue,
+        PhotoTarget::Area,
+        area_id,
+        user.user.id as u32,
+        format,
+        bytes,
+        form.into_inner(),
+    )
+    .await
+}
+
+#[put(
+    "/area/<area_id>/photos/<photo_id>",
+    format = "json",
+    data = "<photo_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_area_photo(
+    area_id: u32,
+    photo_id: u32,
+    photo_req: Json<PhotoRequest>,
+    _user: Authorized<AreaWrite>,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    update_photo(PhotoTarget::Area, area_id, photo_id, &photo_req)
+}
+
+#[delete("/area/<area_id>/photos/<photo_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_area_photo(
+    area_id: u32,
+    photo_id: u32,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    _user: Authorized<AreaWrite>,
+    request_id: RequestId,
+) -> Status {
+    remove_photo(blob_store, PhotoTarget::Area, area_id, photo_id)
+}
+
+#[get("/me/visits/<visit_id>/photos")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_visit_photos(
+    visit_id: u32,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<Vec<PhotoResponse>>, Status> {
+    visit_repository::get_visit(user.id as u32, visit_id).ok_or(Status::NotFound)?;
+    Ok(photos_response(PhotoTarget::Visit, visit_id))
+}
+
+/// 入浴記録の写真は位置情報を消してから保存する
+#[post("/me/visits/<visit_id>/photos", data = "<form>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn post_visit_photo(
+    visit_id: u32,
+    form: Form<PhotoUploadForm<'_>>,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    queue: &State<Arc<ThumbnailQueue>>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    let user_id = user.0.id as u32;
+    visit_repository::get_visit(user_id, visit_id).ok_or(Status::NotFound)?;
+    let (format, bytes) = read_upload(&form).await?;
+    let bytes = strip_gps(format, &bytes).ok_or(Status::UnprocessableEntity)?;
+    save_photo(
+        blob_store,
+        queue,
+        PhotoTarget::Visit,
+        visit_id,
+        user_id,
+        format,
+        bytes,
+        form.into_inner(),
+    )
+    .await
+}
+
+#[put(
+    "/me/visits/<visit_id>/photos/<photo_id>",
+    format = "json",
+    data = "<photo_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_visit_photo(
+    visit_id: u32,
+    photo_id: u32,
+    photo_req: Json<PhotoRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    visit_repository::get_visit(user.0.id as u32, visit_id).ok_or(Status::NotFound)?;
+    update_photo(PhotoTarget::Visit, visit_id, photo_id, &photo_req)
+}
+
+#[delete("/me/visits/<visit_id>/photos/<photo_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_visit_photo(
+    visit_id: u32,
+    photo_id: u32,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Status {
+    if visit_repository::get_visit(user.0.id as u32, visit_id).is_none() {
+        return Status::NotFound;
+    }
+    remove_photo(blob_store, PhotoTarget::Visit, visit_id, photo_id)
+}
+
+/// 前のアイコンは置き換える。位置情報は消してから保存する
+#[post("/me/profile/avatar", data = "<form>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn post_avatar(
+    form: Form<PhotoUploadForm<'_>>,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    queue: &State<Arc<ThumbnailQueue>>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<PhotoResponse>, Status> {
+    let user_id = user.0.id as u32;
+    profile_repository::get_profile_by_user(user_id).ok_or(Status::NotFound)?;
+    let (format, bytes) = read_upload(&form).await?;
+    let bytes = strip_gps(format, &bytes).ok_or(Status::UnprocessableEntity)?;
+    let old_photos = photo_repository::get_photos(PhotoTarget::Avatar, user_id);
+    // 保存に失敗したときに前のアイコンが残るように、保存してから消す
+    let response = save_photo(
+        blob_store,
+        queue,
+        PhotoTarget::Avatar,
+        user_id,
+        user_id,
+        format,
+        bytes,
+        form.into_inner(),
+    )
+    .await?;
+    for photo in old_photos {
+        if photo_repository::delete_photo(photo.id) {
+            delete_blobs(blob_store, photo);
+        }
+    }
+    Ok(response)
+}
+
+#[delete("/me/profile/avatar")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_avatar(
+    blob_store: &State<Arc<dyn BlobStore>>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Status {
+    let photos = photo_repository::delete_photos(PhotoTarget::Avatar, user.0.id as u32);
+    if photos.is_empty() {
+        return Status::NotFound;
+    }
+    for photo in photos {
+        delete_blobs(blob_store, photo);
+    }
+    Status::NoContent
+}
+
+/// 入浴記録の写真は公開している記録か自分の記録のもの、アイコンはプロフィールを見られるものだけを返す
+#[get("/images/<photo_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn get_image(
+    photo_id: u32,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<ImageResponse, Status> {
+    let photo = photo_repository::get_photo(photo_id).ok_or(Status::NotFound)?;
+    check_visible(&photo, user.as_ref())?;
+    let bytes = get_blob(blob_store, photo.blob_key.clone())
+        .await?
+        .ok_or(Status::NotFound)?;
+    Ok(ImageResponse::new(
+        bytes,
+        content_type(photo.format),
+        photo.target,
+    ))
+}
+
+/// `<size>`は`320`・`640`・`1280`と、WebPの`320.webp`など
+#[get("/images/<photo_id>/<size>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub async fn get_image_thumbnail(
+    photo_id: u32,
+    size: &str,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<ThumbnailImageResponse, Status> {
+    let size = ThumbnailSize::parse(size).ok_or(Status::NotFound)?;
+    let photo = photo_repository::get_photo(photo_id).ok_or(Status::NotFound)?;
+    check_visible(&photo, user.as_ref())?;
+    let original =
+        || ThumbnailImageResponse::Original(Redirect::temporary(uri!(get_image(photo_id))));
+    if photo.thumbnail_status != ThumbnailStatus::Ready {
+        return Ok(original());
+    }
+    let Some(bytes) = get_blob(blob_store, photo.thumbnail_key(size)).await? else {
+        return Ok(original());
+    };
+    Ok(ThumbnailImageResponse::Image(ImageResponse::new(
+        bytes,
+        content_type(size.format(photo.format)),
+        photo.target,
+    )))
+}
+
+/// 見られない写真はNotFound
+fn check_visible(photo: &PhotoEntity, user: Option<&ValidatedUser>) -> Result<(), Status> {
+    if photo.target == PhotoTarget::Avatar {
+        let profile =
+            profile_repository::get_profile_by_user(photo.target_id).ok_or(Status::NotFound)?;
+        let viewer = viewer_of(&profile, user.map(|v| v.id as u32));
+        if !profile.privacy.profile.allows(viewer) {
+            return Err(Status::NotFound);
+        }
+        return Ok(());
+    }
+    if photo.target != PhotoTarget::Visit {
+        return Ok(());
+    }
+    let visit = visit_repository::get_visits_by_ids(&[photo.target_id])
+        .into_iter()
+        .next()
+        .ok_or(Status::NotFound)?;
+    let (privacy, viewer) = owner_privacy(visit.user_id, user.map(|v| v.id as u32));
+    if !privacy.shows_record(privacy.visits, visit.is_public, viewer) {
+        return Err(Status::NotFound);
+    }
+    Ok(())
+}
+
+/// 対象ごとのカバー写真。一覧ではページの対象をまとめて読み込む
+pub fn cover_photos(target: PhotoTarget, target_ids: &[u32]) -> HashMap<u32, PhotoEntity> {
+    photo_repository::get_cover_photos(target, target_ids)
+        .into_iter()
+        .map(|v| (v.target_id, v))
+        .collect()
+}
+
+fn content_type(format: ImageFormat) -> ContentType {
+    ContentType::from_str(&format.to_string()).expect("ImageFormat is a valid media type")
+}
+
+fn photos_response(target: PhotoTarget, target_id: u32) -> Json<Vec<PhotoResponse>> {
+    let photos = photo_repository::get_photos(target, target_id);
+    Json(photos.into_iter().map(PhotoResponse::from).collect())
+}
+
+/// 申告されたContent-Typeと先頭のバイト列がどちらも対応している形式で一致するか確認する
+async fn read_upload(form: &PhotoUploadForm<'_>) -> Result<(ImageFormat, Vec<u8>), Status> {
+    if !form.file.is_complete() {
+        return Err(Status::PayloadTooLarge);
+    }
+    if !PhotoEntity::is_valid_caption(form.caption.as_deref().unwrap_or_default()) {
+        return Err(Status::BadRequest);
+    }
+    let declared = form
+        .file
+        .content_type()
+        .and_then(|v| ImageFormat::from_str(&format!("{}/{}", v.top(), v.sub())).ok())
+        .ok_or(Status::UnsupportedMediaType)?;
+    let mut bytes = Vec::new();
+    form.file
+        .open()
+        .await
+        .map_err(|_| Status::BadRequest)?
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(|_| Status::BadRequest)?;
+    if ImageFormat::detect(&bytes) != Some(declared) {
+        return Err(Status::UnsupportedMediaType);
+    }
+    Ok((declared, bytes))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn save_photo(
+    blob_store: &Arc<dyn BlobStore>,
+    queue: &ThumbnailQueue,
+    target: PhotoTarget,
+    target_id: u32,
+    user_id: u32,
+    format: ImageFormat,
+    bytes: Vec<u8>,
+    form: PhotoUploadForm<'_>,
+) -> Result<Json<PhotoResponse>, Status> {
+    let blob_key = new_blob_key("photos", format.extension());
+    let byte_size = bytes.len() as u32;
+    let store = Arc::clone(blob_store);
+    let key = blob_key.clone();
+    task::spawn_blocking(move || store.put(&key, &format.to_string(), &bytes))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()))
+        .map_err(|e| {
+            error!("Failed to save image: {}", e);
+            Status::InternalServerError
+        })?;
+    let photo = photo_repository::post_photo(PhotoEntity {
+        id: 0,
+        target,
+        target_id,
+        user_id,
+        blob_key,
+        format,
+        byte_size,
+        caption: form.caption.unwrap_or_default(),
+        position: 0,
+        is_cover: form.is_cover,
+        thumbnail_status: ThumbnailStatus::Pending,
+    });
+    queue.wake();
+    Ok(Json(PhotoResponse::from(photo)))
+}
+
+fn update_photo(
+    target: PhotoTarget,
+    target_id: u32,
+    photo_id: u32,
+    photo_req: &PhotoRequest,
+) -> Result<Json<PhotoResponse>, Status> {
+    let photo = find_photo(target, target_id, photo_id).ok_or(Status::NotFound)?;
+    let photo = photo_req.apply(photo).ok_or(Status::BadRequest)?;
+    if !photo_repository::put_photo(&photo) {
+        return Err(Status::NotFound);
+    }
+    let photo = photo_repository::get_photo(photo_id).ok_or(Status::NotFound)?;
+    Ok(Json(PhotoResponse::from(photo)))
+}
+
+fn remove_photo(
+    blob_store: &Arc<dyn BlobStore>,
+    target: PhotoTarget,
+    target_id: u32,
+    photo_id: u32,
+) -> Status {
+    let Some(photo) = find_photo(target, target_id, photo_id) else {
+        return Status::NotFound;
+    };
+    if !photo_repository::delete_photo(photo_id) {
+        return Status::NotFound;
+    }
+    delete_blobs(blob_store, photo);
+    Status::NoContent
+}
+
+/// ブロッキングするので`spawn_blocking`で読む
+async fn get_blob(blob_store: &Arc<dyn BlobStore>, key: String) -> Result<Option<Vec<u8>>, Status> {
+    let store = Arc::clone(blob_store);
+    task::spawn_blocking(move || store.get(&key))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result.map_err(|e| e.to_string()))
+        .map_err(|e| {
+            error!("Failed to read image: {}", e);
+            Status::InternalServerError
+        })
+}
+
+/// 元の画像とサムネイルを裏で消す。写真は削除済みなので失敗してもログだけ
+pub fn delete_blobs(blob_store: &Arc<dyn BlobStore>, photo: PhotoEntity) {
+    let store = Arc::clone(blob_store);
+    let span = Span::current();
+    task::spawn_blocking(move || {
+        span.in_scope(|| {
+            for blob_key in photo.blob_keys() {
+                if let Err(e) = store.delete(&blob_key) {
+                    error!("Failed to delete image: {}", e);
+                }
+            }
+        })
+    });
+}
+
+/// URLの対象についている写真か
+fn find_photo(target: PhotoTarget, target_id: u32, photo_id: u32) -> Option<PhotoEntity> {
+    photo_repository::get_photo(photo_id)
+        .filter(|photo| photo.target == target && photo.target_id == target_id)
+}
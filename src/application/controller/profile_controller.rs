@@ -0,0 +1,359 @@
+use crate::application::api_model::profile_api_model::*;
+use crate::application::api_model::review_api_model::{HotelReviewResponse, OnsenReviewResponse};
+use crate::application::api_model::trip_response::TripResponse;
+use crate::application::api_model::visit_response::VisitResponse;
+use crate::application::controller::request_guard::{ValidatedUser, VerifiedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::feed::{merge_feed, FeedContent, FeedCursor, FeedItem, FeedKind};
+use crate::domain::photo_entity::{PhotoEntity, PhotoTarget};
+use crate::domain::profile_entity::{
+    FollowEntity, FollowStatus, PrivacySettings, ProfileEntity, Viewer, Visibility,
+};
+use crate::infrastructure::repository::{
+    follow_repository, photo_repository, profile_repository, review_repository, trip_repository,
+    visit_repository,
+};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::instrument;
+
+/// プロフィールに載せる入浴記録と口コミの件数。新しいものから
+const PROFILE_ITEM_LIMIT: u32 = 50;
+const FEED_DEFAULT_LIMIT: u32 = 20;
+const FEED_MAX_LIMIT: u32 = 100;
+
+#[get("/me/profile")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_my_profile(
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<ProfileResponse>, Status> {
+    let profile =
+        profile_repository::get_profile_by_user(user.id as u32).ok_or(Status::NotFound)?;
+    Ok(Json(profile_response(profile, true)))
+}
+
+/// なければ作る。ハンドルをほかの人が使っていればConflict
+#[put("/me/profile", format = "json", data = "<profile_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_my_profile(
+    profile_req: Json<ProfileRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<ProfileResponse>, Status> {
+    let profile = profile_req
+        .create_entity(user.0.id as u32)
+        .ok_or(Status::BadRequest)?;
+    if !profile_repository::put_profile(&profile) {
+        return Err(Status::Conflict);
+    }
+    Ok(Json(profile_response(profile, true)))
+}
+
+/// 公開範囲の外にあるプロフィールはNotFound
+#[get("/profiles/<handle>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_profile(
+    handle: &str,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<Json<ProfileDetailResponse>, Status> {
+    let profile = profile_repository::get_profile(handle).ok_or(Status::NotFound)?;
+    let user_id = user.map(|v| v.id as u32);
+    let viewer = viewer_of(&profile, user_id);
+    if !profile.privacy.profile.allows(viewer) {
+        return Err(Status::NotFound);
+    }
+    let follow_status = user_id
+        .and_then(|v| follow_repository::get_follow(v, profile.user_id))
+        .map(|v| v.status.to_string());
+    let owner = [profile.user_id];
+    let privacy = profile.privacy;
+    let visits = privacy.visits.allows(viewer).then(|| {
+        visit_repository::get_recent_public_visits(&owner, None, PROFILE_ITEM_LIMIT)
+            .into_iter()
+            .filter_map(|v| match v.content {
+                FeedContent::Visit(visit) => Some(VisitResponse::from(visit)),
+                _ => None,
+            })
+            .collect()
+    });
+    let trips = privacy.trips.allows(viewer).then(|| {
+        trip_repository::get_trips(profile.user_id)
+            .into_iter()
+            .filter(|v| v.is_public)
+            .map(TripResponse::from)
+            .collect()
+    });
+    let onsen_reviews = privacy.reviews.allows(viewer).then(|| {
+        review_repository::get_recent_onsen_reviews(&owner, None, PROFILE_ITEM_LIMIT)
+            .into_iter()
+            .filter_map(|v| match v.content {
+                FeedContent::OnsenReview(review) => Some(OnsenReviewResponse::from(review)),
+                _ => None,
+            })
+            .collect()
+    });
+    let hotel_reviews = privacy.reviews.allows(viewer).then(|| {
+        review_repository::get_recent_hotel_reviews(&owner, None, PROFILE_ITEM_LIMIT)
+            .into_iter()
+            .filter_map(|v| match v.content {
+                FeedContent::HotelReview(review) => Some(HotelReviewResponse::from(review)),
+                _ => None,
+            })
+            .collect()
+    });
+    Ok(Json(ProfileDetailResponse {
+        profile: profile_response(profile, viewer == Viewer::Owner),
+        follow_status,
+        visits,
+        trips,
+        onsen_reviews,
+        hotel_reviews,
+    }))
+}
+
+/// 承認制のプロフィールへのフォローは`pending`になる。フォローするには自分のプロフィールが必要
+#[put("/profiles/<handle>/follow")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_follow(
+    handle: &str,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<FollowResponse>, Status> {
+    let user_id = user.0.id as u32;
+    let followee = profile_repository::get_profile(handle)
+        .filter(|v| v.privacy.profile != Visibility::Private || v.user_id == user_id)
+        .ok_or(Status::NotFound)?;
+    let follow = FollowEntity::new(user_id, &followee).ok_or(Status::BadRequest)?;
+    if profile_repository::get_profile_by_user(user_id).is_none() {
+        return Err(Status::Forbidden);
+    }
+    let follow = follow_repository::post_follow(follow);
+    Ok(Json(FollowResponse {
+        profile: summary_responses(vec![followee]).remove(0),
+        status: follow.status.to_string(),
+    }))
+}
+
+/// フォローの解除と申請の取り下げ
+#[delete("/profiles/<handle>/follow")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_follow(handle: &str, user: VerifiedUser, request_id: RequestId) -> Status {
+    let Some(followee) = profile_repository::get_profile(handle) else {
+        return Status::NotFound;
+    };
+    if !follow_repository::delete_follow(user.0.id as u32, followee.user_id) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// `status`を`pending`にすると承認待ちの申請。省略すると承認済み
+#[get("/me/following?<status>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_following(
+    status: Option<String>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<Vec<FollowResponse>>, Status> {
+    let status = parse_follow_status(status)?;
+    let follows = follow_repository::get_followees(user.id as u32, status);
+    let user_ids: Vec<u32> = follows.iter().map(|v| v.followee_id).collect();
+    Ok(Json(follow_responses(&user_ids, status)))
+}
+
+/// `status`を`pending`にすると承認待ちの申請。省略すると承認済み
+#[get("/me/followers?<status>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_followers(
+    status: Option<String>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<Vec<FollowResponse>>, Status> {
+    let status = parse_follow_status(status)?;
+    let follows = follow_repository::get_followers(user.id as u32, status);
+    let user_ids: Vec<u32> = follows.iter().map(|v| v.follower_id).collect();
+    Ok(Json(follow_responses(&user_ids, status)))
+}
+
+/// フォローの申請を承認する
+#[put("/me/followers/<handle>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_follower(handle: &str, user: VerifiedUser, request_id: RequestId) -> Status {
+    let Some(follower) = profile_repository::get_profile(handle) else {
+        return Status::NotFound;
+    };
+    if !follow_repository::accept_follow(follower.user_id, user.0.id as u32) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// フォロワーを外す。申請の却下にも使う
+#[delete("/me/followers/<handle>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_follower(handle: &str, user: VerifiedUser, request_id: RequestId) -> Status {
+    let Some(follower) = profile_repository::get_profile(handle) else {
+        return Status::NotFound;
+    };
+    if !follow_repository::delete_follow(follower.user_id, user.0.id as u32) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// フォローしている人の公開している入浴記録と承認済みの口コミ。新しい順
+#[get("/me/feed?<before>&<limit>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_feed(
+    before: Option<String>,
+    limit: Option<u32>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<FeedResponse>, Status> {
+    let before = before
+        .map(|v| FeedCursor::from_str(&v))
+        .transpose()
+        .map_err(|_| Status::BadRequest)?;
+    let limit = limit.unwrap_or(FEED_DEFAULT_LIMIT);
+    if !(1..=FEED_MAX_LIMIT).contains(&limit) {
+        return Err(Status::BadRequest);
+    }
+    let followee_ids: Vec<u32> =
+        follow_repository::get_followees(user.id as u32, FollowStatus::Accepted)
+            .into_iter()
+            .map(|v| v.followee_id)
+            .collect();
+    let profiles = profile_repository::get_profiles_by_user_ids(&followee_ids);
+    let shown_ids = |item: fn(&ProfileEntity) -> Visibility| -> Vec<u32> {
+        profiles
+            .iter()
+            .filter(|v| v.privacy.shows(item(v), Viewer::Follower))
+            .map(|v| v.user_id)
+            .collect()
+    };
+    let visit_user_ids = shown_ids(|v| v.privacy.visits);
+    let review_user_ids = shown_ids(|v| v.privacy.reviews);
+    let items = merge_feed(
+        vec![
+            visit_repository::get_recent_public_visits(
+                &visit_user_ids,
+                before.map(|v| v.bound(FeedKind::Visit)),
+                limit,
+            ),
+            review_repository::get_recent_onsen_reviews(
+                &review_user_ids,
+                before.map(|v| v.bound(FeedKind::OnsenReview)),
+                limit,
+            ),
+            review_repository::get_recent_hotel_reviews(
+                &review_user_ids,
+                before.map(|v| v.bound(FeedKind::HotelReview)),
+                limit,
+            ),
+        ],
+        limit as usize,
+    );
+    let next_before = (items.len() == limit as usize)
+        .then(|| items.last())
+        .flatten()
+        .map(|v| FeedCursor::of(v).to_string());
+    let profiles: HashMap<u32, ProfileEntity> =
+        profiles.into_iter().map(|v| (v.user_id, v)).collect();
+    let avatars = avatars(&items.iter().map(|v| v.user_id).collect::<Vec<u32>>());
+    let items = items
+        .into_iter()
+        .filter_map(|item: FeedItem| {
+            let profile = profiles.get(&item.user_id)?.clone();
+            let summary = ProfileSummaryResponse::new(profile, avatars.get(&item.user_id));
+            Some(FeedItemResponse::new(item, summary))
+        })
+        .collect();
+    Ok(Json(FeedResponse { items, next_before }))
+}
+
+/// フォローが承認されていればフォロワーとして扱う
+pub fn viewer_of(profile: &ProfileEntity, user_id: Option<u32>) -> Viewer {
+    let Some(user_id) = user_id else {
+        return Viewer::Other;
+    };
+    if user_id == profile.user_id {
+        return Viewer::Owner;
+    }
+    match follow_repository::get_follow(user_id, profile.user_id) {
+        Some(follow) if follow.status == FollowStatus::Accepted => Viewer::Follower,
+        _ => Viewer::Other,
+    }
+}
+
+/// 持ち主の公開範囲と見ている人との関係。プロフィールがなければ既定の公開範囲
+pub fn owner_privacy(owner_id: u32, user_id: Option<u32>) -> (PrivacySettings, Viewer) {
+    match profile_repository::get_profile_by_user(owner_id) {
+        Some(profile) => (profile.privacy, viewer_of(&profile, user_id)),
+        None if user_id == Some(owner_id) => (PrivacySettings::default(), Viewer::Owner),
+        None => (PrivacySettings::default(), Viewer::Other),
+    }
+}
+
+fn parse_follow_status(status: Option<String>) -> Result<FollowStatus, Status> {
+    match status {
+        Some(status) => FollowStatus::from_str(&status).map_err(|_| Status::BadRequest),
+        None => Ok(FollowStatus::Accepted),
+    }
+}
+
+fn profile_response(profile: ProfileEntity, is_owner: bool) -> ProfileResponse {
+    let avatar = avatars(&[profile.user_id]).remove(&profile.user_id);
+    let counts = follow_repository::count_follows(profile.user_id);
+    ProfileResponse::new(profile, avatar.as_ref(), counts, is_owner)
+}
+
+/// ユーザーごとのアイコン
+fn avatars(user_ids: &[u32]) -> HashMap<u32, PhotoEntity> {
+    photo_repository::get_photos_by_target_ids(PhotoTarget::Avatar, user_ids)
+        .into_iter()
+        .map(|v| (v.target_id, v))
+        .collect()
+}
+
+fn summary_responses(profiles: Vec<ProfileEntity>) -> Vec<ProfileSummaryResponse> {
+    let user_ids: Vec<u32> = profiles.iter().map(|v| v.user_id).collect();
+    let avatars = avatars(&user_ids);
+    profiles
+        .into_iter()
+        .map(|v| {
+            let avatar = avatars.get(&v.user_id);
+            ProfileSummaryResponse::new(v, avatar)
+        })
+        .collect()
+}
+
+/// ユーザーごとのプロフィール。プロフィールを作っていないユーザーは含めない
+pub fn profile_summaries(user_ids: &[u32]) -> HashMap<u32, ProfileSummaryResponse> {
+    let profiles = profile_repository::get_profiles_by_user_ids(user_ids);
+    let user_ids: Vec<u32> = profiles.iter().map(|v| v.user_id).collect();
+    user_ids
+        .into_iter()
+        .zip(summary_responses(profiles))
+        .collect()
+}
+
+/// `user_ids`の順に並べる
+fn follow_responses(user_ids: &[u32], status: FollowStatus) -> Vec<FollowResponse> {
+    let mut profiles: HashMap<u32, ProfileEntity> =
+        profile_repository::get_profiles_by_user_ids(user_ids)
+            .into_iter()
+            .map(|v| (v.user_id, v))
+            .collect();
+    let profiles: Vec<ProfileEntity> = user_ids.iter().filter_map(|v| profiles.remove(v)).collect();
+    summary_responses(profiles)
+        .into_iter()
+        .map(|profile| FollowResponse {
+            profile,
+            status: status.to_string(),
+        })
+        .collect()
+}
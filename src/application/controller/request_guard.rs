@@ -1,18 +1,169 @@
-use crate::application::auth::jwt::decode_jwt;
-use crate::infrastructure::repository::user_repository;
+use crate::application::auth::api_key::{hash_api_key, parse_scopes};
+use crate::application::auth::jwt::{JwtError, JwtKeys};
+use crate::application::fairing::two_factor::TwoFactorConfig;
+use crate::application::rate_limit::{client_ip, SigninRateLimiter};
+use crate::domain::role::{Permission, Role};
+use crate::infrastructure::metrics::JWT_VALIDATION_FAILURES_TOTAL;
+use crate::infrastructure::rdb::diesel_model::diesel_user::User;
+use crate::infrastructure::repository::{api_key_repository, user_repository};
 use chrono::{TimeZone, Utc};
 use rocket::http::Status;
 use rocket::outcome::Outcome;
 use rocket::request::{self, FromRequest, Request};
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::str::FromStr;
 
 pub struct ValidatedUser {
+    pub id: i32,
     pub email: String,
-    pub role: String,
+    pub role: Role,
+    pub verified: bool,
+    /// 2段階認証を済ませたアクセストークンか
+    pub mfa: bool,
+    /// APIキーで認証したときのスコープ。アクセストークンならNoneでロールのパーミッションをすべて使える
+    pub scopes: Option<Vec<Permission>>,
 }
 
-#[derive(Debug)]
+impl ValidatedUser {
+    fn new(user: User, mfa: bool, scopes: Option<Vec<Permission>>) -> Self {
+        ValidatedUser {
+            id: user.id,
+            email: user.email,
+            // 未知のロールは最小権限として扱う
+            role: Role::from_str(&user.role).unwrap_or(Role::Viewer),
+            verified: user.verified_at.is_some(),
+            mfa,
+            scopes,
+        }
+    }
+
+    /// APIキーで認証したか。APIキーの管理や2段階認証の設定はアクセストークンでしかできない
+    pub fn is_api_key(&self) -> bool {
+        self.scopes.is_some()
+    }
+
+    /// ロールがパーミッションを持ち、APIキーならスコープにも含まれているか
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.role.has_permission(permission)
+            && self
+                .scopes
+                .as_ref()
+                .map_or(true, |scopes| scopes.contains(&permission))
+    }
+}
+
+/// メールアドレスを確認済みのユーザー
+pub struct VerifiedUser(pub ValidatedUser);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ApiTokenError {
     Missing,
+    Malformed,
+    Expired,
+    Invalid,
+    UnknownUser,
+    Unverified,
+    TwoFactorRequired,
+    InsufficientPermission(Permission),
+}
+
+impl ApiTokenError {
+    pub fn status(&self) -> Status {
+        match self {
+            ApiTokenError::Malformed => Status::BadRequest,
+            ApiTokenError::Unverified
+            | ApiTokenError::TwoFactorRequired
+            | ApiTokenError::InsufficientPermission(_) => Status::Forbidden,
+            _ => Status::Unauthorized,
+        }
+    }
+
+    /// RFC 6750のWWW-Authenticateヘッダーの値
+    pub fn www_authenticate(&self) -> String {
+        let (error, description) = match self {
+            ApiTokenError::Missing => return format!("Bearer realm=\"{}\"", REALM),
+            ApiTokenError::Malformed => (
+                "invalid_request",
+                "The Authorization header must be a Bearer token or an API key",
+            ),
+            ApiTokenError::Expired => ("invalid_token", "The access token expired"),
+            ApiTokenError::Invalid => ("invalid_token", "The access token is invalid"),
+            ApiTokenError::UnknownUser => ("invalid_token", "The user of the access token does not exist"),
+            ApiTokenError::Unverified => (
+                "insufficient_scope",
+                "The email address is not verified",
+            ),
+            ApiTokenError::TwoFactorRequired => (
+                "insufficient_scope",
+                "The role requires signing in with two-factor authentication",
+            ),
+            ApiTokenError::InsufficientPermission(permission) => {
+                return format!(
+                    "Bearer realm=\"{}\", error=\"insufficient_scope\", error_description=\"The request requires {} permission\", scope=\"{}\"",
+                    REALM, permission, permission
+                )
+            }
+        };
+        format!(
+            "Bearer realm=\"{}\", error=\"{}\", error_description=\"{}\"",
+            REALM, error, description
+        )
+    }
+
+    fn metric_reason(&self) -> Option<&'static str> {
+        match self {
+            ApiTokenError::Missing => Some("missing"),
+            ApiTokenError::Malformed => Some("malformed"),
+            ApiTokenError::Expired => Some("expired"),
+            ApiTokenError::Invalid => Some("invalid"),
+            ApiTokenError::UnknownUser => Some("unknown_user"),
+            ApiTokenError::Unverified
+            | ApiTokenError::TwoFactorRequired
+            | ApiTokenError::InsufficientPermission(_) => None,
+        }
+    }
+
+    /// レスポンスにWWW-Authenticateヘッダーをつけられるようにリクエストに記録する
+    fn fail<S>(self, request: &Request<'_>) -> request::Outcome<S, Self> {
+        if let Some(reason) = self.metric_reason() {
+            JWT_VALIDATION_FAILURES_TOTAL
+                .with_label_values(&[reason])
+                .inc();
+        }
+        request.local_cache(|| FailedAuthentication(Some(self)));
+        Outcome::Error((self.status(), self))
+    }
+}
+
+const REALM: &str = "onsen_tabi";
+
+/// 認証に失敗した理由。リクエストごとにキャッシュする
+pub struct FailedAuthentication(pub Option<ApiTokenError>);
+
+#[derive(Debug, PartialEq, Eq)]
+enum Credential<'a> {
+    Bearer(&'a str),
+    ApiKey(&'a str),
+}
+
+/// `Authorization`ヘッダーからBearerトークンかAPIキーを取り出す。スキームの大文字小文字は区別しない
+fn parse_authorization(authorization: &str) -> Result<Credential<'_>, ApiTokenError> {
+    let (scheme, token) = authorization
+        .trim()
+        .split_once(' ')
+        .ok_or(ApiTokenError::Malformed)?;
+    let token = token.trim_start_matches(' ');
+    if token.is_empty() || token.contains(' ') {
+        return Err(ApiTokenError::Malformed);
+    }
+    if scheme.eq_ignore_ascii_case("Bearer") {
+        Ok(Credential::Bearer(token))
+    } else if scheme.eq_ignore_ascii_case("ApiKey") {
+        Ok(Credential::ApiKey(token))
+    } else {
+        Err(ApiTokenError::Malformed)
+    }
 }
 
 #[rocket::async_trait]
@@ -20,30 +171,250 @@ impl<'r> FromRequest<'r> for ValidatedUser {
     type Error = ApiTokenError;
 
     async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        let bearer_token = request.headers().get_one("Authorization");
+        let Some(authorization) = request.headers().get_one("Authorization") else {
+            return ApiTokenError::Missing.fail(request);
+        };
+        let token = match parse_authorization(authorization) {
+            Ok(Credential::Bearer(token)) => token,
+            Ok(Credential::ApiKey(key)) => return validate_api_key(request, key),
+            Err(error) => return error.fail(request),
+        };
+        let Some(jwt_keys) = request.rocket().state::<JwtKeys>() else {
+            return ApiTokenError::Invalid.fail(request);
+        };
+        let claims = match jwt_keys.decode(token) {
+            Ok(claims) => claims,
+            Err(JwtError::Expired) => return ApiTokenError::Expired.fail(request),
+            Err(JwtError::Invalid) => return ApiTokenError::Invalid.fail(request),
+        };
+        let exp = Utc.timestamp_opt(claims.exp, 0).single();
+        if exp.map_or(true, |exp| exp < Utc::now()) {
+            return ApiTokenError::Expired.fail(request);
+        }
 
-        if let Some(bearer_token) = bearer_token {
-            let prefix = &bearer_token[..6];
-            if prefix != "Bearer" {
-                return Outcome::Error((Status::Unauthorized, ApiTokenError::Missing));
-            }
-            let token = &bearer_token[7..];
-            if let Some(claims) = decode_jwt(token) {
-                let email = claims.email;
-                let exp = Utc.timestamp_opt(claims.exp as i64, 0).unwrap();
-                if exp < Utc::now() {
-                    return Outcome::Error((Status::Unauthorized, ApiTokenError::Missing));
-                }
-
-                let user = user_repository::get_user(&email);
-                if let Some(user) = user {
-                    return Outcome::Success(ValidatedUser {
-                        email: user.email,
-                        role: user.role,
-                    });
-                }
+        let Some(user) = user_repository::get_user(&claims.email) else {
+            return ApiTokenError::UnknownUser.fail(request);
+        };
+        Outcome::Success(ValidatedUser::new(user, claims.mfa, None))
+    }
+}
+
+/// APIキーを検証して最終使用日時を記録する
+fn validate_api_key(
+    request: &Request<'_>,
+    key: &str,
+) -> request::Outcome<ValidatedUser, ApiTokenError> {
+    let Some(api_key) = api_key_repository::get_api_key_by_hash(&hash_api_key(key)) else {
+        return ApiTokenError::Invalid.fail(request);
+    };
+    if api_key
+        .expires_at
+        .is_some_and(|v| v < Utc::now().naive_utc())
+    {
+        return ApiTokenError::Expired.fail(request);
+    }
+    let Some(user) = user_repository::get_user_by_id(api_key.user_id) else {
+        return ApiTokenError::UnknownUser.fail(request);
+    };
+    api_key_repository::put_api_key_last_used(api_key.id);
+    Outcome::Success(ValidatedUser::new(
+        user,
+        api_key.mfa,
+        Some(parse_scopes(&api_key.scopes)),
+    ))
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for VerifiedUser {
+    type Error = ApiTokenError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let user = match request.guard::<ValidatedUser>().await {
+            Outcome::Success(user) => user,
+            Outcome::Error(error) => return Outcome::Error(error),
+            Outcome::Forward(status) => return Outcome::Forward(status),
+        };
+        if !user.verified {
+            return ApiTokenError::Unverified.fail(request);
+        }
+        Outcome::Success(VerifiedUser(user))
+    }
+}
+
+/// `Authorized<P>`で要求するパーミッションを表すマーカー
+pub trait RequiredPermission {
+    const PERMISSION: Permission;
+}
+
+macro_rules! required_permission {
+    ($($marker:ident),*) => {
+        $(
+            pub struct $marker;
+
+            impl RequiredPermission for $marker {
+                const PERMISSION: Permission = Permission::$marker;
             }
+        )*
+    };
+}
+
+required_permission!(
+    OnsenWrite,
+    HotelWrite,
+    AreaWrite,
+    UserManage,
+    ReviewModerate,
+    AchievementManage,
+    CommentModerate,
+    MetricsRead
+);
+
+/// ロールが`P`のパーミッションを持つ確認済みユーザー
+pub struct Authorized<P: RequiredPermission> {
+    pub user: ValidatedUser,
+    permission: PhantomData<P>,
+}
+
+#[rocket::async_trait]
+impl<'r, P: RequiredPermission> FromRequest<'r> for Authorized<P> {
+    type Error = ApiTokenError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let user = match request.guard::<VerifiedUser>().await {
+            Outcome::Success(VerifiedUser(user)) => user,
+            Outcome::Error(error) => return Outcome::Error(error),
+            Outcome::Forward(status) => return Outcome::Forward(status),
+        };
+        if !user.has_permission(P::PERMISSION) {
+            return ApiTokenError::InsufficientPermission(P::PERMISSION).fail(request);
         }
-        return Outcome::Error((Status::Unauthorized, ApiTokenError::Missing));
+        let two_factor_required = request
+            .rocket()
+            .state::<TwoFactorConfig>()
+            .is_some_and(|config| config.is_required(user.role));
+        if two_factor_required && !user.mfa {
+            return ApiTokenError::TwoFactorRequired.fail(request);
+        }
+        Outcome::Success(Authorized {
+            user,
+            permission: PhantomData,
+        })
+    }
+}
+
+/// レートリミットに使うクライアントのIPアドレス。
+/// ロードバランサーの後ろでは、信頼できるプロキシが付け足したX-Forwarded-Forから取る
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientIp {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let trusted_proxies = request
+            .rocket()
+            .state::<SigninRateLimiter>()
+            .map_or(0, |v| v.trusted_proxies());
+        let forwarded_for: Vec<&str> = request.headers().get("X-Forwarded-For").collect();
+        let remote = request.remote().map(|v| v.ip());
+        Outcome::Success(ClientIp(client_ip(remote, &forwarded_for, trusted_proxies)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::controller::request_guard::{
+        parse_authorization, ApiTokenError, Credential, ValidatedUser,
+    };
+    use crate::domain::role::{Permission, Role};
+
+    fn user(scopes: Option<Vec<Permission>>) -> ValidatedUser {
+        ValidatedUser {
+            id: 1,
+            email: "a@example.com".to_string(),
+            role: Role::Editor,
+            verified: true,
+            mfa: false,
+            scopes,
+        }
+    }
+
+    #[test]
+    fn test_api_key_user() {
+        let access_token = user(None);
+        assert!(!access_token.is_api_key());
+        assert!(access_token.has_permission(Permission::OnsenWrite));
+
+        // スコープのないAPIキーでもAPIキーとして扱う
+        let api_key = user(Some(vec![]));
+        assert!(api_key.is_api_key());
+        assert!(!api_key.has_permission(Permission::OnsenWrite));
+
+        let api_key = user(Some(vec![Permission::OnsenWrite, Permission::UserManage]));
+        assert!(api_key.has_permission(Permission::OnsenWrite));
+        assert!(!api_key.has_permission(Permission::UserManage));
+    }
+
+    #[test]
+    fn test_parse_authorization() {
+        assert_eq!(
+            parse_authorization("Bearer abc.def.ghi"),
+            Ok(Credential::Bearer("abc.def.ghi"))
+        );
+        assert_eq!(
+            parse_authorization("bearer abc"),
+            Ok(Credential::Bearer("abc"))
+        );
+        assert_eq!(
+            parse_authorization("BEARER  abc"),
+            Ok(Credential::Bearer("abc"))
+        );
+        assert_eq!(
+            parse_authorization("ApiKey onsen_abc"),
+            Ok(Credential::ApiKey("onsen_abc"))
+        );
+        assert_eq!(
+            parse_authorization("apikey abc"),
+            Ok(Credential::ApiKey("abc"))
+        );
+        assert_eq!(parse_authorization(""), Err(ApiTokenError::Malformed));
+        assert_eq!(parse_authorization("Bear"), Err(ApiTokenError::Malformed));
+        assert_eq!(
+            parse_authorization("Bearer "),
+            Err(ApiTokenError::Malformed)
+        );
+        assert_eq!(
+            parse_authorization("ApiKey "),
+            Err(ApiTokenError::Malformed)
+        );
+        assert_eq!(
+            parse_authorization("Basic abc"),
+            Err(ApiTokenError::Malformed)
+        );
+        assert_eq!(
+            parse_authorization("Bearer a b"),
+            Err(ApiTokenError::Malformed)
+        );
+        assert_eq!(
+            parse_authorization("Bearerabc"),
+            Err(ApiTokenError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_www_authenticate() {
+        assert_eq!(
+            ApiTokenError::Missing.www_authenticate(),
+            "Bearer realm=\"onsen_tabi\""
+        );
+        assert_eq!(
+            ApiTokenError::Expired.www_authenticate(),
+            "Bearer realm=\"onsen_tabi\", error=\"invalid_token\", error_description=\"The access token expired\""
+        );
+        assert!(
+            ApiTokenError::InsufficientPermission(Permission::UserManage)
+                .www_authenticate()
+                .contains("error=\"insufficient_scope\"")
+        );
     }
 }
@@ -0,0 +1,139 @@
+use crate::application::api_model::review_api_model::*;
+use crate::application::controller::request_guard::{Authorized, ReviewModerate, VerifiedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::review_entity::ReviewStatus;
+use crate::infrastructure::repository::{hotel_repository, onsen_repository, review_repository};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::str::FromStr;
+use tracing::instrument;
+
+/// 承認済みの口コミ
+#[get("/onsen/<onsen_id>/reviews")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_onsen_reviews(onsen_id: u32, request_id: RequestId) -> Json<Vec<OnsenReviewResponse>> {
+    let reviews = review_repository::get_onsen_reviews(Some(onsen_id), ReviewStatus::Approved);
+    Json(reviews.into_iter().map(OnsenReviewResponse::from).collect())
+}
+
+/// 自分の評価を登録・更新する。本文を書くと承認されるまで公開しない
+#[put("/onsen/<onsen_id>/review", format = "json", data = "<review_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_onsen_review(
+    onsen_id: u32,
+    review_req: Json<OnsenReviewRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<OnsenReviewResponse>, Status> {
+    if onsen_repository::get_onsen(onsen_id).is_none() {
+        return Err(Status::NotFound);
+    }
+    let user_id = user.0.id as u32;
+    let current = review_repository::get_onsen_review(user_id, onsen_id);
+    let review = review_req
+        .create_entity(user_id, onsen_id, current)
+        .ok_or(Status::BadRequest)?;
+    review_repository::put_onsen_review(&review);
+    let saved = review_repository::get_onsen_review(user_id, onsen_id).ok_or(Status::NotFound)?;
+    Ok(Json(OnsenReviewResponse::from(saved)))
+}
+
+#[delete("/onsen/<onsen_id>/review")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_onsen_review(onsen_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    if !review_repository::delete_onsen_review(user.0.id as u32, onsen_id) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// 承認済みの口コミ
+#[get("/hotel/<hotel_id>/reviews")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_hotel_reviews(hotel_id: u32, request_id: RequestId) -> Json<Vec<HotelReviewResponse>> {
+    let reviews = review_repository::get_hotel_reviews(Some(hotel_id), ReviewStatus::Approved);
+    Json(reviews.into_iter().map(HotelReviewResponse::from).collect())
+}
+
+/// 自分の評価を登録・更新する。本文を書くと承認されるまで公開しない
+#[put("/hotel/<hotel_id>/review", format = "json", data = "<review_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_hotel_review(
+    hotel_id: u32,
+    review_req: Json<HotelReviewRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<HotelReviewResponse>, Status> {
+    if hotel_repository::get_hotel_with_onsen(hotel_id).is_none() {
+        return Err(Status::NotFound);
+    }
+    let user_id = user.0.id as u32;
+    let current = review_repository::get_hotel_review(user_id, hotel_id);
+    let review = review_req
+        .create_entity(user_id, hotel_id, current)
+        .ok_or(Status::BadRequest)?;
+    review_repository::put_hotel_review(&review);
+    let saved = review_repository::get_hotel_review(user_id, hotel_id).ok_or(Status::NotFound)?;
+    Ok(Json(HotelReviewResponse::from(saved)))
+}
+
+#[delete("/hotel/<hotel_id>/review")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_hotel_review(hotel_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    if !review_repository::delete_hotel_review(user.0.id as u32, hotel_id) {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
+
+/// モデレーションの対象の口コミ。`status`を省略すると承認待ち
+#[get("/admin/reviews?<status>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_review_queue(
+    status: Option<String>,
+    _user: Authorized<ReviewModerate>,
+    request_id: RequestId,
+) -> Result<Json<ReviewQueueResponse>, Status> {
+    let status = match status {
+        Some(status) => ReviewStatus::from_str(&status).map_err(|_| Status::BadRequest)?,
+        None => ReviewStatus::Pending,
+    };
+    Ok(Json(ReviewQueueResponse {
+        onsen_reviews: review_repository::get_onsen_reviews(None, status)
+            .into_iter()
+            .map(OnsenReviewResponse::from)
+            .collect(),
+        hotel_reviews: review_repository::get_hotel_reviews(None, status)
+            .into_iter()
+            .map(HotelReviewResponse::from)
+            .collect(),
+    }))
+}
+
+/// `target`は`onsen`か`hotel`
+#[put(
+    "/admin/reviews/<target>/<review_id>",
+    format = "json",
+    data = "<status_req>"
+)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_review_status(
+    target: &str,
+    review_id: u32,
+    status_req: Json<ReviewStatusRequest>,
+    _user: Authorized<ReviewModerate>,
+    request_id: RequestId,
+) -> Status {
+    let Ok(status) = ReviewStatus::from_str(&status_req.status) else {
+        return Status::BadRequest;
+    };
+    let updated = match target {
+        "onsen" => review_repository::put_onsen_review_status(review_id, status),
+        "hotel" => review_repository::put_hotel_review_status(review_id, status),
+        _ => return Status::NotFound,
+    };
+    if !updated {
+        return Status::NotFound;
+    }
+    Status::NoContent
+}
@@ -0,0 +1,22 @@
+use crate::application::api_model::stats_response::StatsResponse;
+use crate::application::controller::request_guard::ValidatedUser;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::visit_stats::VisitStats;
+use crate::infrastructure::repository::{area_repository, onsen_repository, visit_repository};
+use rocket::serde::json::Json;
+use tracing::instrument;
+
+/// 入浴記録から集計した統計。非公開の入浴記録も含める
+#[get("/me/stats")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_stats(user: ValidatedUser, request_id: RequestId) -> Json<StatsResponse> {
+    let visits = visit_repository::get_visits(user.id as u32);
+    let mut onsen_ids: Vec<u32> = visits.iter().map(|v| v.onsen_id).collect();
+    onsen_ids.sort();
+    onsen_ids.dedup();
+    let onsens = onsen_repository::get_onsens_by_ids(&onsen_ids);
+    let area_ids: Vec<u32> = onsens.iter().filter_map(|v| v.area_id).collect();
+    let areas = area_repository::get_areas_by_ids(&area_ids);
+    let stats = VisitStats::compute(&visits, &onsens, &areas);
+    Json(StatsResponse::create(stats, &areas))
+}
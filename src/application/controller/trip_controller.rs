@@ -0,0 +1,174 @@
+use crate::application::api_model::summary_response::*;
+use crate::application::api_model::trip_request::TripRequest;
+use crate::application::api_model::trip_response::*;
+use crate::application::api_model::visit_response::VisitResponse;
+use crate::application::controller::profile_controller::owner_privacy;
+use crate::application::controller::request_guard::{ValidatedUser, VerifiedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::comment_entity::CommentTarget;
+use crate::domain::trip_entity::TripEntity;
+use crate::infrastructure::repository::{
+    area_repository, comment_repository, hotel_repository, onsen_repository, trip_repository,
+    visit_repository,
+};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use std::collections::HashMap;
+use tracing::instrument;
+
+#[get("/me/trips")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_trips(user: ValidatedUser, request_id: RequestId) -> Json<Vec<TripResponse>> {
+    let trips = trip_repository::get_trips(user.id as u32);
+    Json(trips.into_iter().map(TripResponse::from).collect())
+}
+
+#[get("/me/trips/<trip_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_trip(
+    trip_id: u32,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<TripResponse>, Status> {
+    let trip = trip_repository::get_trip(trip_id)
+        .filter(|v| v.user_id == user.id as u32)
+        .ok_or(Status::NotFound)?;
+    Ok(Json(TripResponse::from(trip)))
+}
+
+#[post("/me/trips", format = "json", data = "<trip_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_trip(
+    trip_req: Json<TripRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<TripResponse>, Status> {
+    let trip_entity = trip_req
+        .create_entity(0, user.0.id as u32)
+        .ok_or(Status::BadRequest)?;
+    if !exists_references(&trip_entity) {
+        return Err(Status::BadRequest);
+    }
+    let created_trip = trip_repository::post_trip(trip_entity);
+    Ok(Json(TripResponse::from(created_trip)))
+}
+
+#[put("/me/trips/<trip_id>", format = "json", data = "<trip_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_trip(
+    trip_id: u32,
+    trip_req: Json<TripRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<(), Status> {
+    let trip_entity = trip_req
+        .create_entity(trip_id, user.0.id as u32)
+        .ok_or(Status::BadRequest)?;
+    if !exists_references(&trip_entity) {
+        return Err(Status::BadRequest);
+    }
+    if !trip_repository::put_trip(trip_entity) {
+        return Err(Status::NotFound);
+    }
+    Ok(())
+}
+
+/// 旅行へのコメントも削除する
+#[delete("/me/trips/<trip_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_trip(trip_id: u32, user: VerifiedUser, request_id: RequestId) -> Status {
+    if !trip_repository::delete_trip(user.0.id as u32, trip_id) {
+        return Status::NotFound;
+    }
+    comment_repository::delete_comments(CommentTarget::Trip, trip_id);
+    Status::NoContent
+}
+
+/// 宿・温泉地・温泉の概要を展開した旅行。持ち主以外には公開していてプロフィールの公開範囲の内に
+/// ある旅行だけを返し、同じく見せられない入浴記録を含めない
+#[get("/trips/<trip_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_trip_detail(
+    trip_id: u32,
+    user: Option<ValidatedUser>,
+    request_id: RequestId,
+) -> Result<Json<TripDetailResponse>, Status> {
+    let trip = trip_repository::get_trip(trip_id).ok_or(Status::NotFound)?;
+    let (privacy, viewer) = owner_privacy(trip.user_id, user.map(|v| v.id as u32));
+    if !privacy.shows_record(privacy.trips, trip.is_public, viewer) {
+        return Err(Status::NotFound);
+    }
+    let visits = visit_repository::get_visits_by_ids(&trip.visit_ids());
+    let mut onsens = HashMap::new();
+    let mut hotels = HashMap::new();
+    let mut areas = HashMap::new();
+    let mut days = vec![];
+    for (index, day) in trip.days.iter().enumerate() {
+        let hotel = day.hotel_id.and_then(|hotel_id| {
+            hotels
+                .entry(hotel_id)
+                .or_insert_with(|| hotel_repository::get_hotel_with_onsen(hotel_id))
+                .clone()
+                .map(HotelSummaryResponse::from)
+        });
+        let day_areas = day
+            .area_ids
+            .iter()
+            .filter_map(|area_id| {
+                areas
+                    .entry(*area_id)
+                    .or_insert_with(|| area_repository::get_area(*area_id))
+                    .clone()
+                    .map(AreaSummaryResponse::from)
+            })
+            .collect();
+        let day_visits = day
+            .visit_ids
+            .iter()
+            .filter_map(|visit_id| visits.iter().find(|v| v.id == *visit_id))
+            .filter(|visit| privacy.shows_record(privacy.visits, visit.is_public, viewer))
+            .map(|visit| TripVisitResponse {
+                onsen: onsens
+                    .entry(visit.onsen_id)
+                    .or_insert_with(|| onsen_repository::get_onsen(visit.onsen_id))
+                    .clone()
+                    .map(OnsenSummaryResponse::from),
+                visit: VisitResponse::from(visit.clone()),
+            })
+            .collect();
+        days.push(TripDayDetailResponse {
+            day_number: index as u32 + 1,
+            date: trip.date_of(index).format("%Y-%m-%d").to_string(),
+            hotel,
+            areas: day_areas,
+            visits: day_visits,
+            note: day.note.clone(),
+        });
+    }
+    Ok(Json(TripDetailResponse {
+        id: trip.id,
+        title: trip.title.clone(),
+        start_on: trip.start_on.format("%Y-%m-%d").to_string(),
+        end_on: trip.end_on.format("%Y-%m-%d").to_string(),
+        note: trip.note.clone(),
+        days,
+    }))
+}
+
+/// 入浴記録が自分のもので、宿と温泉地が存在するか
+fn exists_references(trip: &TripEntity) -> bool {
+    let own_visit_ids: Vec<u32> = visit_repository::get_visits(trip.user_id)
+        .iter()
+        .map(|v| v.id)
+        .collect();
+    trip.days.iter().all(|day| {
+        day.visit_ids.iter().all(|v| own_visit_ids.contains(v))
+            && day.hotel_id.map_or(true, |v| {
+                hotel_repository::get_hotel_with_onsen(v).is_some()
+            })
+            && day
+                .area_ids
+                .iter()
+                .all(|v| area_repository::get_area(*v).is_some())
+    })
+}
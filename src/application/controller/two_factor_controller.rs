@@ -0,0 +1,97 @@
+use crate::application::api_model::user_api_model::*;
+use crate::application::auth::totp::{
+    create_backup_codes, create_totp_secret, hash_backup_code, provisioning_uri, verify_totp,
+};
+use crate::application::controller::request_guard::ValidatedUser;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::application::fairing::two_factor::TwoFactorConfig;
+use crate::infrastructure::rdb::diesel_model::diesel_two_factor::UserTotp;
+use crate::infrastructure::repository::two_factor_repository;
+use chrono::Utc;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use tracing::instrument;
+
+/// TOTPの秘密鍵を発行する。`POST /me/2fa/totp/confirm`で確認コードを送るまでは有効にならない。
+/// 漏れたAPIキーでサインインできなくされないように、2段階認証の設定はアクセストークンでしかできない
+#[post("/me/2fa/totp")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_totp(
+    user: ValidatedUser,
+    two_factor_config: &State<TwoFactorConfig>,
+    request_id: RequestId,
+) -> Result<Json<TotpEnrollmentResponse>, Status> {
+    if user.is_api_key() {
+        return Err(Status::Forbidden);
+    }
+    let current = two_factor_repository::get_user_totp(user.id);
+    if current.is_some_and(|v| v.enabled_at.is_some()) {
+        return Err(Status::Conflict);
+    }
+    let secret = create_totp_secret();
+    two_factor_repository::put_pending_user_totp(user.id, &secret);
+    Ok(Json(TotpEnrollmentResponse {
+        provisioning_uri: provisioning_uri(&secret, &two_factor_config.issuer, &user.email),
+        secret,
+    }))
+}
+
+/// 確認コードが正しければTOTPを有効にして、バックアップコードを返す
+#[post("/me/2fa/totp/confirm", format = "json", data = "<code_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_totp_confirm(
+    code_req: Json<TwoFactorCodeRequest>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<BackupCodesResponse>, Status> {
+    if user.is_api_key() {
+        return Err(Status::Forbidden);
+    }
+    let totp = two_factor_repository::get_user_totp(user.id).ok_or(Status::NotFound)?;
+    if totp.enabled_at.is_some() {
+        return Err(Status::Conflict);
+    }
+    let step = verify_totp(&totp.secret, &code_req.code, Utc::now().timestamp(), None)
+        .ok_or(Status::BadRequest)?;
+    let backup_codes = create_backup_codes();
+    let backup_code_hashes: Vec<String> =
+        backup_codes.iter().map(|v| hash_backup_code(v)).collect();
+    two_factor_repository::enable_user_totp(user.id, step, &backup_code_hashes);
+    Ok(Json(BackupCodesResponse { backup_codes }))
+}
+
+/// 確認コードかバックアップコードでTOTPを無効にする
+#[delete("/me/2fa/totp", format = "json", data = "<code_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_totp(
+    code_req: Json<TwoFactorCodeRequest>,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Status {
+    if user.is_api_key() {
+        return Status::Forbidden;
+    }
+    let Some(totp) = two_factor_repository::get_user_totp(user.id) else {
+        return Status::NotFound;
+    };
+    if totp.enabled_at.is_some() && !verify_second_factor(&totp, &code_req.code) {
+        return Status::BadRequest;
+    }
+    two_factor_repository::delete_user_totp(user.id);
+    Status::NoContent
+}
+
+/// 認証アプリの確認コードかバックアップコードを検証する。どちらも1回しか使えない
+pub fn verify_second_factor(totp: &UserTotp, code: &str) -> bool {
+    let step = verify_totp(
+        &totp.secret,
+        code,
+        Utc::now().timestamp(),
+        totp.last_used_step,
+    );
+    if let Some(step) = step {
+        return two_factor_repository::put_totp_last_used_step(totp.user_id, step);
+    }
+    two_factor_repository::use_totp_backup_code(totp.user_id, &hash_backup_code(code))
+}
@@ -1,45 +1,441 @@
-use crate::application::auth::crypto;
+use crate::application::auth::crypto::{self, HashParams};
+use crate::application::auth::one_time_token::{
+    create_email_verification_token, create_password_reset_token,
+    create_two_factor_challenge_token, hash_one_time_token,
+};
+use crate::application::auth::refresh_token::{
+    create_family_id, create_refresh_token, hash_refresh_token,
+};
+use crate::application::controller::request_guard::{
+    Authorized, ClientIp, UserManage, ValidatedUser,
+};
+use crate::application::controller::two_factor_controller::verify_second_factor;
+use crate::application::fairing::request_tracing::RequestId;
+use crate::application::fairing::two_factor::TwoFactorConfig;
+use crate::application::mail_template::{email_verification_mail, password_reset_mail};
+use crate::application::rate_limit::{SigninLimit, SigninRateLimiter};
+use crate::domain::email::Email;
+use crate::domain::password_policy::PasswordPolicy;
+use crate::domain::role::Role;
+use crate::infrastructure::mailer::{MailConfig, Mailer};
+use crate::infrastructure::metrics::SIGNIN_TOTAL;
+use crate::infrastructure::repository::email_verification_token_repository;
+use crate::infrastructure::repository::password_reset_token_repository;
+use crate::infrastructure::repository::refresh_token_repository::{self, RefreshTokenRotation};
+use crate::infrastructure::repository::signin_audit_repository;
+use crate::infrastructure::repository::two_factor_repository;
 use crate::infrastructure::repository::user_repository;
-use crate::{application::api_model::user_api_model::*, application::auth::jwt::encode_jwt};
+use crate::{application::api_model::user_api_model::*, application::auth::jwt::JwtKeys};
 use rocket::http::Status;
 use rocket::serde::json::Json;
+use rocket::tokio::task;
+use rocket::State;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{instrument, Span};
 
 #[post("/signup", format = "json", data = "<auth_req>")]
-pub fn post_signup(auth_req: Json<AuthRequest>) -> Result<Json<AuthResponse>, Status> {
-    let email = auth_req.email.as_str();
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_signup(
+    auth_req: Json<AuthRequest>,
+    client_ip: ClientIp,
+    rate_limiter: &State<SigninRateLimiter>,
+    mailer: &State<Arc<dyn Mailer>>,
+    mail_config: &State<MailConfig>,
+    password_policy: &State<PasswordPolicy>,
+    hash_params: &State<HashParams>,
+    jwt_keys: &State<JwtKeys>,
+    request_id: RequestId,
+) -> Result<Json<AuthResponse>, RateLimitedError> {
+    check_ip_rate_limit(rate_limiter, client_ip)?;
+    let email = Email::new(&auth_req.email).ok_or(Status::BadRequest)?;
+    let email = email.as_str();
     let password = auth_req.password.as_str();
+    password_policy
+        .validate(password)
+        .map_err(|_| Status::BadRequest)?;
 
     let exists_user = user_repository::exists_user(email);
     if exists_user {
-        return Err(Status::Conflict);
+        return Err(Status::Conflict.into());
     }
 
-    let hashed_password = crypto::create_hash(password);
+    let hashed_password = crypto::create_hash(password, *hash_params.inner());
 
     user_repository::post_user(email, hashed_password.as_str());
+    let user = user_repository::get_user(email).ok_or(Status::InternalServerError)?;
+    spawn_email_verification_mail(user.id, email, mailer, mail_config);
 
-    Ok(Json(AuthResponse {
-        token: encode_jwt(email),
-    }))
+    Ok(Json(issue_tokens(
+        jwt_keys,
+        user.id,
+        email,
+        &create_family_id(),
+        false,
+    )))
+}
+
+#[get("/verify?<token>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_verify(token: &str, request_id: RequestId) -> Result<&'static str, Status> {
+    if !email_verification_token_repository::verify_email(&hash_one_time_token(token)) {
+        return Err(Status::BadRequest);
+    }
+    Ok("メールアドレスを確認しました。")
+}
+
+#[post("/verify/resend")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_verify_resend(
+    user: ValidatedUser,
+    client_ip: ClientIp,
+    rate_limiter: &State<SigninRateLimiter>,
+    mailer: &State<Arc<dyn Mailer>>,
+    mail_config: &State<MailConfig>,
+    request_id: RequestId,
+) -> Result<Status, RateLimitedError> {
+    if user.verified {
+        return Err(Status::Conflict.into());
+    }
+    check_ip_rate_limit(rate_limiter, client_ip)?;
+    spawn_email_verification_mail(user.id, &user.email, mailer, mail_config);
+    Ok(Status::Accepted)
 }
 
 #[post("/signin", format = "json", data = "<auth_req>")]
-pub fn post_signin(auth_req: Json<AuthRequest>) -> Result<Json<AuthResponse>, Status> {
-    let email = auth_req.email.as_str();
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_signin(
+    auth_req: Json<AuthRequest>,
+    client_ip: ClientIp,
+    rate_limiter: &State<SigninRateLimiter>,
+    hash_params: &State<HashParams>,
+    two_factor_config: &State<TwoFactorConfig>,
+    jwt_keys: &State<JwtKeys>,
+    request_id: RequestId,
+) -> Result<SigninResponse, SigninError> {
+    let email = Email::normalize(&auth_req.email);
+    let email = email.as_str();
     let password = auth_req.password.as_str();
+    let client_ip = client_ip.0;
+    let ip_address = client_ip.map(|v| v.to_string());
+
+    if let Err(limit) = rate_limiter.check(client_ip, email, Instant::now()) {
+        let (reason, retry_after) = match limit {
+            SigninLimit::RateLimited { retry_after } => ("rate_limited", retry_after),
+            SigninLimit::Locked { retry_after } => ("locked", retry_after),
+        };
+        SIGNIN_TOTAL.with_label_values(&[reason]).inc();
+        signin_audit_repository::post_signin_failure(email, ip_address, reason);
+        return Err(SigninError::too_many_requests(retry_after));
+    }
 
     let user = user_repository::get_user(email);
-    if user.is_none() {
-        return Err(Status::Unauthorized);
+    let matches = user
+        .as_ref()
+        .is_some_and(|user| crypto::verify_hash(password, user.hashed_password.as_str()));
+    let Some(user) = user.filter(|_| matches) else {
+        SIGNIN_TOTAL.with_label_values(&["failure"]).inc();
+        signin_audit_repository::post_signin_failure(email, ip_address, "invalid_credentials");
+        if let Some(lockout) = rate_limiter.record_failure(email, Instant::now()) {
+            tracing::warn!(
+                lockout_seconds = lockout.as_secs(),
+                "The account is locked because of repeated signin failures."
+            );
+            return Err(SigninError::too_many_requests(lockout));
+        }
+        return Err(SigninError::Unauthorized(()));
+    };
+    if crypto::needs_rehash(&user.hashed_password, *hash_params.inner()) {
+        let hashed_password = crypto::create_hash(password, *hash_params.inner());
+        user_repository::put_user_password(user.id, &hashed_password);
     }
-    let user = user.unwrap();
 
-    let matches = crypto::verify_hash(password, user.hashed_password.as_str());
-    if !matches {
-        return Err(Status::Unauthorized);
+    let totp = two_factor_repository::get_user_totp(user.id);
+    if totp.is_some_and(|v| v.enabled_at.is_some()) {
+        SIGNIN_TOTAL
+            .with_label_values(&["two_factor_required"])
+            .inc();
+        let challenge = create_two_factor_challenge_token();
+        two_factor_repository::post_two_factor_challenge(
+            user.id,
+            &challenge.token_hash,
+            challenge.expires_at,
+        );
+        return Ok(SigninResponse::TwoFactorRequired(Json(
+            TwoFactorChallengeResponse {
+                two_factor_token: challenge.token,
+            },
+        )));
     }
+    // 2段階認証があれば確認コードが通るまで失敗の記録を残す
+    rate_limiter.record_success(email);
+    SIGNIN_TOTAL.with_label_values(&["success"]).inc();
+
+    let mut response = issue_tokens(jwt_keys, user.id, email, &create_family_id(), false);
+    // 2段階認証を有効にできるようにトークンは発行するが、パーミッションは使えないことを知らせる
+    let role = Role::from_str(&user.role).unwrap_or(Role::Viewer);
+    response.two_factor_enrollment_required = two_factor_config.is_required(role);
+    Ok(SigninResponse::Authenticated(Json(response)))
+}
+
+#[post("/signin/2fa", format = "json", data = "<two_factor_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_signin_two_factor(
+    two_factor_req: Json<TwoFactorSigninRequest>,
+    client_ip: ClientIp,
+    rate_limiter: &State<SigninRateLimiter>,
+    jwt_keys: &State<JwtKeys>,
+    request_id: RequestId,
+) -> Result<Json<AuthResponse>, SigninError> {
+    let client_ip = client_ip.0;
+    let ip_address = client_ip.map(|v| v.to_string());
+    rate_limiter
+        .check_ip(client_ip, Instant::now())
+        .map_err(SigninError::too_many_requests)?;
+    let unauthorized = || SigninError::Unauthorized(());
+    let challenge = two_factor_repository::get_two_factor_challenge(&hash_one_time_token(
+        &two_factor_req.two_factor_token,
+    ))
+    .ok_or_else(unauthorized)?;
+    let user = user_repository::get_user_by_id(challenge.user_id).ok_or_else(unauthorized)?;
+    // IPアドレスは確認済みなので、アカウントのロックと試行回数だけを確認する
+    if let Err(limit) = rate_limiter.check(None, &user.email, Instant::now()) {
+        let (reason, retry_after) = match limit {
+            SigninLimit::RateLimited { retry_after } => ("rate_limited", retry_after),
+            SigninLimit::Locked { retry_after } => ("locked", retry_after),
+        };
+        SIGNIN_TOTAL.with_label_values(&[reason]).inc();
+        signin_audit_repository::post_signin_failure(&user.email, ip_address, reason);
+        return Err(SigninError::too_many_requests(retry_after));
+    }
+    let totp = two_factor_repository::get_user_totp(challenge.user_id)
+        .filter(|v| v.enabled_at.is_some())
+        .ok_or_else(unauthorized)?;
+    if !verify_second_factor(&totp, &two_factor_req.code) {
+        two_factor_repository::put_two_factor_challenge_failure(challenge.id);
+        SIGNIN_TOTAL.with_label_values(&["failure"]).inc();
+        signin_audit_repository::post_signin_failure(
+            &user.email,
+            ip_address,
+            "invalid_two_factor_code",
+        );
+        if let Some(lockout) = rate_limiter.record_failure(&user.email, Instant::now()) {
+            tracing::warn!(
+                lockout_seconds = lockout.as_secs(),
+                "The account is locked because of repeated signin failures."
+            );
+            return Err(SigninError::too_many_requests(lockout));
+        }
+        return Err(unauthorized());
+    }
+    if !two_factor_repository::use_two_factor_challenge(challenge.id) {
+        return Err(unauthorized());
+    }
+    rate_limiter.record_success(&user.email);
+    SIGNIN_TOTAL.with_label_values(&["success"]).inc();
+
+    Ok(Json(issue_tokens(
+        jwt_keys,
+        user.id,
+        &user.email,
+        &create_family_id(),
+        true,
+    )))
+}
+
+#[post("/token/refresh", format = "json", data = "<refresh_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_token_refresh(
+    refresh_req: Json<RefreshTokenRequest>,
+    jwt_keys: &State<JwtKeys>,
+    request_id: RequestId,
+) -> Result<Json<AuthResponse>, Status> {
+    let new_refresh_token = create_refresh_token();
+    let rotation = refresh_token_repository::rotate_refresh_token(
+        &hash_refresh_token(&refresh_req.refresh_token),
+        &new_refresh_token.token_hash,
+        new_refresh_token.expires_at,
+    );
+    let (user_id, mfa) = match rotation {
+        RefreshTokenRotation::Rotated { user_id, mfa } => (user_id, mfa),
+        RefreshTokenRotation::Reused => {
+            tracing::warn!("Rotated refresh token was reused. The token family is revoked.");
+            return Err(Status::Unauthorized);
+        }
+        RefreshTokenRotation::Invalid => return Err(Status::Unauthorized),
+    };
+    let user = user_repository::get_user_by_id(user_id).ok_or(Status::Unauthorized)?;
 
     Ok(Json(AuthResponse {
-        token: encode_jwt(email),
+        token: jwt_keys.encode(&user.email, mfa),
+        refresh_token: new_refresh_token.token,
+        two_factor_enrollment_required: false,
     }))
 }
+
+#[post("/signout", format = "json", data = "<refresh_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_signout(refresh_req: Json<RefreshTokenRequest>, request_id: RequestId) -> Status {
+    refresh_token_repository::revoke_refresh_token_family(&hash_refresh_token(
+        &refresh_req.refresh_token,
+    ));
+    Status::NoContent
+}
+
+/// ユーザーが存在するかどうかがわからないように、メールはリクエストとは別に送って常に202を返す
+#[post("/password/forgot", format = "json", data = "<forgot_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_password_forgot(
+    forgot_req: Json<PasswordForgotRequest>,
+    client_ip: ClientIp,
+    rate_limiter: &State<SigninRateLimiter>,
+    mailer: &State<Arc<dyn Mailer>>,
+    mail_config: &State<MailConfig>,
+    request_id: RequestId,
+) -> Result<Status, RateLimitedError> {
+    check_ip_rate_limit(rate_limiter, client_ip)?;
+    let email = Email::normalize(&forgot_req.email);
+    let mailer = Arc::clone(mailer);
+    let mail_config = mail_config.inner().clone();
+    let span = Span::current();
+    task::spawn_blocking(move || {
+        span.in_scope(|| send_password_reset_mail(&email, mailer.as_ref(), &mail_config))
+    });
+    Ok(Status::Accepted)
+}
+
+#[post("/password/reset", format = "json", data = "<reset_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_password_reset(
+    reset_req: Json<PasswordResetRequest>,
+    password_policy: &State<PasswordPolicy>,
+    hash_params: &State<HashParams>,
+    request_id: RequestId,
+) -> Status {
+    if password_policy.validate(&reset_req.password).is_err() {
+        return Status::BadRequest;
+    }
+    let hashed_password = crypto::create_hash(&reset_req.password, *hash_params.inner());
+    let reset = password_reset_token_repository::reset_password(
+        &hash_one_time_token(&reset_req.token),
+        &hashed_password,
+    );
+    if !reset {
+        return Status::BadRequest;
+    }
+    Status::NoContent
+}
+
+#[put("/user/<user_id>/role", format = "json", data = "<role_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_user_role(
+    user_id: u32,
+    role_req: Json<UserRoleRequest>,
+    admin: Authorized<UserManage>,
+    request_id: RequestId,
+) -> Result<Status, Status> {
+    let role = Role::from_str(&role_req.role).map_err(|_| Status::BadRequest)?;
+    // 管理者が不在にならないよう、自分自身のロールは変更できない
+    if admin.user.id == user_id as i32 {
+        return Err(Status::Conflict);
+    }
+    if !user_repository::put_user_role(user_id as i32, role) {
+        return Err(Status::NotFound);
+    }
+    tracing::info!(user_id, %role, "User role is changed.");
+    Ok(Status::NoContent)
+}
+
+/// IPアドレスごとの試行回数の制限。サインインと同じバケットを使う
+fn check_ip_rate_limit(
+    rate_limiter: &SigninRateLimiter,
+    client_ip: ClientIp,
+) -> Result<(), RateLimitedError> {
+    rate_limiter
+        .check_ip(client_ip.0, Instant::now())
+        .map_err(RateLimitedError::too_many_requests)
+}
+
+fn send_password_reset_mail(email: &str, mailer: &dyn Mailer, mail_config: &MailConfig) {
+    let Some(user) = user_repository::get_user(email) else {
+        return;
+    };
+    let reset_token = create_password_reset_token();
+    password_reset_token_repository::post_password_reset_token(
+        user.id,
+        &reset_token.token_hash,
+        reset_token.expires_at,
+    );
+    let mail = password_reset_mail(
+        &user.email,
+        &mail_config.password_reset_url,
+        &reset_token.token,
+    );
+    if let Err(e) = mailer.send(&mail) {
+        tracing::error!(error = %e, "Failed to send a password reset mail.");
+    }
+}
+
+/// メールはレスポンスを返したあとに送る
+fn spawn_email_verification_mail(
+    user_id: i32,
+    email: &str,
+    mailer: &Arc<dyn Mailer>,
+    mail_config: &MailConfig,
+) {
+    let email = email.to_string();
+    let mailer = Arc::clone(mailer);
+    let mail_config = mail_config.clone();
+    let span = Span::current();
+    task::spawn_blocking(move || {
+        span.in_scope(|| {
+            send_email_verification_mail(user_id, &email, mailer.as_ref(), &mail_config)
+        })
+    });
+}
+
+fn send_email_verification_mail(
+    user_id: i32,
+    email: &str,
+    mailer: &dyn Mailer,
+    mail_config: &MailConfig,
+) {
+    let verification_token = create_email_verification_token();
+    email_verification_token_repository::post_email_verification_token(
+        user_id,
+        &verification_token.token_hash,
+        verification_token.expires_at,
+    );
+    let mail = email_verification_mail(
+        email,
+        &mail_config.email_verification_url,
+        &verification_token.token,
+    );
+    if let Err(e) = mailer.send(&mail) {
+        tracing::error!(error = %e, "Failed to send an email verification mail.");
+    }
+}
+
+fn issue_tokens(
+    jwt_keys: &JwtKeys,
+    user_id: i32,
+    email: &str,
+    family_id: &str,
+    mfa: bool,
+) -> AuthResponse {
+    let refresh_token = create_refresh_token();
+    refresh_token_repository::post_refresh_token(
+        user_id,
+        family_id,
+        &refresh_token.token_hash,
+        refresh_token.expires_at,
+        mfa,
+    );
+    AuthResponse {
+        token: jwt_keys.encode(email, mfa),
+        refresh_token: refresh_token.token,
+        two_factor_enrollment_required: false,
+    }
+}
@@ -0,0 +1,94 @@
+use crate::application::achievement::award_achievements;
+use crate::application::api_model::visit_request::VisitRequest;
+use crate::application::api_model::visit_response::VisitResponse;
+use crate::application::controller::photo_controller::delete_blobs;
+use crate::application::controller::request_guard::{ValidatedUser, VerifiedUser};
+use crate::application::fairing::request_tracing::RequestId;
+use crate::domain::comment_entity::CommentTarget;
+use crate::domain::photo_entity::PhotoTarget;
+use crate::infrastructure::blob_store::BlobStore;
+use crate::infrastructure::repository::{
+    comment_repository, onsen_repository, photo_repository, visit_repository,
+};
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::State;
+use std::sync::Arc;
+use tracing::instrument;
+
+#[get("/me/visits")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_visits(user: ValidatedUser, request_id: RequestId) -> Json<Vec<VisitResponse>> {
+    let visits = visit_repository::get_visits(user.id as u32);
+    Json(visits.into_iter().map(VisitResponse::from).collect())
+}
+
+#[get("/me/visits/<visit_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn get_visit(
+    visit_id: u32,
+    user: ValidatedUser,
+    request_id: RequestId,
+) -> Result<Json<VisitResponse>, Status> {
+    let visit = visit_repository::get_visit(user.id as u32, visit_id).ok_or(Status::NotFound)?;
+    Ok(Json(VisitResponse::from(visit)))
+}
+
+#[post("/me/visits", format = "json", data = "<visit_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn post_visit(
+    visit_req: Json<VisitRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<Json<VisitResponse>, Status> {
+    let visit_entity = visit_req
+        .create_entity(0, user.0.id as u32)
+        .ok_or(Status::BadRequest)?;
+    if onsen_repository::get_onsen(visit_entity.onsen_id).is_none() {
+        return Err(Status::BadRequest);
+    }
+    let created_visit = visit_repository::post_visit(visit_entity);
+    award_achievements(created_visit.user_id);
+    Ok(Json(VisitResponse::from(created_visit)))
+}
+
+#[put("/me/visits/<visit_id>", format = "json", data = "<visit_req>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn put_visit(
+    visit_id: u32,
+    visit_req: Json<VisitRequest>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Result<(), Status> {
+    let visit_entity = visit_req
+        .create_entity(visit_id, user.0.id as u32)
+        .ok_or(Status::BadRequest)?;
+    if onsen_repository::get_onsen(visit_entity.onsen_id).is_none() {
+        return Err(Status::BadRequest);
+    }
+    let user_id = visit_entity.user_id;
+    if !visit_repository::put_visit(visit_entity) {
+        return Err(Status::NotFound);
+    }
+    award_achievements(user_id);
+    Ok(())
+}
+
+/// 記録の写真とコメントも削除する
+#[delete("/me/visits/<visit_id>")]
+#[instrument(skip_all, fields(request_id = %request_id))]
+pub fn delete_visit(
+    visit_id: u32,
+    blob_store: &State<Arc<dyn BlobStore>>,
+    user: VerifiedUser,
+    request_id: RequestId,
+) -> Status {
+    if !visit_repository::delete_visit(user.0.id as u32, visit_id) {
+        return Status::NotFound;
+    }
+    for photo in photo_repository::delete_photos(PhotoTarget::Visit, visit_id) {
+        delete_blobs(blob_store, photo);
+    }
+    comment_repository::delete_comments(CommentTarget::Visit, visit_id);
+    Status::NoContent
+}
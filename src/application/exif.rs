@@ -0,0 +1,428 @@
+use crate::domain::photo_entity::ImageFormat;
+use std::ops::Range;
+
+/// EXIFのGPSInfoタグ
+const GPS_INFO_TAG: u16 = 0x8825;
+/// JPEGのAPP1に入るXMPと、64KBに収まらないXMPの続き
+const JPEG_XMP_HEADERS: [&[u8]; 2] = [
+    b"http://ns.adobe.com/xap/1.0/\0",
+    b"http://ns.adobe.com/xmp/extension/\0",
+];
+/// PNGのテキストチャンクに入るXMPのキーワード
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+/// WebPのVP8XチャンクのXMPがあることを表すフラグ
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+/// 画像から位置情報を消す。EXIFはGPS IFDを空にして値を0で埋め、ほかのタグ(向きなど)は残す。
+/// XMPにも位置情報が入ることがあるので、XMPはまるごと消す。壊れた画像ならNone
+pub fn strip_gps(format: ImageFormat, bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = bytes.to_vec();
+    let xmp = match format {
+        ImageFormat::Jpeg => strip_jpeg_gps(&mut bytes)?,
+        ImageFormat::Png => strip_png_gps(&mut bytes)?,
+        ImageFormat::Webp => strip_webp_gps(&mut bytes)?,
+    };
+    Some(remove_ranges(bytes, &xmp))
+}
+
+/// APP1の`Exif\0\0`セグメントを書き換え、XMPのセグメントの範囲を返す。SOS以降は画像データ
+fn strip_jpeg_gps(bytes: &mut [u8]) -> Option<Vec<Range<usize>>> {
+    let mut xmp = vec![];
+    let mut offset = 2;
+    loop {
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(offset + 1)?;
+        match marker {
+            // パディング
+            0xFF => {
+                offset += 1;
+                continue;
+            }
+            // 長さを持たないマーカー
+            0x01 | 0xD0..=0xD7 => {
+                offset += 2;
+                continue;
+            }
+            // SOS・EOI
+            0xDA | 0xD9 => return Some(xmp),
+            _ => {}
+        }
+        let length = read_u16(bytes, offset + 2, false)? as usize;
+        let end = offset + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+        let segment = &mut bytes[offset + 4..end];
+        if marker == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            strip_tiff_gps(&mut segment[6..])?;
+        }
+        if marker == 0xE1 && JPEG_XMP_HEADERS.iter().any(|v| segment.starts_with(v)) {
+            xmp.push(offset..end);
+        }
+        offset = end;
+    }
+}
+
+/// `eXIf`チャンクを書き換えたらCRCを計算し直す。XMPを入れたテキストチャンクの範囲を返す
+fn strip_png_gps(bytes: &mut [u8]) -> Option<Vec<Range<usize>>> {
+    let mut xmp = vec![];
+    let mut offset = 8;
+    while offset + 12 <= bytes.len() {
+        let length = read_u32(bytes, offset, false)? as usize;
+        let end = offset + 12 + length;
+        if end > bytes.len() {
+            return None;
+        }
+        if &bytes[offset + 4..offset + 8] == b"eXIf" {
+            strip_tiff_gps(&mut bytes[offset + 8..offset + 8 + length])?;
+            let crc = crc32(&bytes[offset + 4..offset + 8 + length]);
+            bytes[offset + 8 + length..end].copy_from_slice(&crc.to_be_bytes());
+        }
+        let is_text = matches!(&bytes[offset + 4..offset + 8], b"iTXt" | b"tEXt" | b"zTXt");
+        if is_text && bytes[offset + 8..offset + 8 + length].starts_with(PNG_XMP_KEYWORD) {
+            xmp.push(offset..end);
+        }
+        if &bytes[offset + 4..offset + 8] == b"IEND" {
+            return Some(xmp);
+        }
+        offset = end;
+    }
+    Some(xmp)
+}
+
+/// RIFFの`EXIF`チャンク。`Exif\0\0`で始まることもある。
+/// `XMP `チャンクの範囲を返し、RIFFのサイズとVP8XのXMPのフラグをそれに合わせる
+fn strip_webp_gps(bytes: &mut [u8]) -> Option<Vec<Range<usize>>> {
+    let mut xmp = vec![];
+    let mut vp8x = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let length = read_u32(bytes, offset + 4, true)? as usize;
+        let end = offset + 8 + length;
+        if end > bytes.len() {
+            return None;
+        }
+        if &bytes[offset..offset + 4] == b"EXIF" {
+            let chunk = &mut bytes[offset + 8..end];
+            let tiff = if chunk.starts_with(b"Exif\0\0") {
+                &mut chunk[6..]
+            } else {
+                chunk
+            };
+            strip_tiff_gps(tiff)?;
+        }
+        if &bytes[offset..offset + 4] == b"VP8X" && length > 0 {
+            vp8x = Some(offset + 8);
+        }
+        // チャンクは2バイト境界にそろえる
+        let padded_end = (end + length % 2).min(bytes.len());
+        if &bytes[offset..offset + 4] == b"XMP " {
+            xmp.push(offset..padded_end);
+        }
+        offset = padded_end;
+    }
+    if xmp.is_empty() {
+        return Some(xmp);
+    }
+    if let Some(flags) = vp8x {
+        bytes[flags] &= !WEBP_XMP_FLAG;
+    }
+    let removed: usize = xmp.iter().map(|v| v.len()).sum();
+    let riff_size = (bytes.len() - removed).checked_sub(8)? as u32;
+    bytes[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(xmp)
+}
+
+/// 昇順に並んだ重ならない範囲を取り除く
+fn remove_ranges(bytes: Vec<u8>, ranges: &[Range<usize>]) -> Vec<u8> {
+    if ranges.is_empty() {
+        return bytes;
+    }
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut start = 0;
+    for range in ranges {
+        result.extend_from_slice(&bytes[start..range.start]);
+        start = range.end;
+    }
+    result.extend_from_slice(&bytes[start..]);
+    result
+}
+
+/// IFD0のGPSInfoが指すIFDのエントリーと値を0で埋め、エントリー数を0にする
+fn strip_tiff_gps(tiff: &mut [u8]) -> Option<()> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let ifd0 = read_u32(tiff, 4, little_endian)? as usize;
+    let entry_count = read_u16(tiff, ifd0, little_endian)? as usize;
+    let gps_ifd = (0..entry_count).find_map(|index| {
+        let entry = ifd0 + 2 + index * 12;
+        (read_u16(tiff, entry, little_endian)? == GPS_INFO_TAG)
+            .then(|| read_u32(tiff, entry + 8, little_endian))
+            .flatten()
+    });
+    let Some(gps_ifd) = gps_ifd.map(|v| v as usize) else {
+        return Some(());
+    };
+    let gps_entry_count = read_u16(tiff, gps_ifd, little_endian)? as usize;
+    let entries_end = gps_ifd + 2 + gps_entry_count * 12;
+    if entries_end > tiff.len() {
+        return None;
+    }
+    for index in 0..gps_entry_count {
+        let entry = gps_ifd + 2 + index * 12;
+        let value_type = read_u16(tiff, entry + 2, little_endian)?;
+        let count = read_u32(tiff, entry + 4, little_endian)? as usize;
+        let size = type_size(value_type).saturating_mul(count);
+        // 4バイトを超える値はオフセットの先にある
+        if size > 4 {
+            let value_offset = read_u32(tiff, entry + 8, little_endian)? as usize;
+            if let Some(value) = tiff.get_mut(value_offset..value_offset.saturating_add(size)) {
+                value.fill(0);
+            }
+        }
+    }
+    tiff[gps_ifd + 2..entries_end].fill(0);
+    tiff[gps_ifd..gps_ifd + 2].fill(0);
+    Some(())
+}
+
+fn type_size(value_type: u16) -> usize {
+    match value_type {
+        1 | 2 | 6 | 7 => 1,
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 0,
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u16> {
+    let value: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if little_endian {
+        u16::from_le_bytes(value)
+    } else {
+        u16::from_be_bytes(value)
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u32> {
+    let value: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if little_endian {
+        u32::from_le_bytes(value)
+    } else {
+        u32::from_be_bytes(value)
+    })
+}
+
+/// PNGのチャンクのCRC-32
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::exif::*;
+
+    /// IFD0にOrientationとGPSInfo、GPS IFDに緯度(RATIONAL×3)を持つリトルエンディアンのTIFF
+    fn tiff_with_gps() -> Vec<u8> {
+        let mut tiff = b"II\x2a\0\x08\0\0\0".to_vec();
+        // IFD0: 2エントリー
+        tiff.extend([2, 0]);
+        tiff.extend([0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0]);
+        tiff.extend([0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+        tiff.extend([0, 0, 0, 0]);
+        // GPS IFD(38): 1エントリー、値は56から
+        tiff.extend([1, 0]);
+        tiff.extend([0x02, 0x00, 5, 0, 3, 0, 0, 0, 56, 0, 0, 0]);
+        tiff.extend([0, 0, 0, 0]);
+        tiff.extend([
+            36, 0, 0, 0, 1, 0, 0, 0, 39, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0,
+        ]);
+        tiff
+    }
+
+    fn assert_gps_stripped(tiff: &[u8]) {
+        // Orientationは残る
+        assert_eq!(&tiff[10..22], &[0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0]);
+        assert_eq!(&tiff[38..40], &[0, 0]);
+        assert!(tiff[40..].iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_strip_jpeg_gps() {
+        let tiff = tiff_with_gps();
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(&tiff);
+        jpeg.extend([0xFF, 0xDA, 0, 2, 0x12, 0x34, 0xFF, 0xD9]);
+        let stripped = strip_gps(ImageFormat::Jpeg, &jpeg).unwrap();
+        assert_eq!(stripped.len(), jpeg.len());
+        assert_gps_stripped(&stripped[12..12 + tiff.len()]);
+        assert_eq!(&stripped[12 + tiff.len()..], &jpeg[12 + tiff.len()..]);
+    }
+
+    #[test]
+    fn test_strip_png_gps() {
+        let tiff = tiff_with_gps();
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend((tiff.len() as u32).to_be_bytes());
+        png.extend(b"eXIf");
+        png.extend(&tiff);
+        png.extend(crc32(&png[12..]).to_be_bytes());
+        png.extend([0, 0, 0, 0]);
+        png.extend(b"IEND");
+        png.extend(crc32(b"IEND").to_be_bytes());
+        let stripped = strip_gps(ImageFormat::Png, &png).unwrap();
+        assert_gps_stripped(&stripped[16..16 + tiff.len()]);
+        let crc = crc32(&stripped[12..16 + tiff.len()]);
+        assert_eq!(
+            &stripped[16 + tiff.len()..20 + tiff.len()],
+            &crc.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn test_strip_webp_gps() {
+        let tiff = tiff_with_gps();
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend(b"EXIF");
+        webp.extend((tiff.len() as u32).to_le_bytes());
+        webp.extend(&tiff);
+        let stripped = strip_gps(ImageFormat::Webp, &webp).unwrap();
+        assert_gps_stripped(&stripped[20..]);
+    }
+
+    /// 位置情報を入れたXMPのパケット
+    const XMP_WITH_GPS: &[u8] = br#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?><x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:exif="http://ns.adobe.com/exif/1.0/" exif:GPSLatitude="36,37.2N" exif:GPSLongitude="138,35.7E"/></rdf:RDF></x:xmpmeta><?xpacket end="w"?>"#;
+
+    fn contains(bytes: &[u8], needle: &[u8]) -> bool {
+        bytes.windows(needle.len()).any(|v| v == needle)
+    }
+
+    fn jpeg_segment(marker: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xFF, marker];
+        segment.extend(((data.len() + 2) as u16).to_be_bytes());
+        segment.extend(data);
+        segment
+    }
+
+    #[test]
+    fn test_strip_jpeg_xmp() {
+        let mut exif = b"Exif\0\0".to_vec();
+        exif.extend(tiff_with_gps());
+        let mut xmp = b"http://ns.adobe.com/xap/1.0/\0".to_vec();
+        xmp.extend(XMP_WITH_GPS);
+        let mut extended_xmp = b"http://ns.adobe.com/xmp/extension/\0".to_vec();
+        extended_xmp.extend(XMP_WITH_GPS);
+        let mut jpeg = vec![0xFF, 0xD8];
+        jpeg.extend(jpeg_segment(0xE1, &exif));
+        jpeg.extend(jpeg_segment(0xE1, &xmp));
+        jpeg.extend(jpeg_segment(0xE0, b"JFIF\0"));
+        jpeg.extend(jpeg_segment(0xE1, &extended_xmp));
+        jpeg.extend([0xFF, 0xDA, 0, 2, 0x12, 0x34, 0xFF, 0xD9]);
+        let stripped = strip_gps(ImageFormat::Jpeg, &jpeg).unwrap();
+        assert!(!contains(&stripped, b"GPSLatitude"));
+        assert!(!contains(&stripped, b"http://ns.adobe.com/"));
+        let mut expected = vec![0xFF, 0xD8];
+        expected.extend(jpeg_segment(0xE1, &exif));
+        expected.extend(jpeg_segment(0xE0, b"JFIF\0"));
+        expected.extend([0xFF, 0xDA, 0, 2, 0x12, 0x34, 0xFF, 0xD9]);
+        assert_eq!(stripped.len(), expected.len());
+        assert_gps_stripped(&stripped[12..12 + tiff_with_gps().len()]);
+        assert_eq!(
+            &stripped[12 + tiff_with_gps().len()..],
+            &expected[12 + tiff_with_gps().len()..]
+        );
+    }
+
+    fn png_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend(kind);
+        chunk.extend(data);
+        chunk.extend(crc32(&chunk[4..]).to_be_bytes());
+        chunk
+    }
+
+    #[test]
+    fn test_strip_png_xmp() {
+        // キーワード、圧縮フラグ、圧縮方式、言語タグ、翻訳したキーワード、テキスト
+        let mut itxt = b"XML:com.adobe.xmp\0\0\0\0\0".to_vec();
+        itxt.extend(XMP_WITH_GPS);
+        let mut comment = b"Comment\0\0\0\0\0".to_vec();
+        comment.extend(b"onsen");
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(png_chunk(b"iTXt", &itxt));
+        png.extend(png_chunk(b"iTXt", &comment));
+        png.extend(png_chunk(b"IEND", b""));
+        let stripped = strip_gps(ImageFormat::Png, &png).unwrap();
+        assert!(!contains(&stripped, b"GPSLatitude"));
+        let mut expected = b"\x89PNG\r\n\x1a\n".to_vec();
+        expected.extend(png_chunk(b"iTXt", &comment));
+        expected.extend(png_chunk(b"IEND", b""));
+        assert_eq!(stripped, expected);
+    }
+
+    fn webp_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = kind.to_vec();
+        chunk.extend((data.len() as u32).to_le_bytes());
+        chunk.extend(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = chunks.concat();
+        let mut webp = b"RIFF".to_vec();
+        webp.extend(((body.len() + 4) as u32).to_le_bytes());
+        webp.extend(b"WEBP");
+        webp.extend(body);
+        webp
+    }
+
+    #[test]
+    fn test_strip_webp_xmp() {
+        // EXIFとXMPのフラグを立てたVP8X
+        let vp8x = webp_chunk(b"VP8X", &[0x0C, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let image = webp_chunk(b"VP8 ", &[1, 2, 3, 4]);
+        let mut xmp = XMP_WITH_GPS.to_vec();
+        if xmp.len() % 2 == 0 {
+            xmp.push(b' ');
+        }
+        let original = webp(&[vp8x.clone(), image.clone(), webp_chunk(b"XMP ", &xmp)]);
+        let stripped = strip_gps(ImageFormat::Webp, &original).unwrap();
+        assert!(!contains(&stripped, b"GPSLatitude"));
+        let expected_vp8x = webp_chunk(b"VP8X", &[0x08, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(stripped, webp(&[expected_vp8x, image]));
+    }
+
+    #[test]
+    fn test_strip_gps_without_exif() {
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xDA, 0, 2, 0x12, 0xFF, 0xD9];
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &jpeg), Some(jpeg));
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &[0xFF, 0xD8, 0x00]), None);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+}
@@ -0,0 +1,124 @@
+use rocket::fairing::{self, AdHoc, Fairing, Info, Kind};
+use rocket::{Build, Request, Response, Rocket};
+use rocket_cors::{AllowedHeaders, AllowedOrigins, Cors, CorsOptions, Method};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Rocket.tomlの`cors`テーブル。プロファイルごとに許可するオリジンを変えられる
+#[derive(Debug, Deserialize)]
+pub struct CorsConfig {
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    #[serde(default)]
+    pub allowed_methods: Vec<String>,
+    #[serde(default)]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    #[serde(default)]
+    pub allow_credentials: bool,
+    pub max_age: Option<usize>,
+}
+
+impl CorsConfig {
+    pub fn to_cors(&self) -> Result<Cors, String> {
+        let allowed_methods = self
+            .allowed_methods
+            .iter()
+            .map(|v| Method::from_str(v).map_err(|_| format!("Invalid method: {}", v)))
+            .collect::<Result<_, _>>()?;
+        let allowed_headers: Vec<&str> = self.allowed_headers.iter().map(|v| v.as_str()).collect();
+        CorsOptions {
+            allowed_origins: AllowedOrigins::some_exact(&self.allowed_origins),
+            allowed_methods,
+            allowed_headers: AllowedHeaders::some(&allowed_headers),
+            expose_headers: self.expose_headers.iter().cloned().collect(),
+            allow_credentials: self.allow_credentials,
+            max_age: self.max_age,
+            ..Default::default()
+        }
+        .to_cors()
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// rocket_corsは全オリジンを許可したときしかVaryを付けないが、
+/// 許可リストでもOriginによってレスポンスが変わるので常に付ける
+struct VaryOrigin;
+
+#[rocket::async_trait]
+impl Fairing for VaryOrigin {
+    fn info(&self) -> Info {
+        Info {
+            name: "Add Vary: Origin",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
+        let has_vary_origin = response.headers().get("Vary").any(|v| {
+            v.split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("Origin"))
+        });
+        if !has_vary_origin {
+            response.adjoin_raw_header("Vary", "Origin");
+        }
+    }
+}
+
+/// Rocket.tomlの設定からCORSのフェアリングを作ってアタッチする
+pub fn cors_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("CORS", |rocket: Rocket<Build>| async move {
+        let config = match rocket.figment().extract_inner::<CorsConfig>("cors") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid CORS configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        if config.allowed_origins.is_empty() {
+            warn!("No CORS origins are allowed. Set cors.allowed_origins in Rocket.toml or ROCKET_CORS.");
+        }
+        match config.to_cors() {
+            Ok(cors) => fairing::Result::Ok(rocket.attach(cors).attach(VaryOrigin)),
+            Err(e) => {
+                error!("Invalid CORS configuration: {}", e);
+                fairing::Result::Err(rocket)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::fairing::cors::CorsConfig;
+
+    fn config(allowed_origins: Vec<&str>, allowed_methods: Vec<&str>) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: allowed_origins.iter().map(|v| v.to_string()).collect(),
+            allowed_methods: allowed_methods.iter().map(|v| v.to_string()).collect(),
+            allowed_headers: vec!["Authorization".to_string()],
+            expose_headers: vec!["X-Request-Id".to_string()],
+            allow_credentials: true,
+            max_age: Some(3600),
+        }
+    }
+
+    #[test]
+    fn test_cors_config_to_cors() {
+        let config = config(vec!["http://localhost:3000"], vec!["GET", "POST"]);
+        assert!(config.to_cors().is_ok());
+    }
+
+    #[test]
+    fn test_cors_config_invalid_origin() {
+        let config = config(vec!["not a url"], vec!["GET"]);
+        assert!(config.to_cors().is_err());
+    }
+
+    #[test]
+    fn test_cors_config_invalid_method() {
+        let config = config(vec!["http://localhost:3000"], vec!["FETCH"]);
+        assert!(config.to_cors().is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::application::auth::jwt::{JwtConfig, JwtKeys};
+use dotenvy::dotenv;
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+use std::env;
+
+/// 起動時にJWTの鍵を読み込んでStateに登録する。
+/// Rocket.tomlに鍵がなければ環境変数`JWT_SECRET_KEY`のHS256を使う
+pub fn jwt_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("JWT keys", |rocket: Rocket<Build>| async move {
+        let config = match rocket.figment().extract_inner::<JwtConfig>("jwt") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid jwt configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        if config.keys.is_empty() {
+            dotenv().ok();
+            let Ok(secret) = env::var("JWT_SECRET_KEY") else {
+                error!("JWT_SECRET_KEY must be set when jwt.keys is empty");
+                return fairing::Result::Err(rocket);
+            };
+            return fairing::Result::Ok(rocket.manage(JwtKeys::from_secret(&secret, &config)));
+        }
+        match JwtKeys::from_config(&config) {
+            Ok(keys) => fairing::Result::Ok(rocket.manage(keys)),
+            Err(e) => {
+                error!("Invalid jwt configuration: {}", e);
+                fairing::Result::Err(rocket)
+            }
+        }
+    })
+}
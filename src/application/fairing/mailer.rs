@@ -0,0 +1,28 @@
+use crate::infrastructure::mailer::{create_mailer, MailConfig, Mailer};
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+use std::sync::Arc;
+
+/// Rocket.tomlの設定からメーラーを作ってStateに登録する
+pub fn mailer_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("Mailer", |rocket: Rocket<Build>| async move {
+        let config = match rocket.figment().extract_inner::<MailConfig>("mail") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid mail configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        match create_mailer(&config) {
+            Ok(mailer) => fairing::Result::Ok(
+                rocket
+                    .manage(Arc::<dyn Mailer>::from(mailer))
+                    .manage(config),
+            ),
+            Err(e) => {
+                error!("Invalid mail configuration: {}", e);
+                fairing::Result::Err(rocket)
+            }
+        }
+    })
+}
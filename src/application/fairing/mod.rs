@@ -0,0 +1,11 @@
+pub mod cors;
+pub mod jwt;
+pub mod mailer;
+pub mod password;
+pub mod rate_limit;
+pub mod request_metrics;
+pub mod request_tracing;
+pub mod storage;
+pub mod thumbnail;
+pub mod two_factor;
+pub mod www_authenticate;
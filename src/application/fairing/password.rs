@@ -0,0 +1,50 @@
+use crate::application::auth::crypto::HashParams;
+use crate::domain::password_policy::PasswordPolicy;
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+use serde::Deserialize;
+use std::fs;
+
+/// Rocket.tomlの`password`テーブル
+#[derive(Debug, Deserialize)]
+struct PasswordConfig {
+    min_length: usize,
+    max_length: usize,
+    /// 漏洩したパスワードのリスト。1行に1つ、#で始まる行はコメント
+    breached_passwords_file: Option<String>,
+    argon2: HashParams,
+}
+
+fn load_breached_passwords(path: &str) -> std::io::Result<Vec<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty() && !v.starts_with('#'))
+        .map(|v| v.to_string())
+        .collect())
+}
+
+/// Rocket.tomlの設定からパスワードポリシーとハッシュのコストをStateに登録する
+pub fn password_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("Password", |rocket: Rocket<Build>| async move {
+        let config = match rocket.figment().extract_inner::<PasswordConfig>("password") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid password configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        let breached_passwords = match &config.breached_passwords_file {
+            Some(path) => match load_breached_passwords(path) {
+                Ok(breached_passwords) => breached_passwords,
+                Err(e) => {
+                    error!("Failed to load {}: {}", path, e);
+                    return fairing::Result::Err(rocket);
+                }
+            },
+            None => vec![],
+        };
+        let policy = PasswordPolicy::new(config.min_length, config.max_length, breached_passwords);
+        fairing::Result::Ok(rocket.manage(policy).manage(config.argon2))
+    })
+}
@@ -0,0 +1,19 @@
+use crate::application::rate_limit::{RateLimitConfig, SigninRateLimiter};
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+
+/// Rocket.tomlの設定からサインインのレートリミッターを作ってStateに登録する
+pub fn rate_limit_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("Rate limit", |rocket: Rocket<Build>| async move {
+        match rocket
+            .figment()
+            .extract_inner::<RateLimitConfig>("rate_limit")
+        {
+            Ok(config) => fairing::Result::Ok(rocket.manage(SigninRateLimiter::new(config))),
+            Err(e) => {
+                error!("Invalid rate limit configuration: {}", e);
+                fairing::Result::Err(rocket)
+            }
+        }
+    })
+}
@@ -0,0 +1,42 @@
+use crate::infrastructure::metrics::{HTTP_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Data, Request, Response};
+use std::time::Instant;
+
+struct RequestStart(Instant);
+
+/// ルート名ごとのリクエスト数とレイテンシを記録する
+pub struct RequestMetrics;
+
+#[rocket::async_trait]
+impl Fairing for RequestMetrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        request.local_cache(|| RequestStart(Instant::now()));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let start = request.local_cache(|| RequestStart(Instant::now()));
+        // どのルートにもマッチしなかったリクエストはまとめる
+        let route = request
+            .route()
+            .and_then(|v| v.name.as_deref())
+            .unwrap_or("unmatched");
+        HTTP_REQUESTS_TOTAL
+            .with_label_values(&[
+                route,
+                request.method().as_str(),
+                &response.status().code.to_string(),
+            ])
+            .inc();
+        HTTP_REQUEST_DURATION_SECONDS
+            .with_label_values(&[route])
+            .observe(start.0.elapsed().as_secs_f64());
+    }
+}
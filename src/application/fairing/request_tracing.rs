@@ -0,0 +1,113 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::request::{self, FromRequest};
+use rocket::{Data, Request, Response};
+use std::fmt;
+use std::time::Instant;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// リクエストごとに振るID。クライアントがX-Request-Idを付けていればそれを使う
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    pub fn generate() -> Self {
+        RequestId(format!("{:032x}", rand::random::<u128>()))
+    }
+
+    /// ログに埋め込むので英数字と`-_.`だけの128文字以下に限る
+    pub fn parse(value: &str) -> Option<Self> {
+        let is_valid = !value.is_empty()
+            && value.len() <= 128
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        is_valid.then(|| RequestId(value.to_string()))
+    }
+
+    fn from_request(request: &Request<'_>) -> Self {
+        request
+            .headers()
+            .get_one(REQUEST_ID_HEADER)
+            .and_then(RequestId::parse)
+            .unwrap_or_else(RequestId::generate)
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(
+            request
+                .local_cache(|| RequestId::from_request(request))
+                .clone(),
+        )
+    }
+}
+
+struct RequestStart(Instant);
+
+/// リクエストIDを振り、アクセスログを出す
+pub struct RequestTracing;
+
+#[rocket::async_trait]
+impl Fairing for RequestTracing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ID and access log",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        request.local_cache(|| RequestStart(Instant::now()));
+        request.local_cache(|| RequestId::from_request(request));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let request_id = request.local_cache(|| RequestId::from_request(request));
+        let start = request.local_cache(|| RequestStart(Instant::now()));
+        tracing::info!(
+            target: "access",
+            request_id = %request_id,
+            method = %request.method(),
+            path = %request.uri().path(),
+            status = response.status().code,
+            latency_ms = start.0.elapsed().as_secs_f64() * 1000.0,
+        );
+        response.set_header(Header::new(REQUEST_ID_HEADER, request_id.0.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::fairing::request_tracing::RequestId;
+
+    #[test]
+    fn test_request_id_parse() {
+        assert_eq!(
+            RequestId::parse("0b5e-4c1a_9.f").map(|v| v.0),
+            Some("0b5e-4c1a_9.f".to_string())
+        );
+        assert!(RequestId::parse("").is_none());
+        assert!(RequestId::parse("abc def").is_none());
+        assert!(RequestId::parse("abc\n{\"level\":\"ERROR\"}").is_none());
+        assert!(RequestId::parse(&"a".repeat(129)).is_none());
+    }
+
+    #[test]
+    fn test_request_id_generate() {
+        let request_id = RequestId::generate();
+        assert_eq!(request_id.0.len(), 32);
+        assert!(RequestId::parse(&request_id.0).is_some());
+    }
+}
@@ -0,0 +1,26 @@
+use crate::infrastructure::blob_store::{create_blob_store, BlobStore, StorageConfig};
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+use std::sync::Arc;
+
+/// Rocket.tomlの設定から画像の保存先を作って`Arc<dyn BlobStore>`としてStateに登録する
+pub fn storage_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("Storage", |rocket: Rocket<Build>| async move {
+        let config = match rocket.figment().extract_inner::<StorageConfig>("storage") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid storage configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        match create_blob_store(&config) {
+            Ok(blob_store) => {
+                fairing::Result::Ok(rocket.manage(Arc::<dyn BlobStore>::from(blob_store)))
+            }
+            Err(e) => {
+                error!("Invalid storage configuration: {}", e);
+                fairing::Result::Err(rocket)
+            }
+        }
+    })
+}
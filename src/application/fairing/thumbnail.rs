@@ -0,0 +1,51 @@
+use crate::application::thumbnail::{run_thumbnail_worker, ThumbnailConfig, ThumbnailQueue};
+use crate::infrastructure::blob_store::BlobStore;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::{Build, Orbit, Rocket};
+use std::sync::Arc;
+
+/// サムネイルを作るワーカー。起動したらバックグラウンドで動かし、アップロードのたびに起こす
+pub struct ThumbnailWorker;
+
+#[rocket::async_trait]
+impl Fairing for ThumbnailWorker {
+    fn info(&self) -> Info {
+        Info {
+            name: "Thumbnail worker",
+            kind: Kind::Ignite | Kind::Liftoff,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if let Err(e) = rocket
+            .figment()
+            .extract_inner::<ThumbnailConfig>("thumbnail")
+        {
+            error!("Invalid thumbnail configuration: {}", e);
+            return Err(rocket);
+        }
+        Ok(rocket.manage(Arc::new(ThumbnailQueue::default())))
+    }
+
+    /// 画像の保存先はコントローラーと同じ、`storage_fairing`が登録したものを使う
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let Ok(config) = rocket
+            .figment()
+            .extract_inner::<ThumbnailConfig>("thumbnail")
+        else {
+            return;
+        };
+        let (Some(blob_store), Some(queue)) = (
+            rocket.state::<Arc<dyn BlobStore>>(),
+            rocket.state::<Arc<ThumbnailQueue>>(),
+        ) else {
+            error!("Thumbnail worker requires the storage fairing");
+            return;
+        };
+        rocket::tokio::spawn(run_thumbnail_worker(
+            blob_store.clone(),
+            queue.clone(),
+            config,
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::domain::role::Role;
+use rocket::fairing::{self, AdHoc};
+use rocket::{Build, Rocket};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Rocket.tomlの`two_factor`テーブル
+#[derive(Debug, Deserialize, Clone)]
+pub struct TwoFactorConfig {
+    /// 認証アプリに表示する発行者名
+    pub issuer: String,
+    /// 2段階認証でサインインしないとパーミッションを使えないロール
+    pub required_roles: Vec<String>,
+}
+
+impl TwoFactorConfig {
+    pub fn is_required(&self, role: Role) -> bool {
+        self.required_roles
+            .iter()
+            .any(|v| Role::from_str(v).ok() == Some(role))
+    }
+}
+
+/// Rocket.tomlの設定から2段階認証の設定をStateに登録する
+pub fn two_factor_fairing() -> AdHoc {
+    AdHoc::try_on_ignite("Two factor", |rocket: Rocket<Build>| async move {
+        let config = match rocket
+            .figment()
+            .extract_inner::<TwoFactorConfig>("two_factor")
+        {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Invalid two factor configuration: {}", e);
+                return fairing::Result::Err(rocket);
+            }
+        };
+        if let Some(role) = config
+            .required_roles
+            .iter()
+            .find(|v| Role::from_str(v).is_err())
+        {
+            error!("Invalid two factor configuration: unknown role {}", role);
+            return fairing::Result::Err(rocket);
+        }
+        fairing::Result::Ok(rocket.manage(config))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::fairing::two_factor::TwoFactorConfig;
+    use crate::domain::role::Role;
+
+    #[test]
+    fn test_is_required() {
+        let config = TwoFactorConfig {
+            issuer: "onsen_tabi".to_string(),
+            required_roles: vec!["admin".to_string()],
+        };
+        assert!(config.is_required(Role::Admin));
+        assert!(!config.is_required(Role::Editor));
+        assert!(!config.is_required(Role::Viewer));
+    }
+}
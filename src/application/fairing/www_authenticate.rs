@@ -0,0 +1,25 @@
+use crate::application::controller::request_guard::FailedAuthentication;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Request, Response};
+
+/// 認証に失敗したレスポンスにRFC 6750のWWW-Authenticateヘッダーをつける
+pub struct WwwAuthenticate;
+
+#[rocket::async_trait]
+impl Fairing for WwwAuthenticate {
+    fn info(&self) -> Info {
+        Info {
+            name: "WWW-Authenticate",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let failed = request.local_cache(|| FailedAuthentication(None));
+        if let Some(error) = failed.0 {
+            if response.status() == error.status() {
+                response.set_raw_header("WWW-Authenticate", error.www_authenticate());
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::infrastructure::mailer::Mail;
+
+pub fn password_reset_mail(to: &str, password_reset_url: &str, token: &str) -> Mail {
+    Mail {
+        to: to.to_string(),
+        subject: "【onsen_tabi】パスワードの再設定".to_string(),
+        body: format!(
+            "パスワードの再設定を受け付けました。\n\
+             30分以内に次のURLからパスワードを再設定してください。\n\n\
+             {}?token={}\n\n\
+             心当たりがない場合はこのメールを破棄してください。",
+            password_reset_url, token
+        ),
+    }
+}
+
+pub fn email_verification_mail(to: &str, email_verification_url: &str, token: &str) -> Mail {
+    Mail {
+        to: to.to_string(),
+        subject: "【onsen_tabi】メールアドレスの確認".to_string(),
+        body: format!(
+            "onsen_tabiへの登録ありがとうございます。\n\
+             24時間以内に次のURLを開いてメールアドレスを確認してください。\n\n\
+             {}?token={}\n\n\
+             心当たりがない場合はこのメールを破棄してください。",
+            email_verification_url, token
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::mail_template::{email_verification_mail, password_reset_mail};
+
+    #[test]
+    fn test_password_reset_mail() {
+        let mail = password_reset_mail("a@example.com", "https://example.com/reset", "abc");
+        assert_eq!(mail.to, "a@example.com");
+        assert!(mail.body.contains("https://example.com/reset?token=abc\n"));
+    }
+
+    #[test]
+    fn test_email_verification_mail() {
+        let mail =
+            email_verification_mail("a@example.com", "https://api.example.com/verify", "abc");
+        assert!(mail
+            .body
+            .contains("https://api.example.com/verify?token=abc\n"));
+    }
+}
@@ -1,3 +1,9 @@
+mod achievement;
 pub mod api_model;
 mod auth;
 pub mod controller;
+mod exif;
+pub mod fairing;
+mod mail_template;
+pub mod rate_limit;
+pub mod thumbnail;
@@ -0,0 +1,330 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// メモリが増え続けないように、この数を超えたら使われていないエントリを捨てる
+const MAX_ENTRIES: usize = 10_000;
+
+/// Rocket.tomlの`rate_limit`テーブル
+#[derive(Debug, Deserialize, Clone)]
+pub struct RateLimitConfig {
+    /// IPアドレスごとのサインイン試行のバースト数と1分あたりの補充数
+    pub signin_ip_burst: u32,
+    pub signin_ip_per_minute: u32,
+    /// アカウントごとのサインイン試行のバースト数と1分あたりの補充数
+    pub signin_account_burst: u32,
+    pub signin_account_per_minute: u32,
+    /// 続けてパスワードを間違えるとロックする回数と、ロックする秒数
+    pub lockout_threshold: u32,
+    pub lockout_seconds: u64,
+    /// クライアントとの間にあるロードバランサーなどのプロキシの段数。0ならX-Forwarded-Forを見ない
+    #[serde(default)]
+    pub trusted_proxies: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    per_second: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, per_minute: u32, now: Instant) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            per_second: per_minute as f64 / 60.0,
+            tokens: capacity as f64,
+            updated_at: now,
+        }
+    }
+
+    /// トークンを1つ使う。足りなければ次のトークンが補充されるまでの時間を返す
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.per_second <= 0.0 {
+            return Err(Duration::from_secs(u64::MAX / 2));
+        }
+        Err(Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.per_second,
+        ))
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated_at = now;
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Lockout {
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+/// サインインの試行回数を制限する。プロセス内で保持するので、複数台で動かすときは台数分の上限になる
+pub struct SigninRateLimiter {
+    config: RateLimitConfig,
+    ip_buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+    account_buckets: Mutex<HashMap<String, TokenBucket>>,
+    lockouts: Mutex<HashMap<String, Lockout>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SigninLimit {
+    RateLimited { retry_after: Duration },
+    Locked { retry_after: Duration },
+}
+
+impl SigninRateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        SigninRateLimiter {
+            config,
+            ip_buckets: Mutex::new(HashMap::new()),
+            account_buckets: Mutex::new(HashMap::new()),
+            lockouts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// サインインを試行してよいか。試行する場合はトークンを使う
+    pub fn check(&self, ip: Option<IpAddr>, email: &str, now: Instant) -> Result<(), SigninLimit> {
+        let email = normalize(email);
+        if let Some(locked_until) = self.locked_until(&email, now) {
+            return Err(SigninLimit::Locked {
+                retry_after: locked_until - now,
+            });
+        }
+        if let Some(ip) = ip {
+            take(
+                &self.ip_buckets,
+                ip,
+                self.config.signin_ip_burst,
+                self.config.signin_ip_per_minute,
+                now,
+            )
+            .map_err(|retry_after| SigninLimit::RateLimited { retry_after })?;
+        }
+        take(
+            &self.account_buckets,
+            email,
+            self.config.signin_account_burst,
+            self.config.signin_account_per_minute,
+            now,
+        )
+        .map_err(|retry_after| SigninLimit::RateLimited { retry_after })
+    }
+
+    pub fn trusted_proxies(&self) -> usize {
+        self.config.trusted_proxies
+    }
+
+    /// サインアップやメールを送るリクエストなど、IPアドレスごとにだけ制限するリクエストを試行してよいか
+    pub fn check_ip(&self, ip: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+        let Some(ip) = ip else {
+            return Ok(());
+        };
+        take(
+            &self.ip_buckets,
+            ip,
+            self.config.signin_ip_burst,
+            self.config.signin_ip_per_minute,
+            now,
+        )
+    }
+
+    /// パスワードの間違いを記録する。ロックした場合はロックが解除されるまでの時間を返す
+    pub fn record_failure(&self, email: &str, now: Instant) -> Option<Duration> {
+        let mut lockouts = self.lockouts.lock().unwrap();
+        if lockouts.len() > MAX_ENTRIES {
+            lockouts.retain(|_, v| v.locked_until.map_or(false, |v| v > now));
+        }
+        let lockout = lockouts.entry(normalize(email)).or_default();
+        lockout.failures += 1;
+        if lockout.failures < self.config.lockout_threshold {
+            return None;
+        }
+        let lockout_duration = Duration::from_secs(self.config.lockout_seconds);
+        lockout.failures = 0;
+        lockout.locked_until = Some(now + lockout_duration);
+        Some(lockout_duration)
+    }
+
+    pub fn record_success(&self, email: &str) {
+        self.lockouts.lock().unwrap().remove(&normalize(email));
+    }
+
+    fn locked_until(&self, email: &str, now: Instant) -> Option<Instant> {
+        let lockouts = self.lockouts.lock().unwrap();
+        lockouts
+            .get(email)
+            .and_then(|v| v.locked_until)
+            .filter(|v| *v > now)
+    }
+}
+
+/// 制限に使うクライアントのIPアドレス。
+/// プロキシはX-Forwarded-Forの末尾に接続元を付け足すので、末尾からtrusted_proxies番目を使う。
+/// それより前はクライアントが自由に書けるので信用しない。足りなければ接続元を使う
+pub fn client_ip(
+    remote: Option<IpAddr>,
+    forwarded_for: &[&str],
+    trusted_proxies: usize,
+) -> Option<IpAddr> {
+    if trusted_proxies == 0 {
+        return remote;
+    }
+    let hops: Vec<&str> = forwarded_for
+        .iter()
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect();
+    hops.len()
+        .checked_sub(trusted_proxies)
+        .and_then(|i| hops[i].parse().ok())
+        .or(remote)
+}
+
+fn normalize(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn take<K: Eq + Hash>(
+    buckets: &Mutex<HashMap<K, TokenBucket>>,
+    key: K,
+    capacity: u32,
+    per_minute: u32,
+    now: Instant,
+) -> Result<(), Duration> {
+    let mut buckets = buckets.lock().unwrap();
+    if buckets.len() > MAX_ENTRIES {
+        buckets.retain(|_, v| !v.is_full(now));
+    }
+    buckets
+        .entry(key)
+        .or_insert_with(|| TokenBucket::new(capacity, per_minute, now))
+        .try_take(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::rate_limit::*;
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig {
+            signin_ip_burst: 3,
+            signin_ip_per_minute: 60,
+            signin_account_burst: 2,
+            signin_account_per_minute: 6,
+            lockout_threshold: 2,
+            lockout_seconds: 60,
+            trusted_proxies: 0,
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, 60, now);
+        assert!(bucket.try_take(now).is_ok());
+        assert!(bucket.try_take(now).is_ok());
+        assert_eq!(bucket.try_take(now), Err(Duration::from_secs(1)));
+        assert!(bucket.try_take(now + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_signin_rate_limit() {
+        let now = Instant::now();
+        let limiter = SigninRateLimiter::new(config());
+        let ip = Some("127.0.0.1".parse().unwrap());
+        assert!(limiter.check(ip, "a@example.com", now).is_ok());
+        assert!(limiter.check(ip, "A@example.com", now).is_ok());
+        assert_eq!(
+            limiter.check(ip, "a@example.com", now),
+            Err(SigninLimit::RateLimited {
+                retry_after: Duration::from_secs(10)
+            })
+        );
+        assert!(limiter.check(ip, "b@example.com", now).is_err());
+        assert!(limiter.check(None, "b@example.com", now).is_ok());
+    }
+
+    #[test]
+    fn test_ip_rate_limit() {
+        let now = Instant::now();
+        let limiter = SigninRateLimiter::new(config());
+        let ip = Some("127.0.0.1".parse().unwrap());
+        assert!(limiter.check_ip(ip, now).is_ok());
+        assert!(limiter.check(ip, "a@example.com", now).is_ok());
+        assert!(limiter.check_ip(ip, now).is_ok());
+        assert_eq!(limiter.check_ip(ip, now), Err(Duration::from_secs(1)));
+        assert!(limiter.check(ip, "b@example.com", now).is_err());
+        assert!(limiter
+            .check_ip(Some("127.0.0.2".parse().unwrap()), now)
+            .is_ok());
+        assert!(limiter.check_ip(None, now).is_ok());
+        assert!(limiter.check_ip(ip, now + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let remote = Some("10.0.0.1".parse().unwrap());
+        let client = Some("203.0.113.5".parse().unwrap());
+        assert_eq!(client_ip(remote, &["203.0.113.5"], 0), remote);
+        assert_eq!(client_ip(remote, &["203.0.113.5"], 1), client);
+        // クライアントが書いたX-Forwarded-Forは末尾に付け足されたものより前にある
+        assert_eq!(client_ip(remote, &["198.51.100.1, 203.0.113.5"], 1), client);
+        assert_eq!(
+            client_ip(remote, &["198.51.100.1", "203.0.113.5"], 1),
+            client
+        );
+        assert_eq!(
+            client_ip(remote, &["198.51.100.1, 203.0.113.5, 10.0.0.2"], 2),
+            client
+        );
+        assert_eq!(client_ip(remote, &[], 1), remote);
+        assert_eq!(client_ip(remote, &["203.0.113.5"], 2), remote);
+        assert_eq!(client_ip(remote, &["unknown"], 1), remote);
+        assert_eq!(
+            client_ip(remote, &["2001:db8::1"], 1),
+            Some("2001:db8::1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_signin_lockout() {
+        let now = Instant::now();
+        let limiter = SigninRateLimiter::new(config());
+        assert_eq!(limiter.record_failure("a@example.com", now), None);
+        assert_eq!(
+            limiter.record_failure("a@example.com", now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            limiter.check(None, "a@example.com", now + Duration::from_secs(20)),
+            Err(SigninLimit::Locked {
+                retry_after: Duration::from_secs(40)
+            })
+        );
+        assert!(limiter
+            .check(None, "a@example.com", now + Duration::from_secs(60))
+            .is_ok());
+        limiter.record_failure("a@example.com", now);
+        limiter.record_success("a@example.com");
+        assert_eq!(limiter.record_failure("a@example.com", now), None);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::domain::photo_entity::{ImageFormat, PhotoEntity, ThumbnailSize, ThumbnailStatus};
+use crate::infrastructure::blob_store::BlobStore;
+use crate::infrastructure::repository::photo_repository;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::error::{EncodingError, ImageFormatHint};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageError, ImageReader};
+use rocket::tokio::sync::Notify;
+use rocket::tokio::{task, time};
+use serde::Deserialize;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+const JPEG_QUALITY: u8 = 85;
+const WEBP_QUALITY: f32 = 80.0;
+
+/// Rocket.tomlの`[thumbnail]`
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThumbnailConfig {
+    /// アップロードの知らせがなくても未作成の写真を探す間隔
+    pub poll_seconds: u64,
+    /// 1回に処理する写真の数
+    pub batch_size: u32,
+}
+
+/// アップロードしたらワーカーを起こす
+#[derive(Default)]
+pub struct ThumbnailQueue(Notify);
+
+impl ThumbnailQueue {
+    pub fn wake(&self) {
+        self.0.notify_one();
+    }
+}
+
+/// 元の画像をデコードし、EXIFの向きを反映する
+fn decode(bytes: &[u8]) -> Result<DynamicImage, ImageError> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// 幅を`width`に縮める。元の画像のほうが小さければそのまま
+fn resize(image: &DynamicImage, width: u32) -> DynamicImage {
+    if image.width() <= width {
+        return image.clone();
+    }
+    let height = (image.height() as u64 * width as u64 / image.width() as u64).max(1) as u32;
+    image.resize_exact(width, height, FilterType::CatmullRom)
+}
+
+/// JPEGは透過を捨てる
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, ImageError> {
+    let mut bytes = Vec::new();
+    match format {
+        ImageFormat::Jpeg => {
+            JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+                .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))?;
+        }
+        ImageFormat::Png => {
+            image.write_with_encoder(PngEncoder::new(&mut bytes))?;
+        }
+        ImageFormat::Webp => {
+            let (width, height) = (image.width(), image.height());
+            let webp = if image.color().has_alpha() {
+                let pixels = image.to_rgba8();
+                webp::Encoder::from_rgba(&pixels, width, height).encode_simple(false, WEBP_QUALITY)
+            } else {
+                let pixels = image.to_rgb8();
+                webp::Encoder::from_rgb(&pixels, width, height).encode_simple(false, WEBP_QUALITY)
+            }
+            .map_err(|e| {
+                ImageError::Encoding(EncodingError::new(
+                    ImageFormatHint::Exact(image::ImageFormat::WebP),
+                    format!("{:?}", e),
+                ))
+            })?;
+            bytes.extend_from_slice(&webp);
+        }
+    }
+    Ok(bytes)
+}
+
+/// すべての幅と形式のサムネイルを作り、キーと形式とバイト列を返す
+pub fn create_thumbnails(
+    photo: &PhotoEntity,
+    bytes: &[u8],
+) -> Result<Vec<(String, ImageFormat, Vec<u8>)>, ImageError> {
+    let image = decode(bytes)?;
+    let mut thumbnails: Vec<(String, ImageFormat, Vec<u8>)> = Vec::new();
+    for size in ThumbnailSize::all() {
+        let key = photo.thumbnail_key(size);
+        if thumbnails.iter().any(|(v, _, _)| *v == key) {
+            continue;
+        }
+        let format = size.format(photo.format);
+        let thumbnail = encode(&resize(&image, size.width), format)?;
+        thumbnails.push((key, format, thumbnail));
+    }
+    Ok(thumbnails)
+}
+
+/// 未作成の写真のサムネイルを作る。状態を変えた写真の数を返し、保存先のエラーでは中断する
+fn process_pending(blob_store: &dyn BlobStore, batch_size: u32) -> usize {
+    let mut processed = 0;
+    for photo in photo_repository::get_pending_thumbnail_photos(batch_size) {
+        let bytes = match blob_store.get(&photo.blob_key) {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                warn!("Image {} is missing", photo.blob_key);
+                photo_repository::put_thumbnail_status(photo.id, ThumbnailStatus::Failed);
+                processed += 1;
+                continue;
+            }
+            Err(e) => {
+                error!("Failed to read image: {}", e);
+                break;
+            }
+        };
+        let thumbnails = match create_thumbnails(&photo, &bytes) {
+            Ok(thumbnails) => thumbnails,
+            Err(e) => {
+                warn!("Failed to create thumbnails of photo {}: {}", photo.id, e);
+                photo_repository::put_thumbnail_status(photo.id, ThumbnailStatus::Failed);
+                processed += 1;
+                continue;
+            }
+        };
+        let saved = thumbnails.iter().try_for_each(|(key, format, thumbnail)| {
+            blob_store.put(key, &format.to_string(), thumbnail)
+        });
+        if let Err(e) = saved {
+            error!("Failed to save thumbnail: {}", e);
+            break;
+        }
+        if !photo_repository::put_thumbnail_status(photo.id, ThumbnailStatus::Ready) {
+            // 作っている間に削除された
+            for (key, _, _) in &thumbnails {
+                let _ = blob_store.delete(key);
+            }
+        }
+        processed += 1;
+    }
+    processed
+}
+
+/// 未作成の写真がなくなったら、起こされるか`poll_seconds`が経つまで待つ
+pub async fn run_thumbnail_worker(
+    blob_store: Arc<dyn BlobStore>,
+    queue: Arc<ThumbnailQueue>,
+    config: ThumbnailConfig,
+) {
+    let poll = Duration::from_secs(config.poll_seconds);
+    loop {
+        let store = blob_store.clone();
+        let batch_size = config.batch_size;
+        let processed = task::spawn_blocking(move || process_pending(store.as_ref(), batch_size))
+            .await
+            .unwrap_or_else(|e| {
+                error!("Thumbnail worker panicked: {}", e);
+                0
+            });
+        if processed == 0 {
+            let _ = time::timeout(poll, queue.0.notified()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::thumbnail::*;
+    use crate::domain::photo_entity::PhotoTarget;
+    use image::{ImageFormat as Format, RgbImage};
+
+    fn photo(format: ImageFormat) -> PhotoEntity {
+        PhotoEntity {
+            id: 1,
+            target: PhotoTarget::Onsen,
+            target_id: 1,
+            user_id: 1,
+            blob_key: format!("photos/abc.{}", format.extension()),
+            format,
+            byte_size: 0,
+            caption: String::new(),
+            position: 0,
+            is_cover: false,
+            thumbnail_status: ThumbnailStatus::Pending,
+        }
+    }
+
+    fn dimensions(bytes: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory(bytes).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn test_create_thumbnails() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(800, 400));
+        let source = encode(&image, ImageFormat::Jpeg).unwrap();
+        let thumbnails = create_thumbnails(&photo(ImageFormat::Jpeg), &source).unwrap();
+        assert_eq!(thumbnails.len(), 6);
+        let (key, format, bytes) = &thumbnails[0];
+        assert_eq!(key, "photos/abc_320.jpg");
+        assert_eq!(*format, ImageFormat::Jpeg);
+        assert_eq!(dimensions(bytes), (320, 160));
+        let (key, format, bytes) = &thumbnails[1];
+        assert_eq!(key, "photos/abc_320.webp");
+        assert_eq!(*format, ImageFormat::Webp);
+        assert_eq!(image::guess_format(bytes).unwrap(), Format::WebP);
+        assert_eq!(dimensions(bytes), (320, 160));
+        // 元より大きくはしない
+        let (key, _, bytes) = &thumbnails[4];
+        assert_eq!(key, "photos/abc_1280.jpg");
+        assert_eq!(dimensions(bytes), (800, 400));
+    }
+
+    #[test]
+    fn test_create_thumbnails_of_webp() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(700, 10));
+        let source = encode(&image, ImageFormat::Webp).unwrap();
+        let thumbnails = create_thumbnails(&photo(ImageFormat::Webp), &source).unwrap();
+        let keys: Vec<&str> = thumbnails.iter().map(|(key, _, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "photos/abc_320.webp",
+                "photos/abc_640.webp",
+                "photos/abc_1280.webp"
+            ]
+        );
+        assert_eq!(dimensions(&thumbnails[0].2), (320, 4));
+    }
+
+    #[test]
+    fn test_create_thumbnails_of_broken_image() {
+        let photo = photo(ImageFormat::Png);
+        assert!(create_thumbnails(&photo, b"\x89PNG\r\n\x1a\n\0\0\0\0").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::domain::{
+    area_entity::AreaEntity, onsen::onsen_entity::OnsenEntity,
+    onsen::spring_category::SpringCategory, visit_stats::VisitStats,
+};
+use chrono::NaiveDateTime;
+use std::str::FromStr;
+
+/// 実績の達成条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum AchievementCondition {
+    /// 入った温泉の数
+    OnsenCount(u32),
+    /// 訪れた温泉地の数
+    AreaCount(u32),
+    /// 訪れた都道府県の数
+    PrefectureCount(u32),
+    /// 訪れた国民保養温泉地の数
+    NationalResortCount(u32),
+    /// 入った療養泉の分類の数。10ならすべての泉質
+    SpringCategoryCount(u32),
+    /// 療養泉の分類
+    SpringCategory(SpringCategory),
+    /// pHが指定した値より低い温泉に入った
+    PhBelow(f64),
+    /// pHが指定した値以上の温泉に入った
+    PhAtLeast(f64),
+    /// 都道府県の温泉地をすべて訪れた
+    PrefectureComplete(String),
+}
+
+impl AchievementCondition {
+    /// `kind`ごとに`target`か`value`のどちらかを使う。値が範囲外ならNone
+    pub fn new(kind: &str, target: &str, value: f64) -> Option<Self> {
+        let count = || (value >= 1.0 && value.fract() == 0.0).then_some(value as u32);
+        let ph = || (0.0..=14.0).contains(&value).then_some(value);
+        let condition = match kind {
+            "onsen_count" => Self::OnsenCount(count()?),
+            "area_count" => Self::AreaCount(count()?),
+            "prefecture_count" => Self::PrefectureCount(count()?),
+            "national_resort_count" => Self::NationalResortCount(count()?),
+            "spring_category_count" => {
+                let count = count()?;
+                if count as usize > SpringCategory::ALL.len() {
+                    return None;
+                }
+                Self::SpringCategoryCount(count)
+            }
+            "spring_category" => Self::SpringCategory(SpringCategory::from_str(target).ok()?),
+            "ph_below" => Self::PhBelow(ph()?),
+            "ph_at_least" => Self::PhAtLeast(ph()?),
+            "prefecture_complete" if !target.is_empty() => {
+                Self::PrefectureComplete(target.to_string())
+            }
+            _ => return None,
+        };
+        Some(condition)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::OnsenCount(_) => "onsen_count",
+            Self::AreaCount(_) => "area_count",
+            Self::PrefectureCount(_) => "prefecture_count",
+            Self::NationalResortCount(_) => "national_resort_count",
+            Self::SpringCategoryCount(_) => "spring_category_count",
+            Self::SpringCategory(_) => "spring_category",
+            Self::PhBelow(_) => "ph_below",
+            Self::PhAtLeast(_) => "ph_at_least",
+            Self::PrefectureComplete(_) => "prefecture_complete",
+        }
+    }
+
+    /// 使わなければ空文字
+    pub fn target(&self) -> String {
+        match self {
+            Self::SpringCategory(category) => category.to_string(),
+            Self::PrefectureComplete(prefecture) => prefecture.clone(),
+            _ => "".to_string(),
+        }
+    }
+
+    /// 使わなければ0
+    pub fn value(&self) -> f64 {
+        match self {
+            Self::OnsenCount(count)
+            | Self::AreaCount(count)
+            | Self::PrefectureCount(count)
+            | Self::NationalResortCount(count)
+            | Self::SpringCategoryCount(count) => *count as f64,
+            Self::PhBelow(ph) | Self::PhAtLeast(ph) => *ph,
+            Self::SpringCategory(_) | Self::PrefectureComplete(_) => 0.0,
+        }
+    }
+
+    /// `onsens`は入った温泉、`areas`は都道府県の温泉地をすべて含むこと
+    pub fn is_satisfied(
+        &self,
+        stats: &VisitStats,
+        onsens: &[OnsenEntity],
+        areas: &[AreaEntity],
+    ) -> bool {
+        let visited_phs = || {
+            onsens
+                .iter()
+                .filter(|v| stats.onsen_ids.contains(&v.id))
+                .filter_map(|v| v.ph)
+        };
+        match self {
+            Self::OnsenCount(count) => stats.onsen_ids.len() >= *count as usize,
+            Self::AreaCount(count) => stats.area_ids.len() >= *count as usize,
+            Self::PrefectureCount(count) => stats.prefectures.len() >= *count as usize,
+            Self::NationalResortCount(count) => stats.national_resort_ids.len() >= *count as usize,
+            Self::SpringCategoryCount(count) => stats.spring_categories.len() >= *count as usize,
+            Self::SpringCategory(category) => stats.spring_categories.contains(category),
+            Self::PhBelow(ph) => visited_phs().any(|v| v < *ph),
+            Self::PhAtLeast(ph) => visited_phs().any(|v| v >= *ph),
+            Self::PrefectureComplete(prefecture) => {
+                let mut prefecture_areas = areas
+                    .iter()
+                    .filter(|v| &v.prefecture == prefecture)
+                    .peekable();
+                prefecture_areas.peek().is_some()
+                    && prefecture_areas.all(|v| stats.area_ids.contains(&v.id))
+            }
+        }
+    }
+}
+
+/// 管理者が定義する実績
+#[derive(Clone)]
+pub struct AchievementEntity {
+    pub id: u32,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+}
+
+impl AchievementEntity {
+    /// 名前が空ならNone
+    pub fn new(
+        id: u32,
+        name: &str,
+        description: &str,
+        condition: AchievementCondition,
+    ) -> Option<Self> {
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self {
+            id,
+            name: name.to_string(),
+            description: description.to_string(),
+            condition,
+        })
+    }
+}
+
+/// ユーザーが獲得したバッジ
+#[derive(Clone)]
+pub struct UserAchievementEntity {
+    pub user_id: u32,
+    pub achievement_id: u32,
+    pub achieved_at: NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::achievement_entity::*;
+    use crate::domain::onsen::chemical::{Chemical::*, ClType};
+    use crate::domain::onsen::onsen_entity::SpringLiquid;
+    use crate::domain::onsen::onsen_quality::OnsenQuality;
+    use crate::domain::test_fixtures::{self, area, onsen};
+    use crate::domain::visit_entity::VisitEntity;
+    use chrono::NaiveDate;
+
+    fn visit(id: u32, onsen_id: u32) -> VisitEntity {
+        test_fixtures::visit(
+            id,
+            1,
+            onsen_id,
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+        )
+    }
+
+    #[test]
+    fn new_test() {
+        let condition = AchievementCondition::new("spring_category_count", "", 10.0).unwrap();
+        assert_eq!(condition, AchievementCondition::SpringCategoryCount(10));
+        assert_eq!(condition.kind(), "spring_category_count");
+        assert_eq!(condition.value(), 10.0);
+        let condition = AchievementCondition::new("spring_category", "acidic", 0.0).unwrap();
+        assert_eq!(condition.target(), "acidic");
+        let condition = AchievementCondition::new("prefecture_complete", "群馬県", 0.0).unwrap();
+        assert_eq!(condition.target(), "群馬県");
+    }
+
+    #[test]
+    fn new_test_return_none_when_invalid() {
+        assert!(AchievementCondition::new("unknown", "", 1.0).is_none());
+        assert!(AchievementCondition::new("onsen_count", "", 0.0).is_none());
+        assert!(AchievementCondition::new("onsen_count", "", 1.5).is_none());
+        assert!(AchievementCondition::new("spring_category_count", "", 11.0).is_none());
+        assert!(AchievementCondition::new("spring_category", "spa", 0.0).is_none());
+        assert!(AchievementCondition::new("ph_below", "", 15.0).is_none());
+        assert!(AchievementCondition::new("prefecture_complete", "", 0.0).is_none());
+        assert!(AchievementEntity::new(1, "", "", AchievementCondition::OnsenCount(1)).is_none());
+    }
+
+    #[test]
+    fn is_satisfied_test() {
+        let areas = vec![
+            area(1, "群馬県", true),
+            area(2, "群馬県", true),
+            area(3, "栃木県", true),
+        ];
+        let onsens = vec![
+            onsen(
+                1,
+                Some(OnsenQuality::new(&[HIon, S], Some(SpringLiquid::Acidic))),
+                Some(1.7),
+                Some(1),
+            ),
+            onsen(
+                2,
+                Some(OnsenQuality::new(&[NaIon, ClIon(ClType::Normal)], None)),
+                None,
+                Some(2),
+            ),
+            onsen(3, Some(OnsenQuality::new(&[], None)), Some(9.8), Some(3)),
+        ];
+        let stats = VisitStats::compute(&[visit(1, 1), visit(2, 2)], &onsens, &areas);
+        let satisfied =
+            |condition: AchievementCondition| condition.is_satisfied(&stats, &onsens, &areas);
+        assert!(satisfied(AchievementCondition::OnsenCount(2)));
+        assert!(!satisfied(AchievementCondition::OnsenCount(3)));
+        assert!(satisfied(AchievementCondition::NationalResortCount(2)));
+        assert!(satisfied(AchievementCondition::SpringCategoryCount(3)));
+        assert!(!satisfied(AchievementCondition::SpringCategoryCount(10)));
+        assert!(satisfied(AchievementCondition::SpringCategory(
+            SpringCategory::Acidic
+        )));
+        assert!(satisfied(AchievementCondition::PhBelow(2.0)));
+        assert!(!satisfied(AchievementCondition::PhBelow(1.5)));
+        assert!(!satisfied(AchievementCondition::PhAtLeast(9.0)));
+        assert!(satisfied(AchievementCondition::PrefectureComplete(
+            "群馬県".to_string()
+        )));
+        assert!(!satisfied(AchievementCondition::PrefectureComplete(
+            "栃木県".to_string()
+        )));
+        assert!(!satisfied(AchievementCondition::PrefectureComplete(
+            "長野県".to_string()
+        )));
+    }
+}
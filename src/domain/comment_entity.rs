@@ -0,0 +1,208 @@
+use chrono::NaiveDateTime;
+use strum_macros::{Display, EnumString};
+
+/// コメントをつけられる対象
+#[derive(Display, Debug, PartialEq, Eq, Hash, EnumString, Clone, Copy)]
+pub enum CommentTarget {
+    #[strum(serialize = "trip")]
+    Trip,
+    #[strum(serialize = "visit")]
+    Visit,
+}
+
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum CommentStatus {
+    #[strum(serialize = "visible")]
+    Visible,
+    /// 管理者が非表示にした
+    #[strum(serialize = "hidden")]
+    Hidden,
+    /// 返信のあるコメントを書いた人が削除した。スレッドを残すために本文だけ消す
+    #[strum(serialize = "deleted")]
+    Deleted,
+}
+
+#[derive(Clone, Debug)]
+pub struct CommentEntity {
+    pub id: u32,
+    pub target: CommentTarget,
+    pub target_id: u32,
+    pub user_id: u32,
+    /// 返信のときは返信先のコメント。返信への返信はできない
+    pub parent_id: Option<u32>,
+    pub body: String,
+    pub status: CommentStatus,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl CommentEntity {
+    /// 本文は1〜1000文字
+    pub fn new(
+        target: CommentTarget,
+        target_id: u32,
+        user_id: u32,
+        parent_id: Option<u32>,
+        body: &str,
+        now: NaiveDateTime,
+    ) -> Option<Self> {
+        let body = Self::valid_body(body)?;
+        Some(CommentEntity {
+            id: 0,
+            target,
+            target_id,
+            user_id,
+            parent_id,
+            body,
+            status: CommentStatus::Visible,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn valid_body(body: &str) -> Option<String> {
+        let body = body.trim();
+        (1..=1000)
+            .contains(&body.chars().count())
+            .then(|| body.to_string())
+    }
+
+    /// 同じ対象の表示中のコメントで、返信でないものにだけ返信できる
+    pub fn accepts_reply(&self, target: CommentTarget, target_id: u32) -> bool {
+        self.target == target
+            && self.target_id == target_id
+            && self.parent_id.is_none()
+            && self.status == CommentStatus::Visible
+    }
+}
+
+/// コメントと、それへの返信
+#[derive(Clone, Debug)]
+pub struct CommentThread {
+    pub comment: CommentEntity,
+    pub replies: Vec<CommentEntity>,
+}
+
+/// 古い順に並んだコメントをスレッドにまとめる
+pub fn build_threads(comments: Vec<CommentEntity>) -> Vec<CommentThread> {
+    let (parents, replies): (Vec<CommentEntity>, Vec<CommentEntity>) =
+        comments.into_iter().partition(|v| v.parent_id.is_none());
+    let mut threads: Vec<CommentThread> = parents
+        .into_iter()
+        .map(|comment| CommentThread {
+            comment,
+            replies: vec![],
+        })
+        .collect();
+    for reply in replies {
+        if let Some(thread) = threads
+            .iter_mut()
+            .find(|v| Some(v.comment.id) == reply.parent_id)
+        {
+            thread.replies.push(reply);
+        }
+    }
+    threads
+}
+
+/// 通報の理由
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum ReportReason {
+    #[strum(serialize = "spam")]
+    Spam,
+    #[strum(serialize = "harassment")]
+    Harassment,
+    #[strum(serialize = "inappropriate")]
+    Inappropriate,
+    #[strum(serialize = "other")]
+    Other,
+}
+
+/// 1人1件。管理者が対応すると消える
+#[derive(Clone, Debug)]
+pub struct CommentReportEntity {
+    pub comment_id: u32,
+    pub user_id: u32,
+    pub reason: ReportReason,
+    pub note: String,
+}
+
+impl CommentReportEntity {
+    /// 補足は500文字まで
+    pub fn new(comment_id: u32, user_id: u32, reason: ReportReason, note: &str) -> Option<Self> {
+        let note = note.trim();
+        if note.chars().count() > 500 {
+            return None;
+        }
+        Some(CommentReportEntity {
+            comment_id,
+            user_id,
+            reason,
+            note: note.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::comment_entity::*;
+    use chrono::NaiveDate;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+
+    fn comment(id: u32, parent_id: Option<u32>) -> CommentEntity {
+        CommentEntity {
+            id,
+            ..CommentEntity::new(CommentTarget::Trip, 1, 2, parent_id, "いいですね", now()).unwrap()
+        }
+    }
+
+    #[test]
+    fn comment_entity_test() {
+        let comment = CommentEntity::new(CommentTarget::Trip, 1, 2, None, " 楽しそう ", now());
+        assert_eq!(comment.unwrap().body, "楽しそう");
+        assert!(CommentEntity::new(CommentTarget::Trip, 1, 2, None, " ", now()).is_none());
+        let body = "湯".repeat(1001);
+        assert!(CommentEntity::new(CommentTarget::Trip, 1, 2, None, &body, now()).is_none());
+    }
+
+    #[test]
+    fn accepts_reply_test() {
+        assert!(comment(1, None).accepts_reply(CommentTarget::Trip, 1));
+        assert!(!comment(1, None).accepts_reply(CommentTarget::Visit, 1));
+        assert!(!comment(1, None).accepts_reply(CommentTarget::Trip, 2));
+        assert!(!comment(2, Some(1)).accepts_reply(CommentTarget::Trip, 1));
+        let hidden = CommentEntity {
+            status: CommentStatus::Hidden,
+            ..comment(1, None)
+        };
+        assert!(!hidden.accepts_reply(CommentTarget::Trip, 1));
+    }
+
+    #[test]
+    fn build_threads_test() {
+        let threads = build_threads(vec![
+            comment(1, None),
+            comment(2, None),
+            comment(3, Some(1)),
+            comment(4, Some(2)),
+            comment(5, Some(1)),
+        ]);
+        let ids: Vec<(u32, Vec<u32>)> = threads
+            .iter()
+            .map(|v| (v.comment.id, v.replies.iter().map(|v| v.id).collect()))
+            .collect();
+        assert_eq!(ids, vec![(1, vec![3, 5]), (2, vec![4])]);
+    }
+
+    #[test]
+    fn comment_report_entity_test() {
+        assert!(CommentReportEntity::new(1, 2, ReportReason::Spam, "").is_some());
+        assert!(CommentReportEntity::new(1, 2, ReportReason::Other, &"a".repeat(501)).is_none());
+    }
+}
@@ -0,0 +1,82 @@
+/// メールアドレス。RFC 5321の長さの制限と、よくある形式だけを受け付ける。
+/// 大文字小文字で別のユーザーにならないように小文字にそろえる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email(String);
+
+impl Email {
+    pub fn new(value: &str) -> Option<Self> {
+        let value = Self::normalize(value);
+        if value.len() > 254 {
+            return None;
+        }
+        let (local, domain) = value.rsplit_once('@')?;
+        if local.is_empty() || local.len() > 64 || local.contains('@') {
+            return None;
+        }
+        if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+            return None;
+        }
+        let is_local_char =
+            |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c);
+        if !local.chars().all(is_local_char) {
+            return None;
+        }
+        let labels: Vec<&str> = domain.split('.').collect();
+        if labels.len() < 2 {
+            return None;
+        }
+        let is_valid_label = |label: &&str| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        };
+        if !labels.iter().all(is_valid_label) {
+            return None;
+        }
+        Some(Email(value))
+    }
+
+    /// サインインなどで入力されたメールアドレスを、検証せずに登録時と同じ形にそろえる
+    pub fn normalize(value: &str) -> String {
+        value.trim().to_lowercase()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::email::Email;
+
+    #[test]
+    fn test_valid_email() {
+        assert_eq!(
+            Email::new("onsen@example.com").map(|v| v.as_str().to_string()),
+            Some("onsen@example.com".to_string())
+        );
+        assert!(Email::new(" first.last+tag@mail.example.co.jp ").is_some());
+        assert_eq!(
+            Email::new(" Onsen@Example.COM ").map(|v| v.as_str().to_string()),
+            Some("onsen@example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_email() {
+        assert!(Email::new("").is_none());
+        assert!(Email::new("onsen").is_none());
+        assert!(Email::new("@example.com").is_none());
+        assert!(Email::new("onsen@").is_none());
+        assert!(Email::new("onsen@localhost").is_none());
+        assert!(Email::new("onsen@@example.com").is_none());
+        assert!(Email::new("on sen@example.com").is_none());
+        assert!(Email::new(".onsen@example.com").is_none());
+        assert!(Email::new("onsen@example..com").is_none());
+        assert!(Email::new("onsen@-example.com").is_none());
+        assert!(Email::new(&format!("{}@example.com", "a".repeat(65))).is_none());
+    }
+}
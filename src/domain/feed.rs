@@ -0,0 +1,251 @@
+use crate::domain::review_entity::{HotelReviewEntity, OnsenReviewEntity};
+use crate::domain::visit_entity::VisitEntity;
+use chrono::NaiveDateTime;
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+/// タイムラインに載せる、フォローしている人の投稿
+#[derive(Clone)]
+pub enum FeedContent {
+    Visit(VisitEntity),
+    OnsenReview(OnsenReviewEntity),
+    HotelReview(HotelReviewEntity),
+}
+
+#[derive(Clone)]
+pub struct FeedItem {
+    pub user_id: u32,
+    pub posted_at: NaiveDateTime,
+    pub content: FeedContent,
+}
+
+impl FeedItem {
+    pub fn new(posted_at: NaiveDateTime, content: FeedContent) -> Self {
+        let user_id = match &content {
+            FeedContent::Visit(visit) => visit.user_id,
+            FeedContent::OnsenReview(review) => review.user_id,
+            FeedContent::HotelReview(review) => review.user_id,
+        };
+        FeedItem {
+            user_id,
+            posted_at,
+            content,
+        }
+    }
+}
+
+/// 投稿の種類。同じ時刻の投稿はこの順に並べる
+#[derive(Display, EnumString, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum FeedKind {
+    #[strum(serialize = "visit")]
+    Visit,
+    #[strum(serialize = "onsenReview")]
+    OnsenReview,
+    #[strum(serialize = "hotelReview")]
+    HotelReview,
+}
+
+impl FeedContent {
+    pub fn kind(&self) -> FeedKind {
+        match self {
+            FeedContent::Visit(_) => FeedKind::Visit,
+            FeedContent::OnsenReview(_) => FeedKind::OnsenReview,
+            FeedContent::HotelReview(_) => FeedKind::HotelReview,
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        match self {
+            FeedContent::Visit(visit) => visit.id,
+            FeedContent::OnsenReview(review) => review.id,
+            FeedContent::HotelReview(review) => review.id,
+        }
+    }
+}
+
+/// フィードの位置。時刻の新しい順、同じ時刻なら種類の順、同じ種類ならIDの大きい順に並べる。
+/// 時刻は秒で丸めず、同じ時刻の投稿も種類とIDで区別する
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FeedCursor {
+    pub posted_at: NaiveDateTime,
+    pub kind: FeedKind,
+    pub id: u32,
+}
+
+/// 種類ごとの読み込み条件。`posted_at`より前か、同じ時刻で`id`より小さいもの。
+/// `id`がNoneなら同じ時刻のものはすべて
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FeedBound {
+    pub posted_at: NaiveDateTime,
+    pub id: Option<u32>,
+}
+
+const CURSOR_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6fZ";
+
+impl FeedCursor {
+    pub fn of(item: &FeedItem) -> Self {
+        FeedCursor {
+            posted_at: item.posted_at,
+            kind: item.content.kind(),
+            id: item.content.id(),
+        }
+    }
+
+    /// この位置より後ろにある`kind`の投稿の条件
+    pub fn bound(&self, kind: FeedKind) -> FeedBound {
+        let id = match kind.cmp(&self.kind) {
+            std::cmp::Ordering::Less => Some(0),
+            std::cmp::Ordering::Equal => Some(self.id),
+            std::cmp::Ordering::Greater => None,
+        };
+        FeedBound {
+            posted_at: self.posted_at,
+            id,
+        }
+    }
+}
+
+/// `2026-10-01T09:00:00.123456Z,visit,12`の形式
+impl fmt::Display for FeedCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{}",
+            self.posted_at.format(CURSOR_TIME_FORMAT),
+            self.kind,
+            self.id
+        )
+    }
+}
+
+impl FromStr for FeedCursor {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split(',');
+        let (Some(posted_at), Some(kind), Some(id), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(());
+        };
+        Ok(FeedCursor {
+            posted_at: NaiveDateTime::parse_from_str(posted_at, "%Y-%m-%dT%H:%M:%S%.fZ")
+                .map_err(|_| ())?,
+            kind: FeedKind::from_str(kind).map_err(|_| ())?,
+            id: id.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+/// 種類ごとに`FeedCursor`の順で読み込んだ投稿をまとめ、先頭から`limit`件にする
+pub fn merge_feed(items: Vec<Vec<FeedItem>>, limit: usize) -> Vec<FeedItem> {
+    let mut items: Vec<FeedItem> = items.into_iter().flatten().collect();
+    items.sort_by_key(|v| {
+        (
+            Reverse(v.posted_at),
+            v.content.kind(),
+            Reverse(v.content.id()),
+        )
+    });
+    items.truncate(limit);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::feed::*;
+    use crate::domain::review_entity::ReviewStatus;
+    use crate::domain::test_fixtures;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn visit(user_id: u32, hour: u32) -> FeedItem {
+        visit_with_id(1, user_id, hour)
+    }
+
+    fn visit_with_id(id: u32, user_id: u32, hour: u32) -> FeedItem {
+        let visit = test_fixtures::visit(id, user_id, 1, at(hour).date());
+        FeedItem::new(at(hour), FeedContent::Visit(visit))
+    }
+
+    fn hotel_review(user_id: u32, hour: u32) -> FeedItem {
+        let review =
+            HotelReviewEntity::new(1, user_id, 1, 3, 3, 3, "", ReviewStatus::Approved).unwrap();
+        FeedItem::new(at(hour), FeedContent::HotelReview(review))
+    }
+
+    #[test]
+    fn merge_feed_test() {
+        let feed = merge_feed(
+            vec![
+                vec![visit(1, 9), visit(2, 5)],
+                vec![hotel_review(3, 7), hotel_review(1, 3)],
+            ],
+            3,
+        );
+        let posted: Vec<(u32, NaiveDateTime)> =
+            feed.iter().map(|v| (v.user_id, v.posted_at)).collect();
+        assert_eq!(posted, vec![(1, at(9)), (3, at(7)), (2, at(5))]);
+        assert!(matches!(feed[1].content, FeedContent::HotelReview(_)));
+    }
+
+    #[test]
+    fn merge_feed_order_test() {
+        let feed = merge_feed(
+            vec![
+                vec![visit_with_id(2, 1, 9), visit_with_id(1, 1, 9)],
+                vec![hotel_review(3, 9)],
+            ],
+            3,
+        );
+        let cursors: Vec<(FeedKind, u32)> = feed
+            .iter()
+            .map(|v| (v.content.kind(), v.content.id()))
+            .collect();
+        assert_eq!(
+            cursors,
+            vec![
+                (FeedKind::Visit, 2),
+                (FeedKind::Visit, 1),
+                (FeedKind::HotelReview, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn feed_cursor_test() {
+        let posted_at = at(9) + chrono::Duration::microseconds(123456);
+        let cursor = FeedCursor {
+            posted_at,
+            kind: FeedKind::OnsenReview,
+            id: 12,
+        };
+        assert_eq!(
+            cursor.to_string(),
+            "2026-10-01T09:00:00.123456Z,onsenReview,12"
+        );
+        assert_eq!(FeedCursor::from_str(&cursor.to_string()), Ok(cursor));
+        assert_eq!(
+            FeedCursor::from_str("2026-10-01T09:00:00Z,visit,3")
+                .unwrap()
+                .posted_at,
+            at(9)
+        );
+        assert!(FeedCursor::from_str("2026-10-01T09:00:00Z").is_err());
+        assert!(FeedCursor::from_str("2026-10-01T09:00:00Z,trip,3").is_err());
+        assert!(FeedCursor::from_str("2026-10-01T09:00:00Z,visit,3,4").is_err());
+
+        assert_eq!(cursor.bound(FeedKind::Visit).id, Some(0));
+        assert_eq!(cursor.bound(FeedKind::OnsenReview).id, Some(12));
+        assert_eq!(cursor.bound(FeedKind::HotelReview).id, None);
+        assert_eq!(cursor.bound(FeedKind::Visit).posted_at, posted_at);
+    }
+}
@@ -48,6 +48,7 @@ mod tests {
             None,
             "単純温泉",
             Some("neutral"),
+            None,
             Some("isotonic"),
             Some("hot"),
             "sotoyu",
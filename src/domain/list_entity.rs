@@ -0,0 +1,188 @@
+use strum_macros::{Display, EnumString};
+
+/// リストの種類。お気に入りと行きたいはユーザーごとに1つずつ
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum ListKind {
+    #[strum(serialize = "favorite")]
+    Favorite, // お気に入り
+    #[strum(serialize = "want_to_go")]
+    WantToGo, // 行きたい
+    #[strum(serialize = "custom")]
+    Custom, // 名前をつけたリスト
+}
+
+impl ListKind {
+    /// お気に入りと行きたいの名前
+    pub fn default_name(&self) -> Option<&'static str> {
+        match self {
+            ListKind::Favorite => Some("お気に入り"),
+            ListKind::WantToGo => Some("行きたい"),
+            ListKind::Custom => None,
+        }
+    }
+}
+
+/// リストに入れられるもの
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum ListItemTarget {
+    #[strum(serialize = "onsen")]
+    Onsen,
+    #[strum(serialize = "hotel")]
+    Hotel,
+    #[strum(serialize = "area")]
+    Area,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListItemEntity {
+    pub target: ListItemTarget,
+    pub target_id: u32,
+}
+
+/// ユーザーのリスト。並び順は`items`の順
+#[derive(Clone)]
+pub struct ListEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub kind: ListKind,
+    pub name: String,
+    /// 共有リンクのトークン。共有していなければNone
+    pub share_token: Option<String>,
+    pub items: Vec<ListItemEntity>,
+}
+
+impl ListEntity {
+    /// 名前は前後の空白を除いて1〜255文字。名前が長すぎる、同じ項目が重複している場合はNone
+    pub fn new(
+        id: u32,
+        user_id: u32,
+        kind: ListKind,
+        name: &str,
+        share_token: Option<String>,
+        items: Vec<ListItemEntity>,
+    ) -> Option<Self> {
+        let name = name.trim();
+        if !(1..=255).contains(&name.chars().count()) {
+            return None;
+        }
+        if items
+            .iter()
+            .enumerate()
+            .any(|(index, item)| items[..index].contains(item))
+        {
+            return None;
+        }
+        Some(Self {
+            id,
+            user_id,
+            kind,
+            name: name.to_string(),
+            share_token,
+            items,
+        })
+    }
+
+    /// 名前を変えたり削除したりできるのは自分で作ったリストだけ
+    pub fn is_custom(&self) -> bool {
+        self.kind == ListKind::Custom
+    }
+
+    /// 末尾に追加する。既に入っていればfalse
+    pub fn add_item(&mut self, item: ListItemEntity) -> bool {
+        if self.items.contains(&item) {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+
+    /// 入っていなければfalse
+    pub fn remove_item(&mut self, item: &ListItemEntity) -> bool {
+        let len = self.items.len();
+        self.items.retain(|v| v != item);
+        self.items.len() != len
+    }
+
+    /// 今の項目をすべて1回ずつ並べたものでなければfalse
+    pub fn reorder(&mut self, items: Vec<ListItemEntity>) -> bool {
+        if items.len() != self.items.len() || !self.items.iter().all(|v| items.contains(v)) {
+            return false;
+        }
+        self.items = items;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::list_entity::*;
+
+    fn item(target: ListItemTarget, target_id: u32) -> ListItemEntity {
+        ListItemEntity { target, target_id }
+    }
+
+    #[test]
+    fn new_test_return_none_when_invalid() {
+        assert!(ListEntity::new(1, 2, ListKind::Custom, "", None, vec![]).is_none());
+        assert!(ListEntity::new(1, 2, ListKind::Custom, "  ", None, vec![]).is_none());
+        assert!(ListEntity::new(1, 2, ListKind::Custom, &"湯".repeat(256), None, vec![]).is_none());
+        assert!(ListEntity::new(
+            1,
+            2,
+            ListKind::Custom,
+            "群馬",
+            None,
+            vec![
+                item(ListItemTarget::Onsen, 1),
+                item(ListItemTarget::Onsen, 1)
+            ]
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn new_test_trim_name() {
+        let list = ListEntity::new(1, 2, ListKind::Custom, " 群馬 ", None, vec![]).unwrap();
+        assert_eq!(list.name, "群馬");
+        assert!(ListEntity::new(1, 2, ListKind::Custom, &"湯".repeat(255), None, vec![]).is_some());
+    }
+
+    #[test]
+    fn add_and_remove_item_test() {
+        let mut list =
+            ListEntity::new(1, 2, ListKind::Favorite, "お気に入り", None, vec![]).unwrap();
+        assert!(list.add_item(item(ListItemTarget::Onsen, 1)));
+        assert!(list.add_item(item(ListItemTarget::Area, 1)));
+        assert!(!list.add_item(item(ListItemTarget::Onsen, 1)));
+        assert_eq!(list.items.len(), 2);
+        assert!(list.remove_item(&item(ListItemTarget::Onsen, 1)));
+        assert!(!list.remove_item(&item(ListItemTarget::Hotel, 1)));
+        assert_eq!(list.items, vec![item(ListItemTarget::Area, 1)]);
+    }
+
+    #[test]
+    fn reorder_test() {
+        let mut list = ListEntity::new(
+            1,
+            2,
+            ListKind::WantToGo,
+            "行きたい",
+            None,
+            vec![
+                item(ListItemTarget::Onsen, 1),
+                item(ListItemTarget::Hotel, 2),
+            ],
+        )
+        .unwrap();
+        assert!(!list.reorder(vec![item(ListItemTarget::Hotel, 2)]));
+        assert!(!list.reorder(vec![
+            item(ListItemTarget::Hotel, 2),
+            item(ListItemTarget::Onsen, 3)
+        ]));
+        assert!(list.reorder(vec![
+            item(ListItemTarget::Hotel, 2),
+            item(ListItemTarget::Onsen, 1)
+        ]));
+        assert_eq!(list.items[0], item(ListItemTarget::Hotel, 2));
+    }
+}
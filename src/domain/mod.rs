@@ -1,3 +1,19 @@
+pub mod achievement_entity;
 pub mod area_entity;
+pub mod comment_entity;
+pub mod email;
+pub mod feed;
 pub mod hotel_entity;
+pub mod list_entity;
+pub mod notification_entity;
 pub mod onsen;
+pub mod password_policy;
+pub mod photo_entity;
+pub mod profile_entity;
+pub mod review_entity;
+pub mod role;
+#[cfg(test)]
+pub mod test_fixtures;
+pub mod trip_entity;
+pub mod visit_entity;
+pub mod visit_stats;
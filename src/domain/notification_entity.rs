@@ -0,0 +1,91 @@
+use crate::domain::comment_entity::CommentEntity;
+use strum_macros::{Display, EnumString};
+
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum NotificationKind {
+    /// 自分の旅行・入浴記録へのコメント
+    #[strum(serialize = "comment")]
+    Comment,
+    /// 自分のコメントへの返信
+    #[strum(serialize = "reply")]
+    Reply,
+}
+
+#[derive(Clone, Debug)]
+pub struct NotificationEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub kind: NotificationKind,
+    /// 通知のきっかけになったユーザー
+    pub actor_id: u32,
+    pub comment_id: u32,
+    pub is_read: bool,
+}
+
+/// コメントを知らせる相手。対象の持ち主と返信先のコメントを書いた人に、自分のコメントは知らせない
+pub fn comment_notifications(
+    comment: &CommentEntity,
+    owner_id: u32,
+    parent_author_id: Option<u32>,
+) -> Vec<NotificationEntity> {
+    let notification = |user_id, kind| NotificationEntity {
+        id: 0,
+        user_id,
+        kind,
+        actor_id: comment.user_id,
+        comment_id: comment.id,
+        is_read: false,
+    };
+    let mut notifications = vec![];
+    if let Some(parent_author_id) = parent_author_id.filter(|v| *v != comment.user_id) {
+        notifications.push(notification(parent_author_id, NotificationKind::Reply));
+    }
+    if owner_id != comment.user_id && Some(owner_id) != parent_author_id {
+        notifications.push(notification(owner_id, NotificationKind::Comment));
+    }
+    notifications
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::comment_entity::{CommentEntity, CommentTarget};
+    use crate::domain::notification_entity::*;
+    use chrono::NaiveDate;
+
+    fn comment(user_id: u32) -> CommentEntity {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        CommentEntity::new(CommentTarget::Trip, 1, user_id, None, "いいですね", now).unwrap()
+    }
+
+    fn recipients(notifications: Vec<NotificationEntity>) -> Vec<(u32, NotificationKind)> {
+        notifications.iter().map(|v| (v.user_id, v.kind)).collect()
+    }
+
+    #[test]
+    fn comment_notifications_test() {
+        assert_eq!(
+            recipients(comment_notifications(&comment(2), 1, None)),
+            vec![(1, NotificationKind::Comment)]
+        );
+        assert!(comment_notifications(&comment(1), 1, None).is_empty());
+        assert_eq!(
+            recipients(comment_notifications(&comment(3), 1, Some(2))),
+            vec![(2, NotificationKind::Reply), (1, NotificationKind::Comment)]
+        );
+        assert_eq!(
+            recipients(comment_notifications(&comment(2), 1, Some(1))),
+            vec![(1, NotificationKind::Reply)]
+        );
+        assert_eq!(
+            recipients(comment_notifications(&comment(1), 1, Some(2))),
+            vec![(2, NotificationKind::Reply)]
+        );
+        assert_eq!(
+            recipients(comment_notifications(&comment(2), 1, Some(2))),
+            vec![(1, NotificationKind::Comment)]
+        );
+    }
+}
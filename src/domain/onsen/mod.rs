@@ -2,3 +2,4 @@ pub mod onsen_entity;
 
 pub mod chemical;
 pub mod onsen_quality;
+pub mod spring_category;
@@ -61,6 +61,8 @@ pub struct OnsenEntity {
     pub quality: Option<OnsenQuality>,
     pub spring_quality: String,
     pub liquid: Option<SpringLiquid>,
+    /// 水素イオン濃度。分析書に記載があれば
+    pub ph: Option<f64>,
     pub osmotic_pressure: Option<SpringOsmoticPressure>,
     pub temperature: Option<SpringTemperature>,
     pub form: SpringForm,
@@ -78,6 +80,7 @@ impl OnsenEntity {
         quality: Option<OnsenQuality>,
         spring_quality: &str,
         liquid: Option<&str>,
+        ph: Option<f64>,
         osmotic_pressure: Option<&str>,
         temperature: Option<&str>,
         form: &str,
@@ -90,6 +93,9 @@ impl OnsenEntity {
         if name.is_empty() {
             return None;
         }
+        if ph.is_some_and(|v| !(0.0..=14.0).contains(&v)) {
+            return None;
+        }
         let liquid = liquid.and_then(|v| SpringLiquid::from_str(v).ok());
         let osmotic_pressure =
             osmotic_pressure.and_then(|v| SpringOsmoticPressure::from_str(v).ok());
@@ -101,6 +107,7 @@ impl OnsenEntity {
             quality,
             spring_quality: spring_quality.to_string(),
             liquid,
+            ph,
             osmotic_pressure,
             temperature,
             form,
@@ -132,6 +139,7 @@ mod tests {
             Some(COMMON_ONSEN_QUALITY.clone()),
             "ナトリウム・カルシウム 塩化物硫酸塩温泉",
             Some("neutral"),
+            None,
             Some("hypotonic"),
             Some("hot"),
             "uchiyu",
@@ -154,6 +162,7 @@ mod tests {
             Some(COMMON_ONSEN_QUALITY.clone()),
             "ナトリウム・カルシウム 塩化物硫酸塩温泉",
             Some("neutral"),
+            None,
             Some("hypotonic"),
             Some("hot"),
             "uchiyu",
@@ -165,4 +174,29 @@ mod tests {
         );
         onsen.expect("");
     }
+
+    #[test]
+    fn new_test_return_none_when_ph_is_out_of_range() {
+        let onsen = |ph| {
+            OnsenEntity::new(
+                1,
+                "湯畑",
+                None,
+                "",
+                Some("acidic"),
+                Some(ph),
+                None,
+                None,
+                "sotoyu",
+                true,
+                "",
+                None,
+                "",
+                None,
+            )
+        };
+        assert_eq!(onsen(2.1).unwrap().ph, Some(2.1));
+        assert!(onsen(-0.1).is_none());
+        assert!(onsen(14.1).is_none());
+    }
 }
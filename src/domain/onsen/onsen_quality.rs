@@ -3,6 +3,7 @@ use crate::domain::onsen::chemical::ClType;
 use crate::domain::onsen::chemical::RnType;
 use crate::domain::onsen::onsen_entity::SpringLiquid;
 use crate::domain::onsen::onsen_entity::SpringLiquid::*;
+use crate::domain::onsen::spring_category::SpringCategory;
 use std::{fmt, vec};
 
 use super::chemical::FeType;
@@ -90,6 +91,23 @@ impl OnsenQuality {
         self.inclusions.contains(&Rn(RnType::Weak))
     }
 
+    /// 該当する療養泉の分類。単純硫黄温泉などは硫黄泉に含める
+    pub fn categories(&self) -> Vec<SpringCategory> {
+        let found: Vec<SpringCategory> = self
+            .anions
+            .iter()
+            .chain(self.inclusions.iter())
+            .filter_map(SpringCategory::of)
+            .collect();
+        if found.is_empty() {
+            return vec![SpringCategory::Simple];
+        }
+        SpringCategory::ALL
+            .into_iter()
+            .filter(|v| found.contains(v))
+            .collect()
+    }
+
     pub fn to_string_vec(&self) -> Vec<String> {
         let cations_string_vec: Vec<String> = self.cations.iter().map(|v| v.to_string()).collect();
         let anions_string_vec: Vec<String> = self.anions.iter().map(|v| v.to_string()).collect();
@@ -157,6 +175,7 @@ mod tests {
     use crate::domain::onsen::chemical::RnType;
     use crate::domain::onsen::onsen_quality::OnsenQuality;
     use crate::domain::onsen::onsen_quality::SpringLiquid::*;
+    use crate::domain::onsen::spring_category::SpringCategory;
 
     #[test]
     fn test_tanjun_onsen() {
@@ -424,4 +443,22 @@ mod tests {
         assert_eq!(quality.to_string(), "含鉄（Ⅱ）－ナトリウム－炭酸水素塩泉");
         assert_eq!(quality.to_string_vec(), vec!["NaIon", "HCO3Ion", "FeIon"]);
     }
+
+    #[test]
+    fn test_categories() {
+        let quality = OnsenQuality::new(&[], Some(Alkaline));
+        assert_eq!(quality.categories(), vec![SpringCategory::Simple]);
+        let quality = OnsenQuality::new(&[S], None);
+        assert_eq!(quality.categories(), vec![SpringCategory::Sulfur]);
+        let quality = OnsenQuality::new(&[HIon, S, SO4Ion, ClIon(ClType::Normal)], Some(Acidic));
+        assert_eq!(
+            quality.categories(),
+            vec![
+                SpringCategory::Chloride,
+                SpringCategory::Sulfate,
+                SpringCategory::Acidic,
+                SpringCategory::Sulfur
+            ]
+        );
+    }
 }
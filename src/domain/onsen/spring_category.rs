@@ -0,0 +1,91 @@
+use crate::domain::onsen::chemical::Chemical::{self, *};
+use strum_macros::{Display, EnumString};
+
+/// 療養泉の泉質の分類
+// https://www.env.go.jp/nature/onsen/pdf/2-5_p_16.pdf
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum SpringCategory {
+    #[strum(serialize = "simple")]
+    Simple, // 単純温泉
+    #[strum(serialize = "chloride")]
+    Chloride, // 塩化物泉
+    #[strum(serialize = "hydrogen_carbonate")]
+    HydrogenCarbonate, // 炭酸水素塩泉
+    #[strum(serialize = "sulfate")]
+    Sulfate, // 硫酸塩泉
+    #[strum(serialize = "carbon_dioxide")]
+    CarbonDioxide, // 二酸化炭素泉
+    #[strum(serialize = "iron")]
+    Iron, // 含鉄泉
+    #[strum(serialize = "acidic")]
+    Acidic, // 酸性泉
+    #[strum(serialize = "iodine")]
+    Iodine, // 含よう素泉
+    #[strum(serialize = "sulfur")]
+    Sulfur, // 硫黄泉
+    #[strum(serialize = "radioactive")]
+    Radioactive, // 放射能泉
+}
+
+impl SpringCategory {
+    pub const ALL: [SpringCategory; 10] = [
+        SpringCategory::Simple,
+        SpringCategory::Chloride,
+        SpringCategory::HydrogenCarbonate,
+        SpringCategory::Sulfate,
+        SpringCategory::CarbonDioxide,
+        SpringCategory::Iron,
+        SpringCategory::Acidic,
+        SpringCategory::Iodine,
+        SpringCategory::Sulfur,
+        SpringCategory::Radioactive,
+    ];
+
+    /// 陰イオンと特殊成分から決まる分類。陽イオンや銅・アルミニウムはNone
+    pub fn of(chemical: &Chemical) -> Option<Self> {
+        match chemical {
+            ClIon(_) => Some(SpringCategory::Chloride),
+            HCO3Ion => Some(SpringCategory::HydrogenCarbonate),
+            SO4Ion => Some(SpringCategory::Sulfate),
+            CO2 => Some(SpringCategory::CarbonDioxide),
+            FeIon(_) => Some(SpringCategory::Iron),
+            HIon => Some(SpringCategory::Acidic),
+            IIon => Some(SpringCategory::Iodine),
+            S => Some(SpringCategory::Sulfur),
+            Rn(_) => Some(SpringCategory::Radioactive),
+            NaIon | CaIon | MgIon | AlIon | CuIon => None,
+        }
+    }
+
+    pub fn jp(&self) -> &'static str {
+        match self {
+            SpringCategory::Simple => "単純温泉",
+            SpringCategory::Chloride => "塩化物泉",
+            SpringCategory::HydrogenCarbonate => "炭酸水素塩泉",
+            SpringCategory::Sulfate => "硫酸塩泉",
+            SpringCategory::CarbonDioxide => "二酸化炭素泉",
+            SpringCategory::Iron => "含鉄泉",
+            SpringCategory::Acidic => "酸性泉",
+            SpringCategory::Iodine => "含よう素泉",
+            SpringCategory::Sulfur => "硫黄泉",
+            SpringCategory::Radioactive => "放射能泉",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::onsen::chemical::{Chemical::*, ClType};
+    use crate::domain::onsen::spring_category::SpringCategory;
+
+    #[test]
+    fn test_of() {
+        assert_eq!(
+            SpringCategory::of(&ClIon(ClType::Strong)),
+            Some(SpringCategory::Chloride)
+        );
+        assert_eq!(SpringCategory::of(&HIon), Some(SpringCategory::Acidic));
+        assert_eq!(SpringCategory::of(&NaIon), None);
+        assert_eq!(SpringCategory::of(&CuIon), None);
+    }
+}
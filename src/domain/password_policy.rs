@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PasswordPolicyViolation {
+    TooShort,
+    TooLong,
+    Breached,
+}
+
+/// パスワードの長さ（文字数）と、漏洩したパスワードのリストで検証する
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    max_length: usize,
+    breached_passwords: HashSet<String>,
+}
+
+impl PasswordPolicy {
+    pub fn new(
+        min_length: usize,
+        max_length: usize,
+        breached_passwords: impl IntoIterator<Item = String>,
+    ) -> Self {
+        PasswordPolicy {
+            min_length,
+            max_length,
+            breached_passwords: breached_passwords
+                .into_iter()
+                .map(|v| v.to_lowercase())
+                .collect(),
+        }
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), PasswordPolicyViolation> {
+        let length = password.chars().count();
+        if length < self.min_length {
+            return Err(PasswordPolicyViolation::TooShort);
+        }
+        if length > self.max_length {
+            return Err(PasswordPolicyViolation::TooLong);
+        }
+        if self.breached_passwords.contains(&password.to_lowercase()) {
+            return Err(PasswordPolicyViolation::Breached);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::password_policy::{PasswordPolicy, PasswordPolicyViolation};
+
+    #[test]
+    fn test_validate_password() {
+        let policy = PasswordPolicy::new(8, 12, vec!["Password1".to_string()]);
+        assert_eq!(policy.validate(""), Err(PasswordPolicyViolation::TooShort));
+        assert_eq!(
+            policy.validate("1234567"),
+            Err(PasswordPolicyViolation::TooShort)
+        );
+        assert_eq!(
+            policy.validate("1234567890123"),
+            Err(PasswordPolicyViolation::TooLong)
+        );
+        assert_eq!(
+            policy.validate("password1"),
+            Err(PasswordPolicyViolation::Breached)
+        );
+        assert_eq!(policy.validate("あいうえおかきく"), Ok(()));
+        assert_eq!(policy.validate("onsen-tabi"), Ok(()));
+    }
+}
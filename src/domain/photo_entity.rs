@@ -0,0 +1,263 @@
+use strum_macros::{Display, EnumString};
+
+/// 写真をつける対象
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum PhotoTarget {
+    #[strum(serialize = "onsen")]
+    Onsen,
+    #[strum(serialize = "area")]
+    Area,
+    #[strum(serialize = "visit")]
+    Visit,
+    /// プロフィールのアイコン。`target_id`はユーザーのID
+    #[strum(serialize = "avatar")]
+    Avatar,
+}
+
+/// アップロードできる画像の形式
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum ImageFormat {
+    #[strum(serialize = "image/jpeg")]
+    Jpeg,
+    #[strum(serialize = "image/png")]
+    Png,
+    #[strum(serialize = "image/webp")]
+    Webp,
+}
+
+impl ImageFormat {
+    /// Content-Typeは信用せず先頭のバイト列で判定する
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return Some(ImageFormat::Jpeg);
+        }
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(ImageFormat::Png);
+        }
+        if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            return Some(ImageFormat::Webp);
+        }
+        None
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => "png",
+            ImageFormat::Webp => "webp",
+        }
+    }
+}
+
+/// サムネイルの生成状況。作れない画像は`Failed`にして元の画像を使う
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum ThumbnailStatus {
+    #[strum(serialize = "pending")]
+    Pending,
+    #[strum(serialize = "ready")]
+    Ready,
+    #[strum(serialize = "failed")]
+    Failed,
+}
+
+/// サムネイルの幅。元の画像より大きくはしない
+pub const THUMBNAIL_WIDTHS: [u32; 3] = [320, 640, 1280];
+
+/// `GET /images/<id>/<size>`の`<size>`。`320`は元と同じ形式、`320.webp`はWebP
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ThumbnailSize {
+    pub width: u32,
+    pub webp: bool,
+}
+
+impl ThumbnailSize {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (width, webp) = match value.strip_suffix(".webp") {
+            Some(width) => (width, true),
+            None => (value, false),
+        };
+        let width = width.parse().ok()?;
+        THUMBNAIL_WIDTHS
+            .contains(&width)
+            .then_some(ThumbnailSize { width, webp })
+    }
+
+    pub fn all() -> Vec<Self> {
+        THUMBNAIL_WIDTHS
+            .iter()
+            .flat_map(|width| {
+                [false, true].map(|webp| ThumbnailSize {
+                    width: *width,
+                    webp,
+                })
+            })
+            .collect()
+    }
+
+    pub fn format(&self, source: ImageFormat) -> ImageFormat {
+        if self.webp {
+            ImageFormat::Webp
+        } else {
+            source
+        }
+    }
+}
+
+/// 温泉・温泉地・入浴記録の写真。並び順は`position`の昇順
+#[derive(Clone, Debug)]
+pub struct PhotoEntity {
+    pub id: u32,
+    pub target: PhotoTarget,
+    pub target_id: u32,
+    /// アップロードしたユーザー
+    pub user_id: u32,
+    /// `BlobStore`のキー
+    pub blob_key: String,
+    pub format: ImageFormat,
+    pub byte_size: u32,
+    pub caption: String,
+    pub position: u32,
+    /// 一覧で代表として表示する写真。対象ごとに1枚まで
+    pub is_cover: bool,
+    pub thumbnail_status: ThumbnailStatus,
+}
+
+impl PhotoEntity {
+    pub fn is_valid_caption(caption: &str) -> bool {
+        caption.chars().count() <= 255
+    }
+
+    /// 元の画像のキーに幅をつける。`photos/abc.jpg`の320pxのWebPは`photos/abc_320.webp`
+    pub fn thumbnail_key(&self, size: ThumbnailSize) -> String {
+        let stem = self
+            .blob_key
+            .rsplit_once('.')
+            .map_or(self.blob_key.as_str(), |(stem, _)| stem);
+        format!(
+            "{}_{}.{}",
+            stem,
+            size.width,
+            size.format(self.format).extension()
+        )
+    }
+
+    /// 元の画像とすべてのサムネイルのキー。WebPの写真は同じキーをまとめる
+    pub fn blob_keys(&self) -> Vec<String> {
+        let mut keys = vec![self.blob_key.clone()];
+        for size in ThumbnailSize::all() {
+            let key = self.thumbnail_key(size);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+}
+
+/// `ids`の中の`id`を`position`番目(0始まり)に移す。範囲外なら末尾。`id`がなければfalse
+pub fn move_photo(ids: &mut Vec<u32>, id: u32, position: usize) -> bool {
+    let Some(index) = ids.iter().position(|v| *v == id) else {
+        return false;
+    };
+    ids.remove(index);
+    ids.insert(position.min(ids.len()), id);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::photo_entity::*;
+
+    #[test]
+    fn detect_test() {
+        assert_eq!(
+            ImageFormat::detect(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(
+            ImageFormat::detect(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::detect(b"RIFF\x24\0\0\0WEBPVP8 "),
+            Some(ImageFormat::Webp)
+        );
+        assert_eq!(ImageFormat::detect(b"GIF89a"), None);
+        assert_eq!(ImageFormat::Jpeg.to_string(), "image/jpeg");
+    }
+
+    #[test]
+    fn thumbnail_size_test() {
+        assert_eq!(
+            ThumbnailSize::parse("320"),
+            Some(ThumbnailSize {
+                width: 320,
+                webp: false
+            })
+        );
+        assert_eq!(
+            ThumbnailSize::parse("1280.webp"),
+            Some(ThumbnailSize {
+                width: 1280,
+                webp: true
+            })
+        );
+        assert_eq!(ThumbnailSize::parse("321"), None);
+        assert_eq!(ThumbnailSize::parse("320.png"), None);
+        assert_eq!(ThumbnailSize::parse("webp"), None);
+        assert_eq!(ThumbnailSize::all().len(), THUMBNAIL_WIDTHS.len() * 2);
+    }
+
+    #[test]
+    fn thumbnail_key_test() {
+        let photo = PhotoEntity {
+            id: 1,
+            target: PhotoTarget::Onsen,
+            target_id: 1,
+            user_id: 1,
+            blob_key: "photos/abc.jpg".to_string(),
+            format: ImageFormat::Jpeg,
+            byte_size: 100,
+            caption: String::new(),
+            position: 0,
+            is_cover: false,
+            thumbnail_status: ThumbnailStatus::Pending,
+        };
+        let size = ThumbnailSize::parse("320").unwrap();
+        assert_eq!(photo.thumbnail_key(size), "photos/abc_320.jpg");
+        let size = ThumbnailSize::parse("320.webp").unwrap();
+        assert_eq!(photo.thumbnail_key(size), "photos/abc_320.webp");
+        assert_eq!(photo.blob_keys().len(), 7);
+        let webp_photo = PhotoEntity {
+            blob_key: "photos/abc.webp".to_string(),
+            format: ImageFormat::Webp,
+            ..photo
+        };
+        assert_eq!(
+            webp_photo.blob_keys(),
+            vec![
+                "photos/abc.webp",
+                "photos/abc_320.webp",
+                "photos/abc_640.webp",
+                "photos/abc_1280.webp"
+            ]
+        );
+    }
+
+    #[test]
+    fn is_valid_caption_test() {
+        assert!(PhotoEntity::is_valid_caption(""));
+        assert!(PhotoEntity::is_valid_caption(&"湯".repeat(255)));
+        assert!(!PhotoEntity::is_valid_caption(&"湯".repeat(256)));
+    }
+
+    #[test]
+    fn move_photo_test() {
+        let mut ids = vec![1, 2, 3];
+        assert!(move_photo(&mut ids, 3, 0));
+        assert_eq!(ids, vec![3, 1, 2]);
+        assert!(move_photo(&mut ids, 3, 10));
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(!move_photo(&mut ids, 4, 0));
+    }
+}
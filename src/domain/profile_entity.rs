@@ -0,0 +1,228 @@
+use strum_macros::{Display, EnumString};
+
+/// 公開範囲
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum Visibility {
+    #[strum(serialize = "public")]
+    Public,
+    /// 承認済みのフォロワーと本人
+    #[strum(serialize = "followers")]
+    Followers,
+    /// 本人だけ
+    #[strum(serialize = "private")]
+    Private,
+}
+
+/// 見ている人とプロフィールの持ち主との関係
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Viewer {
+    Owner,
+    Follower,
+    /// ログインしていない人も含む
+    Other,
+}
+
+impl Visibility {
+    pub fn allows(&self, viewer: Viewer) -> bool {
+        match self {
+            Visibility::Public => true,
+            Visibility::Followers => viewer != Viewer::Other,
+            Visibility::Private => viewer == Viewer::Owner,
+        }
+    }
+}
+
+/// プロフィールに載せる項目ごとの公開範囲。個別に非公開にした記録や旅行はどの設定でも見せない
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PrivacySettings {
+    pub profile: Visibility,
+    pub visits: Visibility,
+    pub trips: Visibility,
+    pub reviews: Visibility,
+    /// フォローを承認制にする
+    pub approve_followers: bool,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        PrivacySettings {
+            profile: Visibility::Public,
+            visits: Visibility::Public,
+            trips: Visibility::Public,
+            reviews: Visibility::Public,
+            approve_followers: false,
+        }
+    }
+}
+
+impl PrivacySettings {
+    /// プロフィール自体が見えないときは項目も見せない
+    pub fn shows(&self, item: Visibility, viewer: Viewer) -> bool {
+        self.profile.allows(viewer) && item.allows(viewer)
+    }
+
+    /// 旅行・入浴記録を見せるか。持ち主以外には個別に公開していて項目の公開範囲の内にあるものだけ
+    pub fn shows_record(&self, item: Visibility, is_public: bool, viewer: Viewer) -> bool {
+        viewer == Viewer::Owner || (is_public && self.shows(item, viewer))
+    }
+}
+
+/// 公開プロフィール。ログインに使う`user`とは別に持ち、メールアドレスは載せない
+#[derive(Clone, Debug)]
+pub struct ProfileEntity {
+    pub user_id: u32,
+    /// URLに使う一意の名前
+    pub handle: String,
+    pub display_name: String,
+    pub bio: String,
+    pub privacy: PrivacySettings,
+}
+
+impl ProfileEntity {
+    /// ハンドルは英小文字・数字・`_`の3〜30文字、表示名は1〜50文字、自己紹介は500文字まで
+    pub fn new(
+        user_id: u32,
+        handle: &str,
+        display_name: &str,
+        bio: &str,
+        privacy: PrivacySettings,
+    ) -> Option<Self> {
+        let is_valid_handle = (3..=30).contains(&handle.len())
+            && handle
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        let display_name = display_name.trim();
+        let is_valid_display_name = (1..=50).contains(&display_name.chars().count());
+        let bio = bio.trim();
+        if !is_valid_handle || !is_valid_display_name || bio.chars().count() > 500 {
+            return None;
+        }
+        Some(ProfileEntity {
+            user_id,
+            handle: handle.to_string(),
+            display_name: display_name.to_string(),
+            bio: bio.to_string(),
+            privacy,
+        })
+    }
+}
+
+/// フォローの状態。承認制のプロフィールへのフォローは承認されるまで`Pending`
+#[derive(Display, Debug, PartialEq, EnumString, Clone, Copy)]
+pub enum FollowStatus {
+    #[strum(serialize = "pending")]
+    Pending,
+    #[strum(serialize = "accepted")]
+    Accepted,
+}
+
+#[derive(Clone, Debug)]
+pub struct FollowEntity {
+    pub follower_id: u32,
+    pub followee_id: u32,
+    pub status: FollowStatus,
+}
+
+impl FollowEntity {
+    /// 自分はフォローできない
+    pub fn new(follower_id: u32, followee: &ProfileEntity) -> Option<Self> {
+        if follower_id == followee.user_id {
+            return None;
+        }
+        let status = if followee.privacy.approve_followers {
+            FollowStatus::Pending
+        } else {
+            FollowStatus::Accepted
+        };
+        Some(FollowEntity {
+            follower_id,
+            followee_id: followee.user_id,
+            status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::profile_entity::*;
+
+    #[test]
+    fn visibility_test() {
+        assert!(Visibility::Public.allows(Viewer::Other));
+        assert!(!Visibility::Followers.allows(Viewer::Other));
+        assert!(Visibility::Followers.allows(Viewer::Follower));
+        assert!(!Visibility::Private.allows(Viewer::Follower));
+        assert!(Visibility::Private.allows(Viewer::Owner));
+    }
+
+    #[test]
+    fn privacy_settings_test() {
+        let privacy = PrivacySettings {
+            profile: Visibility::Followers,
+            ..PrivacySettings::default()
+        };
+        assert!(!privacy.shows(privacy.visits, Viewer::Other));
+        assert!(privacy.shows(privacy.visits, Viewer::Follower));
+        let privacy = PrivacySettings {
+            reviews: Visibility::Private,
+            ..PrivacySettings::default()
+        };
+        assert!(privacy.shows(privacy.trips, Viewer::Other));
+        assert!(!privacy.shows(privacy.reviews, Viewer::Follower));
+        assert!(privacy.shows(privacy.reviews, Viewer::Owner));
+    }
+
+    #[test]
+    fn shows_record_test() {
+        let privacy = PrivacySettings {
+            profile: Visibility::Private,
+            ..PrivacySettings::default()
+        };
+        assert!(!privacy.shows_record(privacy.trips, true, Viewer::Other));
+        assert!(!privacy.shows_record(privacy.trips, true, Viewer::Follower));
+        assert!(privacy.shows_record(privacy.trips, true, Viewer::Owner));
+        let privacy = PrivacySettings {
+            visits: Visibility::Followers,
+            ..PrivacySettings::default()
+        };
+        assert!(!privacy.shows_record(privacy.visits, true, Viewer::Other));
+        assert!(privacy.shows_record(privacy.visits, true, Viewer::Follower));
+        assert!(privacy.shows_record(privacy.trips, true, Viewer::Other));
+        assert!(!privacy.shows_record(privacy.trips, false, Viewer::Follower));
+        assert!(privacy.shows_record(privacy.trips, false, Viewer::Owner));
+    }
+
+    #[test]
+    fn profile_entity_test() {
+        let privacy = PrivacySettings::default();
+        let profile = ProfileEntity::new(1, "onsen_lover", " 温泉好き ", "", privacy).unwrap();
+        assert_eq!(profile.display_name, "温泉好き");
+        assert!(ProfileEntity::new(1, "ab", "名前", "", privacy).is_none());
+        assert!(ProfileEntity::new(1, "Onsen", "名前", "", privacy).is_none());
+        assert!(ProfileEntity::new(1, "onsen-lover", "名前", "", privacy).is_none());
+        assert!(ProfileEntity::new(1, "onsen", " ", "", privacy).is_none());
+        assert!(ProfileEntity::new(1, "onsen", "名前", &"湯".repeat(501), privacy).is_none());
+    }
+
+    #[test]
+    fn follow_entity_test() {
+        let privacy = PrivacySettings::default();
+        let profile = ProfileEntity::new(2, "onsen", "名前", "", privacy).unwrap();
+        assert_eq!(
+            FollowEntity::new(1, &profile).unwrap().status,
+            FollowStatus::Accepted
+        );
+        assert!(FollowEntity::new(2, &profile).is_none());
+        let profile = ProfileEntity {
+            privacy: PrivacySettings {
+                approve_followers: true,
+                ..privacy
+            },
+            ..profile
+        };
+        assert_eq!(
+            FollowEntity::new(1, &profile).unwrap().status,
+            FollowStatus::Pending
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use strum_macros::{Display, EnumString};
+
+/// 口コミの公開状態。本文のない評価だけの口コミは承認済みにする
+#[derive(Display, Debug, PartialEq, Eq, EnumString, Clone, Copy)]
+pub enum ReviewStatus {
+    #[strum(serialize = "pending")]
+    Pending, // 承認待ち
+    #[strum(serialize = "approved")]
+    Approved, // 公開
+    #[strum(serialize = "rejected")]
+    Rejected, // 非公開
+}
+
+impl ReviewStatus {
+    /// 本文を書き換えたら承認し直す
+    pub fn for_comment(comment: &str) -> Self {
+        if comment.trim().is_empty() {
+            ReviewStatus::Approved
+        } else {
+            ReviewStatus::Pending
+        }
+    }
+}
+
+/// 1から5の評価
+fn is_score(score: u8) -> bool {
+    (1..=5).contains(&score)
+}
+
+/// 口コミの本文は2000文字まで
+fn is_valid_comment(comment: &str) -> bool {
+    comment.chars().count() <= 2000
+}
+
+/// 温泉の評価と口コミ。1人1件
+#[derive(Clone, Debug)]
+pub struct OnsenReviewEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub onsen_id: u32,
+    pub overall: u8,
+    pub water_quality: u8,
+    pub cleanliness: u8,
+    pub view: u8,
+    /// 5が空いている
+    pub crowding: u8,
+    pub comment: String,
+    pub status: ReviewStatus,
+}
+
+impl OnsenReviewEntity {
+    /// 評価が1から5でなければNone
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u32,
+        user_id: u32,
+        onsen_id: u32,
+        overall: u8,
+        water_quality: u8,
+        cleanliness: u8,
+        view: u8,
+        crowding: u8,
+        comment: &str,
+        status: ReviewStatus,
+    ) -> Option<Self> {
+        if ![overall, water_quality, cleanliness, view, crowding]
+            .into_iter()
+            .all(is_score)
+            || !is_valid_comment(comment)
+        {
+            return None;
+        }
+        Some(Self {
+            id,
+            user_id,
+            onsen_id,
+            overall,
+            water_quality,
+            cleanliness,
+            view,
+            crowding,
+            comment: comment.to_string(),
+            status,
+        })
+    }
+}
+
+/// 宿の評価と口コミ。1人1件
+#[derive(Clone, Debug)]
+pub struct HotelReviewEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub hotel_id: u32,
+    pub room: u8,
+    pub food: u8,
+    pub bath: u8,
+    pub comment: String,
+    pub status: ReviewStatus,
+}
+
+impl HotelReviewEntity {
+    /// 評価が1から5でなければNone
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u32,
+        user_id: u32,
+        hotel_id: u32,
+        room: u8,
+        food: u8,
+        bath: u8,
+        comment: &str,
+        status: ReviewStatus,
+    ) -> Option<Self> {
+        if ![room, food, bath].into_iter().all(is_score) || !is_valid_comment(comment) {
+            return None;
+        }
+        Some(Self {
+            id,
+            user_id,
+            hotel_id,
+            room,
+            food,
+            bath,
+            comment: comment.to_string(),
+            status,
+        })
+    }
+}
+
+/// 温泉の評価の件数と平均。却下された口コミは集計しない
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OnsenRatingSummary {
+    pub count: u32,
+    pub overall: Option<f64>,
+    pub water_quality: Option<f64>,
+    pub cleanliness: Option<f64>,
+    pub view: Option<f64>,
+    pub crowding: Option<f64>,
+}
+
+impl OnsenRatingSummary {
+    pub fn aggregate(reviews: &[OnsenReviewEntity]) -> Self {
+        Self {
+            count: reviews.len() as u32,
+            overall: average(reviews.iter().map(|v| v.overall)),
+            water_quality: average(reviews.iter().map(|v| v.water_quality)),
+            cleanliness: average(reviews.iter().map(|v| v.cleanliness)),
+            view: average(reviews.iter().map(|v| v.view)),
+            crowding: average(reviews.iter().map(|v| v.crowding)),
+        }
+    }
+}
+
+/// 宿の評価の件数と平均
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HotelRatingSummary {
+    pub count: u32,
+    pub room: Option<f64>,
+    pub food: Option<f64>,
+    pub bath: Option<f64>,
+}
+
+impl HotelRatingSummary {
+    pub fn aggregate(reviews: &[HotelReviewEntity]) -> Self {
+        Self {
+            count: reviews.len() as u32,
+            room: average(reviews.iter().map(|v| v.room)),
+            food: average(reviews.iter().map(|v| v.food)),
+            bath: average(reviews.iter().map(|v| v.bath)),
+        }
+    }
+}
+
+/// 小数第2位で丸めた平均。評価がなければNone
+fn average(scores: impl Iterator<Item = u8>) -> Option<f64> {
+    let (sum, count) = scores.fold((0u32, 0u32), |(sum, count), v| (sum + v as u32, count + 1));
+    if count == 0 {
+        return None;
+    }
+    Some((sum as f64 / count as f64 * 100.0).round() / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::review_entity::*;
+
+    fn onsen_review(overall: u8, view: u8) -> OnsenReviewEntity {
+        OnsenReviewEntity::new(0, 1, 2, overall, 3, 3, view, 3, "", ReviewStatus::Approved).unwrap()
+    }
+
+    #[test]
+    fn new_test_return_none_when_score_is_out_of_range() {
+        assert!(
+            OnsenReviewEntity::new(0, 1, 2, 0, 3, 3, 3, 3, "", ReviewStatus::Approved).is_none()
+        );
+        assert!(HotelReviewEntity::new(0, 1, 2, 3, 6, 3, "", ReviewStatus::Approved).is_none());
+    }
+
+    #[test]
+    fn new_test_return_none_when_comment_is_too_long() {
+        let comment = "湯".repeat(2000);
+        assert!(
+            OnsenReviewEntity::new(0, 1, 2, 3, 3, 3, 3, 3, &comment, ReviewStatus::Pending)
+                .is_some()
+        );
+        let comment = "湯".repeat(2001);
+        assert!(
+            OnsenReviewEntity::new(0, 1, 2, 3, 3, 3, 3, 3, &comment, ReviewStatus::Pending)
+                .is_none()
+        );
+        assert!(
+            HotelReviewEntity::new(0, 1, 2, 3, 3, 3, &comment, ReviewStatus::Pending).is_none()
+        );
+    }
+
+    #[test]
+    fn test_review_status_for_comment() {
+        assert_eq!(ReviewStatus::for_comment(" "), ReviewStatus::Approved);
+        assert_eq!(ReviewStatus::for_comment("最高"), ReviewStatus::Pending);
+    }
+
+    #[test]
+    fn test_onsen_rating_summary() {
+        let summary = OnsenRatingSummary::aggregate(&[
+            onsen_review(5, 1),
+            onsen_review(4, 2),
+            onsen_review(4, 2),
+        ]);
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.overall, Some(4.33));
+        assert_eq!(summary.view, Some(1.67));
+        assert_eq!(OnsenRatingSummary::aggregate(&[]).overall, None);
+    }
+}
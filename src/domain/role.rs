@@ -0,0 +1,95 @@
+use strum_macros::{Display, EnumString};
+
+#[derive(Display, Debug, PartialEq, Eq, EnumString, Clone, Copy)]
+pub enum Role {
+    #[strum(serialize = "viewer")]
+    Viewer,
+    #[strum(serialize = "editor")]
+    Editor,
+    #[strum(serialize = "admin")]
+    Admin,
+}
+
+#[derive(Display, Debug, PartialEq, Eq, EnumString, Clone, Copy)]
+pub enum Permission {
+    #[strum(serialize = "onsen:write")]
+    OnsenWrite,
+    #[strum(serialize = "hotel:write")]
+    HotelWrite,
+    #[strum(serialize = "area:write")]
+    AreaWrite,
+    #[strum(serialize = "user:manage")]
+    UserManage,
+    #[strum(serialize = "review:moderate")]
+    ReviewModerate,
+    #[strum(serialize = "achievement:manage")]
+    AchievementManage,
+    #[strum(serialize = "comment:moderate")]
+    CommentModerate,
+    #[strum(serialize = "metrics:read")]
+    MetricsRead,
+}
+
+impl Role {
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Viewer => &[],
+            Role::Editor => &[
+                Permission::OnsenWrite,
+                Permission::HotelWrite,
+                Permission::AreaWrite,
+            ],
+            Role::Admin => &[
+                Permission::OnsenWrite,
+                Permission::HotelWrite,
+                Permission::AreaWrite,
+                Permission::UserManage,
+                Permission::ReviewModerate,
+                Permission::AchievementManage,
+                Permission::CommentModerate,
+                Permission::MetricsRead,
+            ],
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::role::{Permission, Role};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_role_from_str() {
+        assert_eq!(Role::from_str("viewer"), Ok(Role::Viewer));
+        assert_eq!(Role::from_str("editor"), Ok(Role::Editor));
+        assert_eq!(Role::from_str("admin"), Ok(Role::Admin));
+        assert!(Role::from_str("user").is_err());
+        assert_eq!(Role::Editor.to_string(), "editor");
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(!Role::Viewer.has_permission(Permission::OnsenWrite));
+        assert!(Role::Editor.has_permission(Permission::OnsenWrite));
+        assert!(Role::Editor.has_permission(Permission::AreaWrite));
+        assert!(!Role::Editor.has_permission(Permission::UserManage));
+        assert!(Role::Admin.has_permission(Permission::UserManage));
+        assert!(!Role::Editor.has_permission(Permission::ReviewModerate));
+        assert!(Role::Admin.has_permission(Permission::ReviewModerate));
+        assert!(!Role::Editor.has_permission(Permission::AchievementManage));
+        assert!(Role::Admin.has_permission(Permission::AchievementManage));
+        assert!(!Role::Editor.has_permission(Permission::CommentModerate));
+        assert!(Role::Admin.has_permission(Permission::CommentModerate));
+        assert!(!Role::Editor.has_permission(Permission::MetricsRead));
+        assert!(Role::Admin.has_permission(Permission::MetricsRead));
+        assert_eq!(Permission::UserManage.to_string(), "user:manage");
+        assert_eq!(
+            Permission::from_str("onsen:write"),
+            Ok(Permission::OnsenWrite)
+        );
+    }
+}
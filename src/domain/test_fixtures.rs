@@ -0,0 +1,43 @@
+//! ドメインのテストで使うエンティティ
+use crate::domain::area_entity::AreaEntity;
+use crate::domain::onsen::onsen_entity::OnsenEntity;
+use crate::domain::onsen::onsen_quality::OnsenQuality;
+use crate::domain::visit_entity::VisitEntity;
+use chrono::NaiveDate;
+
+/// 泉質・pH・温泉地のほかは空の温泉
+pub fn onsen(
+    id: u32,
+    quality: Option<OnsenQuality>,
+    ph: Option<f64>,
+    area_id: Option<u32>,
+) -> OnsenEntity {
+    OnsenEntity::new(
+        id, "湯", quality, "", None, ph, None, None, "uchiyu", true, "", None, "", area_id,
+    )
+    .unwrap()
+}
+
+pub fn area(id: u32, prefecture: &str, national_resort: bool) -> AreaEntity {
+    AreaEntity::new(
+        id,
+        "温泉地",
+        "",
+        prefecture,
+        national_resort,
+        None,
+        "",
+        "",
+        "",
+        vec![],
+    )
+    .unwrap()
+}
+
+/// 公開している入浴記録
+pub fn visit(id: u32, user_id: u32, onsen_id: u32, visited_on: NaiveDate) -> VisitEntity {
+    VisitEntity::new(
+        id, user_id, onsen_id, visited_on, None, None, None, None, "", true,
+    )
+    .unwrap()
+}
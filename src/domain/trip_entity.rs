@@ -0,0 +1,129 @@
+use chrono::{Duration, NaiveDate};
+
+/// 旅行の1日。並び順は`TripEntity::days`の順
+#[derive(Clone, Debug, PartialEq)]
+pub struct TripDayEntity {
+    /// 泊まった宿
+    pub hotel_id: Option<u32>,
+    /// その日の入浴記録
+    pub visit_ids: Vec<u32>,
+    /// 通った温泉地
+    pub area_ids: Vec<u32>,
+    pub note: String,
+}
+
+/// 旅の記録
+#[derive(Clone)]
+pub struct TripEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub title: String,
+    pub start_on: NaiveDate,
+    pub end_on: NaiveDate,
+    pub note: String,
+    pub is_public: bool,
+    pub days: Vec<TripDayEntity>,
+}
+
+impl TripEntity {
+    /// タイトルが空、期間が逆転している、期間より日数が多い場合はNone
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u32,
+        user_id: u32,
+        title: &str,
+        start_on: NaiveDate,
+        end_on: NaiveDate,
+        note: &str,
+        is_public: bool,
+        days: Vec<TripDayEntity>,
+    ) -> Option<Self> {
+        if title.is_empty() {
+            return None;
+        }
+        if start_on > end_on {
+            return None;
+        }
+        let trip = Self {
+            id,
+            user_id,
+            title: title.to_string(),
+            start_on,
+            end_on,
+            note: note.to_string(),
+            is_public,
+            days,
+        };
+        if trip.days.len() > trip.day_count() {
+            return None;
+        }
+        Some(trip)
+    }
+
+    /// 期間の日数。日帰りなら1
+    pub fn day_count(&self) -> usize {
+        ((self.end_on - self.start_on).num_days() + 1) as usize
+    }
+
+    /// `days[index]`の日付
+    pub fn date_of(&self, index: usize) -> NaiveDate {
+        self.start_on + Duration::days(index as i64)
+    }
+
+    pub fn visit_ids(&self) -> Vec<u32> {
+        self.days.iter().flat_map(|v| v.visit_ids.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::trip_entity::*;
+
+    fn day(visit_ids: &[u32]) -> TripDayEntity {
+        TripDayEntity {
+            hotel_id: None,
+            visit_ids: visit_ids.to_vec(),
+            area_ids: vec![],
+            note: "".to_string(),
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    #[test]
+    fn new_test() {
+        let trip = TripEntity::new(
+            1,
+            2,
+            "草津・四万",
+            date(2),
+            date(3),
+            "",
+            true,
+            vec![day(&[1, 2]), day(&[3])],
+        )
+        .unwrap();
+        assert_eq!(trip.day_count(), 2);
+        assert_eq!(trip.date_of(1), date(3));
+        assert_eq!(trip.visit_ids(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn new_test_return_none_when_invalid() {
+        assert!(TripEntity::new(1, 2, "", date(2), date(3), "", true, vec![]).is_none());
+        assert!(TripEntity::new(1, 2, "旅", date(3), date(2), "", true, vec![]).is_none());
+        assert!(TripEntity::new(
+            1,
+            2,
+            "日帰り",
+            date(2),
+            date(2),
+            "",
+            true,
+            vec![day(&[]), day(&[])]
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::NaiveDate;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+/// 入浴した時間帯
+#[derive(Display, Debug, PartialEq, EnumString, Clone)]
+pub enum TimeOfDay {
+    #[strum(serialize = "morning")]
+    Morning, // 朝
+    #[strum(serialize = "daytime")]
+    Daytime, // 昼
+    #[strum(serialize = "evening")]
+    Evening, // 夕方
+    #[strum(serialize = "night")]
+    Night, // 夜
+}
+
+/// 天気
+#[derive(Display, Debug, PartialEq, EnumString, Clone)]
+pub enum Weather {
+    #[strum(serialize = "sunny")]
+    Sunny, // 晴れ
+    #[strum(serialize = "cloudy")]
+    Cloudy, // 曇り
+    #[strum(serialize = "rainy")]
+    Rainy, // 雨
+    #[strum(serialize = "snowy")]
+    Snowy, // 雪
+}
+
+/// 混雑具合
+#[derive(Display, Debug, PartialEq, EnumString, Clone)]
+pub enum CrowdLevel {
+    #[strum(serialize = "empty")]
+    Empty, // 貸切状態
+    #[strum(serialize = "few")]
+    Few, // 空いている
+    #[strum(serialize = "moderate")]
+    Moderate, // 普通
+    #[strum(serialize = "crowded")]
+    Crowded, // 混んでいる
+}
+
+/// 体感の湯温
+#[derive(Display, Debug, PartialEq, EnumString, Clone)]
+pub enum FeltTemperature {
+    #[strum(serialize = "lukewarm")]
+    Lukewarm, // ぬるい
+    #[strum(serialize = "comfortable")]
+    Comfortable, // ちょうどいい
+    #[strum(serialize = "hot")]
+    Hot, // 熱い
+    #[strum(serialize = "very_hot")]
+    VeryHot, // とても熱い
+}
+
+/// ユーザーが温泉に入った記録
+#[derive(Clone)]
+pub struct VisitEntity {
+    pub id: u32,
+    pub user_id: u32,
+    pub onsen_id: u32,
+    pub visited_on: NaiveDate,
+    pub time_of_day: Option<TimeOfDay>,
+    pub weather: Option<Weather>,
+    pub crowd_level: Option<CrowdLevel>,
+    pub water_temperature: Option<FeltTemperature>,
+    pub note: String,
+    pub is_public: bool,
+}
+
+impl VisitEntity {
+    /// 選択肢にない値が指定されたらNone
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u32,
+        user_id: u32,
+        onsen_id: u32,
+        visited_on: NaiveDate,
+        time_of_day: Option<&str>,
+        weather: Option<&str>,
+        crowd_level: Option<&str>,
+        water_temperature: Option<&str>,
+        note: &str,
+        is_public: bool,
+    ) -> Option<Self> {
+        Some(Self {
+            id,
+            user_id,
+            onsen_id,
+            visited_on,
+            time_of_day: parse_option(time_of_day)?,
+            weather: parse_option(weather)?,
+            crowd_level: parse_option(crowd_level)?,
+            water_temperature: parse_option(water_temperature)?,
+            note: note.to_string(),
+            is_public,
+        })
+    }
+}
+
+fn parse_option<T: FromStr>(value: Option<&str>) -> Option<Option<T>> {
+    match value {
+        Some(value) => T::from_str(value).ok().map(Some),
+        None => Some(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::visit_entity::*;
+
+    #[test]
+    fn new_test() {
+        let visit = VisitEntity::new(
+            1,
+            2,
+            3,
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            Some("morning"),
+            Some("snowy"),
+            None,
+            Some("very_hot"),
+            "雪見風呂",
+            true,
+        )
+        .unwrap();
+        assert_eq!(visit.time_of_day, Some(TimeOfDay::Morning));
+        assert_eq!(visit.weather, Some(Weather::Snowy));
+        assert_eq!(visit.crowd_level, None);
+        assert_eq!(visit.water_temperature, Some(FeltTemperature::VeryHot));
+        assert_eq!(visit.note, "雪見風呂");
+    }
+
+    #[test]
+    fn new_test_return_none_when_value_is_unknown() {
+        let visit = VisitEntity::new(
+            1,
+            2,
+            3,
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            None,
+            Some("foggy"),
+            None,
+            None,
+            "",
+            false,
+        );
+        assert!(visit.is_none());
+    }
+}
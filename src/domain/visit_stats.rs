@@ -0,0 +1,134 @@
+use crate::domain::{
+    area_entity::AreaEntity, onsen::onsen_entity::OnsenEntity,
+    onsen::spring_category::SpringCategory, visit_entity::VisitEntity,
+};
+use chrono::Datelike;
+use std::collections::BTreeMap;
+
+/// 都道府県の数
+pub const PREFECTURE_COUNT: usize = 47;
+
+/// 入浴記録から集計した統計。並び順は初めて訪れた順
+#[derive(Debug, PartialEq)]
+pub struct VisitStats {
+    pub visit_count: usize,
+    pub onsen_ids: Vec<u32>,
+    pub area_ids: Vec<u32>,
+    pub prefectures: Vec<String>,
+    /// `SpringCategory::ALL`の順
+    pub spring_categories: Vec<SpringCategory>,
+    /// 国民保養温泉地
+    pub national_resort_ids: Vec<u32>,
+    pub visits_per_year: BTreeMap<i32, usize>,
+}
+
+impl VisitStats {
+    /// `onsens`・`areas`に含まれない温泉・温泉地は入浴回数にだけ数える
+    pub fn compute(visits: &[VisitEntity], onsens: &[OnsenEntity], areas: &[AreaEntity]) -> Self {
+        let mut visits: Vec<&VisitEntity> = visits.iter().collect();
+        visits.sort_by_key(|v| (v.visited_on, v.id));
+        let mut stats = Self {
+            visit_count: visits.len(),
+            onsen_ids: vec![],
+            area_ids: vec![],
+            prefectures: vec![],
+            spring_categories: vec![],
+            national_resort_ids: vec![],
+            visits_per_year: BTreeMap::new(),
+        };
+        let mut categories: Vec<SpringCategory> = vec![];
+        for visit in visits {
+            *stats
+                .visits_per_year
+                .entry(visit.visited_on.year())
+                .or_default() += 1;
+            let Some(onsen) = onsens.iter().find(|v| v.id == visit.onsen_id) else {
+                continue;
+            };
+            push_unique(&mut stats.onsen_ids, onsen.id);
+            if let Some(quality) = &onsen.quality {
+                for category in quality.categories() {
+                    push_unique(&mut categories, category);
+                }
+            }
+            let Some(area) = onsen
+                .area_id
+                .and_then(|area_id| areas.iter().find(|v| v.id == area_id))
+            else {
+                continue;
+            };
+            push_unique(&mut stats.area_ids, area.id);
+            push_unique(&mut stats.prefectures, area.prefecture.clone());
+            if area.national_resort {
+                push_unique(&mut stats.national_resort_ids, area.id);
+            }
+        }
+        stats.spring_categories = SpringCategory::ALL
+            .into_iter()
+            .filter(|v| categories.contains(v))
+            .collect();
+        stats
+    }
+}
+
+fn push_unique<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::onsen::chemical::Chemical::*;
+    use crate::domain::onsen::onsen_quality::OnsenQuality;
+    use crate::domain::onsen::spring_category::SpringCategory;
+    use crate::domain::test_fixtures::{area, onsen, visit};
+    use crate::domain::visit_stats::*;
+    use chrono::NaiveDate;
+
+    fn visited_in(year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, 1, 2).unwrap()
+    }
+
+    #[test]
+    fn compute_test() {
+        let onsens = vec![
+            onsen(
+                1,
+                Some(OnsenQuality::new(&[NaIon, SO4Ion], None)),
+                None,
+                Some(1),
+            ),
+            onsen(2, Some(OnsenQuality::new(&[S], None)), None, Some(2)),
+            onsen(3, None, None, Some(3)),
+            onsen(4, None, None, None),
+        ];
+        let areas = vec![
+            area(1, "群馬県", true),
+            area(2, "群馬県", false),
+            area(3, "栃木県", true),
+        ];
+        let visits = vec![
+            visit(1, 1, 2, visited_in(2024)),
+            visit(2, 1, 1, visited_in(2023)),
+            visit(3, 1, 1, visited_in(2024)),
+            visit(4, 1, 3, visited_in(2024)),
+            visit(5, 1, 4, visited_in(2024)),
+            visit(6, 1, 99, visited_in(2022)),
+        ];
+        let stats = VisitStats::compute(&visits, &onsens, &areas);
+        assert_eq!(stats.visit_count, 6);
+        assert_eq!(stats.onsen_ids, vec![1, 2, 3, 4]);
+        assert_eq!(stats.area_ids, vec![1, 2, 3]);
+        assert_eq!(stats.prefectures, vec!["群馬県", "栃木県"]);
+        assert_eq!(
+            stats.spring_categories,
+            vec![SpringCategory::Sulfate, SpringCategory::Sulfur]
+        );
+        assert_eq!(stats.national_resort_ids, vec![1, 3]);
+        assert_eq!(
+            stats.visits_per_year.into_iter().collect::<Vec<_>>(),
+            vec![(2022, 1), (2023, 1), (2024, 4)]
+        );
+    }
+}
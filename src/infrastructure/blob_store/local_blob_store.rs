@@ -0,0 +1,72 @@
+use super::{is_valid_key, BlobStore, BlobStoreError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// ローカルのディレクトリにキーのパスで保存する
+pub struct LocalBlobStore {
+    dir: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(dir: PathBuf) -> Self {
+        LocalBlobStore { dir }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        if !is_valid_key(key) {
+            return Err(BlobStoreError(format!("invalid key: {}", key)));
+        }
+        Ok(self.dir.join(key))
+    }
+}
+
+impl BlobStore for LocalBlobStore {
+    fn put(&self, key: &str, _content_type: &str, bytes: &[u8]) -> Result<(), BlobStoreError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| BlobStoreError(e.to_string()))?;
+        }
+        // 書きかけのファイルを読まれないように一時ファイルから置き換える
+        let temp_path = path.with_extension(format!("tmp{}", rand::random::<u32>()));
+        fs::write(&temp_path, bytes).map_err(|e| BlobStoreError(e.to_string()))?;
+        fs::rename(&temp_path, &path).map_err(|e| BlobStoreError(e.to_string()))
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, BlobStoreError> {
+        match fs::read(self.path(key)?) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(BlobStoreError(e.to_string())),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        match fs::remove_file(self.path(key)?) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(BlobStoreError(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::blob_store::local_blob_store::LocalBlobStore;
+    use crate::infrastructure::blob_store::BlobStore;
+    use std::fs;
+
+    #[test]
+    fn test_put_get_delete() {
+        let dir = std::env::temp_dir().join(format!("onsen_tabi_blob_{}", rand::random::<u32>()));
+        let store = LocalBlobStore::new(dir.clone());
+        assert!(store.put("photos/a.jpg", "image/jpeg", b"abc").is_ok());
+        assert_eq!(store.get("photos/a.jpg").unwrap(), Some(b"abc".to_vec()));
+        assert_eq!(store.get("photos/b.jpg").unwrap(), None);
+        assert!(store.get("../a.jpg").is_err());
+        assert!(store.delete("photos/a.jpg").is_ok());
+        assert_eq!(store.get("photos/a.jpg").unwrap(), None);
+        assert!(store.delete("photos/a.jpg").is_ok());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+pub mod local_blob_store;
+#[cfg(feature = "s3")]
+pub mod s3_blob_store;
+
+use local_blob_store::LocalBlobStore;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct BlobStoreError(pub String);
+
+impl fmt::Display for BlobStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 画像などのバイナリの置き場所。キーは`new_blob_key`で作る
+pub trait BlobStore: Send + Sync {
+    fn put(&self, key: &str, content_type: &str, bytes: &[u8]) -> Result<(), BlobStoreError>;
+    /// キーがなければ`Ok(None)`
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, BlobStoreError>;
+    fn delete(&self, key: &str) -> Result<(), BlobStoreError>;
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Local,
+    S3,
+}
+
+/// Rocket.tomlの`[storage]`
+#[derive(Debug, Deserialize, Clone)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    /// `local`のときに保存するディレクトリ
+    pub dir: Option<String>,
+    /// `s3`のときの接続先。認証情報は環境変数`S3_ACCESS_KEY_ID`と`S3_SECRET_ACCESS_KEY`
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: Option<String>,
+}
+
+pub fn create_blob_store(config: &StorageConfig) -> Result<Box<dyn BlobStore>, BlobStoreError> {
+    match config.backend {
+        StorageBackend::Local => {
+            let dir = config
+                .dir
+                .as_ref()
+                .ok_or(BlobStoreError("storage.dir must be set".to_string()))?;
+            Ok(Box::new(LocalBlobStore::new(dir.into())))
+        }
+        #[cfg(feature = "s3")]
+        StorageBackend::S3 => Ok(Box::new(s3_blob_store::S3BlobStore::from_config(config)?)),
+        #[cfg(not(feature = "s3"))]
+        StorageBackend::S3 => Err(BlobStoreError(
+            "storage.backend = \"s3\" requires the s3 feature".to_string(),
+        )),
+    }
+}
+
+/// 推測されにくいランダムなキー。`photos/`のような接頭辞と拡張子をつける
+pub fn new_blob_key(prefix: &str, extension: &str) -> String {
+    let name: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    format!("{}/{}.{}", prefix, name, extension)
+}
+
+/// キーに`..`や絶対パスが入っていないか
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::blob_store::*;
+
+    #[test]
+    fn test_new_blob_key() {
+        let key = new_blob_key("photos", "jpg");
+        assert!(key.starts_with("photos/"));
+        assert!(key.ends_with(".jpg"));
+        assert_eq!(key.len(), "photos/".len() + 32 + ".jpg".len());
+        assert!(is_valid_key(&key));
+        assert_ne!(key, new_blob_key("photos", "jpg"));
+    }
+
+    #[test]
+    fn test_is_valid_key() {
+        assert!(is_valid_key("photos/abc.webp"));
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("/etc/passwd"));
+        assert!(!is_valid_key("photos/../../etc/passwd"));
+        assert!(!is_valid_key("photos//a.jpg"));
+        assert!(!is_valid_key("photos/a b.jpg"));
+    }
+}
@@ -0,0 +1,73 @@
+use super::{Mail, MailError, Mailer};
+use chrono::Utc;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// メールサーバーなしで確認できるように、メールをファイルか標準出力に書き出す
+pub struct FileMailer {
+    from: String,
+    dir: Option<PathBuf>,
+}
+
+impl FileMailer {
+    pub fn new(from: &str, dir: Option<PathBuf>) -> Self {
+        FileMailer {
+            from: from.to_string(),
+            dir,
+        }
+    }
+
+    fn format(&self, mail: &Mail) -> String {
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\n\r\n{}\r\n",
+            self.from,
+            mail.to,
+            mail.subject,
+            Utc::now().to_rfc2822(),
+            mail.body
+        )
+    }
+}
+
+impl Mailer for FileMailer {
+    fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = self.format(mail);
+        let Some(dir) = &self.dir else {
+            let mut stdout = std::io::stdout().lock();
+            return writeln!(stdout, "{}", message).map_err(|e| MailError(e.to_string()));
+        };
+        fs::create_dir_all(dir).map_err(|e| MailError(e.to_string()))?;
+        let file_name = format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%d%H%M%S%f"),
+            rand::random::<u32>()
+        );
+        fs::write(dir.join(file_name), message).map_err(|e| MailError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::mailer::file_mailer::FileMailer;
+    use crate::infrastructure::mailer::{Mail, Mailer};
+    use std::fs;
+
+    #[test]
+    fn test_send_to_file() {
+        let dir = std::env::temp_dir().join(format!("onsen_tabi_mail_{}", rand::random::<u32>()));
+        let mailer = FileMailer::new("no-reply@example.com", Some(dir.clone()));
+        let mail = Mail {
+            to: "a@example.com".to_string(),
+            subject: "件名".to_string(),
+            body: "本文".to_string(),
+        };
+        assert!(mailer.send(&mail).is_ok());
+        let files: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let content = fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+        assert!(content.contains("To: a@example.com\r\n"));
+        assert!(content.ends_with("\r\n\r\n本文\r\n"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
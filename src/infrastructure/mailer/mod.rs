@@ -0,0 +1,74 @@
+pub mod file_mailer;
+pub mod smtp_mailer;
+
+use file_mailer::FileMailer;
+use serde::Deserialize;
+use smtp_mailer::SmtpMailer;
+use std::env;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub struct MailError(pub String);
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub trait Mailer: Send + Sync {
+    fn send(&self, mail: &Mail) -> Result<(), MailError>;
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MailTransport {
+    Stdout,
+    File,
+    Smtp,
+}
+
+/// Rocket.tomlの`[mail]`
+#[derive(Debug, Deserialize, Clone)]
+pub struct MailConfig {
+    pub transport: MailTransport,
+    pub from: String,
+    /// `file`のときにメールを書き出すディレクトリ
+    pub dir: Option<String>,
+    /// `smtp`のときの接続先。設定がなければ環境変数`SMTP_URL`を使う
+    pub smtp_url: Option<String>,
+    /// パスワード再設定画面のURL。`?token=`をつけてメールに載せる
+    pub password_reset_url: String,
+    /// メールアドレス確認のURL。`GET /verify`のURLを設定する
+    pub email_verification_url: String,
+}
+
+pub fn create_mailer(config: &MailConfig) -> Result<Box<dyn Mailer>, MailError> {
+    match config.transport {
+        MailTransport::Stdout => Ok(Box::new(FileMailer::new(&config.from, None))),
+        MailTransport::File => {
+            let dir = config
+                .dir
+                .as_ref()
+                .ok_or(MailError("mail.dir must be set".to_string()))?;
+            Ok(Box::new(FileMailer::new(&config.from, Some(dir.into()))))
+        }
+        MailTransport::Smtp => {
+            let smtp_url = config
+                .smtp_url
+                .clone()
+                .or_else(|| env::var("SMTP_URL").ok())
+                .ok_or(MailError(
+                    "mail.smtp_url or SMTP_URL must be set".to_string(),
+                ))?;
+            Ok(Box::new(SmtpMailer::new(&config.from, &smtp_url)?))
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramTimer, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    Opts, Registry, TextEncoder,
+};
+
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+fn register<T: prometheus::core::Collector + Clone + 'static>(collector: T) -> T {
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("Metric is registered twice");
+    collector
+}
+
+/// ルート名ごとのリクエスト数
+pub static HTTP_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("http_requests_total", "Number of HTTP requests"),
+            &["route", "method", "status"],
+        )
+        .unwrap(),
+    )
+});
+
+/// ルート名ごとのレイテンシ
+pub static HTTP_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["route"],
+        )
+        .unwrap(),
+    )
+});
+
+/// リポジトリの関数ごとのDB処理時間
+pub static DB_QUERY_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "db_query_duration_seconds",
+                "Duration of repository functions in seconds",
+            )
+            .buckets(vec![
+                0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+            ]),
+            &["function"],
+        )
+        .unwrap(),
+    )
+});
+
+/// dropされるまでの時間をリポジトリの関数の処理時間として記録する
+pub fn db_timer(function: &str) -> HistogramTimer {
+    DB_QUERY_DURATION_SECONDS
+        .with_label_values(&[function])
+        .start_timer()
+}
+
+/// 使用中のDBコネクション数。コネクションプールは使わずに都度接続している
+pub static DB_CONNECTIONS_IN_USE: Lazy<IntGauge> = Lazy::new(|| {
+    register(
+        IntGauge::new(
+            "db_connections_in_use",
+            "Number of open database connections",
+        )
+        .unwrap(),
+    )
+});
+
+pub static DB_CONNECTION_ERRORS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register(
+        IntCounter::new(
+            "db_connection_errors_total",
+            "Number of failed attempts to connect to the database",
+        )
+        .unwrap(),
+    )
+});
+
+/// resultはsuccess, failure, rate_limited, locked, two_factor_required
+pub static SIGNIN_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("signin_total", "Number of signin attempts"),
+            &["result"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static JWT_VALIDATION_FAILURES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new(
+                "jwt_validation_failures_total",
+                "Number of requests rejected by JWT validation",
+            ),
+            &["reason"],
+        )
+        .unwrap(),
+    )
+});
+
+/// Prometheusのテキスト形式で出力する
+pub fn gather() -> String {
+    Lazy::force(&HTTP_REQUESTS_TOTAL);
+    Lazy::force(&HTTP_REQUEST_DURATION_SECONDS);
+    Lazy::force(&DB_QUERY_DURATION_SECONDS);
+    Lazy::force(&DB_CONNECTIONS_IN_USE);
+    Lazy::force(&DB_CONNECTION_ERRORS_TOTAL);
+    Lazy::force(&SIGNIN_TOTAL);
+    Lazy::force(&JWT_VALIDATION_FAILURES_TOTAL);
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("Failed to encode metrics");
+    String::from_utf8(buffer).expect("Metrics are not UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::metrics::*;
+
+    #[test]
+    fn test_gather() {
+        SIGNIN_TOTAL.with_label_values(&["success"]).inc();
+        DB_QUERY_DURATION_SECONDS
+            .with_label_values(&["get_onsens"])
+            .observe(0.003);
+        let text = gather();
+        assert!(text.contains("# TYPE signin_total counter"));
+        assert!(text.contains("signin_total{result=\"success\"}"));
+        assert!(
+            text.contains("db_query_duration_seconds_bucket{function=\"get_onsens\",le=\"0.005\"}")
+        );
+        assert!(text.contains("# TYPE db_connections_in_use gauge"));
+    }
+}
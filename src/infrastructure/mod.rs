@@ -1,2 +1,5 @@
-mod mysql;
+pub mod blob_store;
+pub mod mailer;
+pub mod metrics;
+pub mod rdb;
 pub mod repository;
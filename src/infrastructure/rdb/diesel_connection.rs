@@ -0,0 +1,169 @@
+use crate::infrastructure::metrics::{DB_CONNECTIONS_IN_USE, DB_CONNECTION_ERRORS_TOTAL};
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::sql_types::Integer;
+use diesel::{
+    Connection, ConnectionError, ConnectionResult, MultiConnection, MysqlConnection, PgConnection,
+    QueryResult, RunQueryDsl,
+};
+use dotenvy::dotenv;
+use std::env;
+
+/// DATABASE_URLのスキームで接続先のRDBを切り替える
+#[derive(MultiConnection)]
+pub enum DbConnection {
+    Mysql(MysqlConnection),
+    Postgresql(PgConnection),
+}
+
+/// DATABASE_URLから決まる接続先のRDB
+#[derive(Debug, PartialEq)]
+pub enum DatabaseBackend {
+    Mysql,
+    Postgresql,
+}
+
+impl DatabaseBackend {
+    /// postgres://とpostgresql://はPostgreSQL、それ以外はMySQL
+    pub fn from_url(database_url: &str) -> Self {
+        if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+            DatabaseBackend::Postgresql
+        } else {
+            DatabaseBackend::Mysql
+        }
+    }
+}
+
+pub fn establish_connection() -> DbConnection {
+    try_establish_connection().unwrap_or_else(|e| {
+        panic!(
+            "Error connecting to {}: {}",
+            env::var("DATABASE_URL").unwrap_or_default(),
+            e
+        )
+    })
+}
+
+pub fn try_establish_connection() -> ConnectionResult<DbConnection> {
+    dotenv().ok();
+
+    let connection = match env::var("DATABASE_URL") {
+        Ok(database_url) => match DatabaseBackend::from_url(&database_url) {
+            DatabaseBackend::Postgresql => {
+                PgConnection::establish(&database_url).map(DbConnection::Postgresql)
+            }
+            DatabaseBackend::Mysql => {
+                MysqlConnection::establish(&database_url).map(DbConnection::Mysql)
+            }
+        },
+        Err(_) => Err(ConnectionError::InvalidConnectionUrl(
+            "DATABASE_URL must be set".to_string(),
+        )),
+    };
+    match &connection {
+        Ok(_) => DB_CONNECTIONS_IN_USE.inc(),
+        Err(_) => DB_CONNECTION_ERRORS_TOTAL.inc(),
+    }
+    connection
+}
+
+impl Drop for DbConnection {
+    fn drop(&mut self) {
+        DB_CONNECTIONS_IN_USE.dec();
+    }
+}
+
+/// INSERT文に`RETURNING id`をつける
+#[derive(QueryId)]
+pub struct ReturningId<T>(pub T);
+
+impl<T> Query for ReturningId<T> {
+    type SqlType = Integer;
+}
+
+impl<T> RunQueryDsl<DbConnection> for ReturningId<T> {}
+
+impl<T> QueryFragment<MultiBackend> for ReturningId<T>
+where
+    T: QueryFragment<MultiBackend>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, MultiBackend>) -> QueryResult<()> {
+        self.0.walk_ast(out.reborrow())?;
+        out.push_sql(" RETURNING id");
+        Ok(())
+    }
+}
+
+/// INSERTして採番されたidを返す。
+/// MySQLはRETURNINGをサポートしていないのでLAST_INSERT_ID()で取得する
+macro_rules! insert_returning_id {
+    ($connection:expr, $table:expr, $values:expr) => {{
+        let connection: &mut $crate::infrastructure::rdb::diesel_connection::DbConnection =
+            $connection;
+        let statement = diesel::insert_into($table).values($values);
+        let sql = $crate::infrastructure::rdb::diesel_trace::debug_sql(connection, &statement);
+        tracing::debug!(sql, rows = 1, "insert returning id");
+        if let $crate::infrastructure::rdb::diesel_connection::DbConnection::Mysql(connection) =
+            connection
+        {
+            statement.execute(connection).expect("DB error");
+            diesel::sql_query("select LAST_INSERT_ID() as id")
+                .load::<$crate::infrastructure::rdb::diesel_model::Sequence>(connection)
+                .expect("get_id_error")
+                .first()
+                .unwrap()
+                .id as i32
+        } else {
+            $crate::infrastructure::rdb::diesel_connection::ReturningId(statement)
+                .get_result::<i32>(connection)
+                .expect("DB error")
+        }
+    }};
+}
+pub(crate) use insert_returning_id;
+
+/// INSERTして、一意制約に引っかかる行は挿入せずに無視する。挿入した行数を返す。
+/// MySQLはINSERT IGNORE、PostgreSQLはON CONFLICT DO NOTHING
+macro_rules! insert_or_ignore {
+    ($connection:expr, $table:expr, $values:expr) => {{
+        let connection: &mut $crate::infrastructure::rdb::diesel_connection::DbConnection =
+            $connection;
+        match connection {
+            $crate::infrastructure::rdb::diesel_connection::DbConnection::Mysql(connection) => {
+                $crate::infrastructure::rdb::diesel_trace::TracedRunQueryDsl::traced_execute_on(
+                    diesel::insert_or_ignore_into($table).values($values),
+                    connection,
+                )
+            }
+            $crate::infrastructure::rdb::diesel_connection::DbConnection::Postgresql(
+                connection,
+            ) => $crate::infrastructure::rdb::diesel_trace::TracedRunQueryDsl::traced_execute_on(
+                diesel::insert_into($table)
+                    .values($values)
+                    .on_conflict_do_nothing(),
+                connection,
+            ),
+        }
+    }};
+}
+pub(crate) use insert_or_ignore;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_backend_from_url() {
+        assert_eq!(
+            DatabaseBackend::from_url("postgres://app@localhost/onsen"),
+            DatabaseBackend::Postgresql
+        );
+        assert_eq!(
+            DatabaseBackend::from_url("postgresql://app@localhost/onsen"),
+            DatabaseBackend::Postgresql
+        );
+        assert_eq!(
+            DatabaseBackend::from_url("mysql://root@localhost/onsen"),
+            DatabaseBackend::Mysql
+        );
+    }
+}
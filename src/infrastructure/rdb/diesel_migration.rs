@@ -0,0 +1,16 @@
+use super::diesel_connection::DbConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+const MYSQL_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+const POSTGRES_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations_postgres");
+
+/// 未適用のマイグレーションがあるかどうか。確認できなかった場合はErr
+pub fn has_pending_migration(connection: &mut DbConnection) -> Result<bool, String> {
+    match connection {
+        DbConnection::Mysql(connection) => connection.has_pending_migration(MYSQL_MIGRATIONS),
+        DbConnection::Postgresql(connection) => {
+            connection.has_pending_migration(POSTGRES_MIGRATIONS)
+        }
+    }
+    .map_err(|e| e.to_string())
+}
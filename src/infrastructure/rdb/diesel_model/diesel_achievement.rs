@@ -0,0 +1,50 @@
+use crate::domain::achievement_entity::{
+    AchievementCondition, AchievementEntity, UserAchievementEntity,
+};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::achievement)]
+pub struct Achievement {
+    pub id: i32,
+    pub name: String,
+    pub description: String,
+    pub condition_kind: String,
+    pub condition_target: String,
+    pub condition_value: f64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Achievement> for AchievementEntity {
+    fn from(value: Achievement) -> Self {
+        let condition = AchievementCondition::new(
+            &value.condition_kind,
+            &value.condition_target,
+            value.condition_value,
+        )
+        .expect("Saved data violates AchievementCondition");
+        AchievementEntity::new(value.id as u32, &value.name, &value.description, condition)
+            .expect("Saved data violates AchievementEntity")
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::user_achievement)]
+pub struct UserAchievement {
+    pub id: i32,
+    pub user_id: i32,
+    pub achievement_id: i32,
+    pub achieved_at: NaiveDateTime,
+}
+
+impl From<UserAchievement> for UserAchievementEntity {
+    fn from(value: UserAchievement) -> Self {
+        UserAchievementEntity {
+            user_id: value.user_id as u32,
+            achievement_id: value.achievement_id as u32,
+            achieved_at: value.achieved_at,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::api_key)]
+pub struct ApiKey {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    /// パーミッションをスペース区切りで保存する
+    pub scopes: String,
+    pub mfa: bool,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
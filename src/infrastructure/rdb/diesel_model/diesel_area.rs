@@ -4,7 +4,7 @@ use diesel::{Identifiable, Insertable, Queryable, Selectable};
 #[derive(Queryable, Selectable, Identifiable, Insertable, Debug, Clone)]
 #[diesel(table_name=crate::schema::area)]
 pub struct Area {
-    pub id: u32,
+    pub id: i32,
     pub name: String,
     pub kana: String,
     pub prefecture: String,
@@ -18,7 +18,7 @@ pub struct Area {
 impl From<Area> for AreaEntity {
     fn from(value: Area) -> Self {
         AreaEntity::new(
-            value.id,
+            value.id as u32,
             &value.name,
             &value.kana,
             &value.prefecture,
@@ -36,7 +36,7 @@ impl From<Area> for AreaEntity {
 impl From<AreaEntity> for Area {
     fn from(value: AreaEntity) -> Self {
         Self {
-            id: value.id,
+            id: value.id as i32,
             name: value.name,
             kana: value.kana,
             prefecture: value.prefecture,
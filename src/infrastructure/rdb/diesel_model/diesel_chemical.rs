@@ -6,21 +6,21 @@ use diesel::{Identifiable, Insertable, Queryable, Selectable};
 #[derive(Queryable, Selectable, Identifiable, Insertable, Debug, Clone)]
 #[diesel(table_name=crate::schema::chemicals)]
 pub struct DieselChemical {
-    pub id: u32,
-    pub na_ion: u32,
-    pub ca_ion: u32,
-    pub mg_ion: u32,
-    pub cl_ion: u32,
-    pub hco3_ion: u32,
-    pub so4_ion: u32,
-    pub co2_ion: u32,
-    pub fe_ion: u32,
-    pub h_ion: u32,
-    pub i_ion: u32,
-    pub al_ion: u32,
-    pub cu_ion: u32,
-    pub s: u32,
-    pub rn: u32,
+    pub id: i32,
+    pub na_ion: i32,
+    pub ca_ion: i32,
+    pub mg_ion: i32,
+    pub cl_ion: i32,
+    pub hco3_ion: i32,
+    pub so4_ion: i32,
+    pub co2_ion: i32,
+    pub fe_ion: i32,
+    pub h_ion: i32,
+    pub i_ion: i32,
+    pub al_ion: i32,
+    pub cu_ion: i32,
+    pub s: i32,
+    pub rn: i32,
     pub strong_na_cl: bool,
     pub fe_type: String,
     pub weak_rn: bool,
@@ -45,7 +45,7 @@ impl DieselChemical {
         } else {
             RnType::Normal
         };
-        let mut chemicals: Vec<(Chemical, u32)> = vec![
+        let mut chemicals: Vec<(Chemical, i32)> = vec![
             (Chemical::NaIon, self.na_ion),
             (Chemical::CaIon, self.ca_ion),
             (Chemical::MgIon, self.mg_ion),
@@ -96,7 +96,7 @@ impl From<OnsenQuality> for DieselChemical {
             weak_rn: value.is_weak_rn(),
         };
         for (i, v) in value.cations.iter().enumerate() {
-            let index = i as u32;
+            let index = i as i32;
             match v {
                 Chemical::NaIon => self_.na_ion = index + 1,
                 Chemical::CaIon => self_.ca_ion = index + 1,
@@ -105,7 +105,7 @@ impl From<OnsenQuality> for DieselChemical {
             }
         }
         for (i, v) in value.anions.iter().enumerate() {
-            let index = i as u32;
+            let index = i as i32;
             match v {
                 Chemical::ClIon(_) => self_.cl_ion = index + 4,
                 Chemical::HCO3Ion => self_.hco3_ion = index + 4,
@@ -114,7 +114,7 @@ impl From<OnsenQuality> for DieselChemical {
             }
         }
         for (i, v) in value.inclusions.iter().enumerate() {
-            let index = i as u32;
+            let index = i as i32;
             match v {
                 Chemical::CO2 => self_.co2_ion = index + 7,
                 Chemical::FeIon(_) => self_.fe_ion = index + 7,
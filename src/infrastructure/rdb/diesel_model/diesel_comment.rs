@@ -0,0 +1,62 @@
+use crate::domain::comment_entity::{
+    CommentEntity, CommentReportEntity, CommentStatus, CommentTarget, ReportReason,
+};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::comment)]
+pub struct Comment {
+    pub id: i32,
+    pub target: String,
+    pub target_id: i32,
+    pub user_id: i32,
+    pub parent_id: Option<i32>,
+    pub body: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// 削除済みのコメントは本文が空なので`CommentEntity::new`を通さない
+impl From<Comment> for CommentEntity {
+    fn from(value: Comment) -> Self {
+        CommentEntity {
+            id: value.id as u32,
+            target: CommentTarget::from_str(&value.target)
+                .expect("Saved data violates CommentTarget"),
+            target_id: value.target_id as u32,
+            user_id: value.user_id as u32,
+            parent_id: value.parent_id.map(|v| v as u32),
+            body: value.body,
+            status: CommentStatus::from_str(&value.status)
+                .expect("Saved data violates CommentStatus"),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::comment_report)]
+pub struct CommentReport {
+    pub id: i32,
+    pub comment_id: i32,
+    pub user_id: i32,
+    pub reason: String,
+    pub note: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<CommentReport> for CommentReportEntity {
+    fn from(value: CommentReport) -> Self {
+        CommentReportEntity::new(
+            value.comment_id as u32,
+            value.user_id as u32,
+            ReportReason::from_str(&value.reason).expect("Saved data violates ReportReason"),
+            &value.note,
+        )
+        .expect("Saved data violates CommentReportEntity")
+    }
+}
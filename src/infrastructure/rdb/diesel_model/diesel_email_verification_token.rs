@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::email_verification_token)]
+pub struct EmailVerificationToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,25 @@
+use crate::domain::profile_entity::{FollowEntity, FollowStatus};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::follow)]
+pub struct Follow {
+    pub id: i32,
+    pub follower_id: i32,
+    pub followee_id: i32,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<Follow> for FollowEntity {
+    fn from(value: Follow) -> Self {
+        FollowEntity {
+            follower_id: value.follower_id as u32,
+            followee_id: value.followee_id as u32,
+            status: FollowStatus::from_str(&value.status)
+                .expect("Saved data violates FollowStatus"),
+        }
+    }
+}
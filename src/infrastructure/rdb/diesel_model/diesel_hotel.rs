@@ -4,7 +4,7 @@ use diesel::{Identifiable, Insertable, Queryable, Selectable};
 #[derive(Queryable, Selectable, Identifiable, Insertable, Debug, Clone)]
 #[diesel(table_name=crate::schema::hotel)]
 pub struct Hotel {
-    pub id: u32,
+    pub id: i32,
     pub name: String,
     pub has_washitsu: bool,
     pub solo_available: bool,
@@ -15,7 +15,7 @@ pub struct Hotel {
 impl From<Hotel> for HotelEntity {
     fn from(value: Hotel) -> Self {
         HotelEntity::new(
-            value.id,
+            value.id as u32,
             &value.name,
             value.has_washitsu,
             value.solo_available,
@@ -30,7 +30,7 @@ impl From<Hotel> for HotelEntity {
 impl From<HotelEntity> for Hotel {
     fn from(value: HotelEntity) -> Self {
         Self {
-            id: value.id,
+            id: value.id as i32,
             name: value.name,
             has_washitsu: value.has_washitsu,
             solo_available: value.solo_available,
@@ -0,0 +1,30 @@
+use crate::domain::notification_entity::{NotificationEntity, NotificationKind};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::notification)]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub actor_id: i32,
+    pub comment_id: i32,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<Notification> for NotificationEntity {
+    fn from(value: Notification) -> Self {
+        NotificationEntity {
+            id: value.id as u32,
+            user_id: value.user_id as u32,
+            kind: NotificationKind::from_str(&value.kind)
+                .expect("Saved data violates NotificationKind"),
+            actor_id: value.actor_id as u32,
+            comment_id: value.comment_id as u32,
+            is_read: value.read_at.is_some(),
+        }
+    }
+}
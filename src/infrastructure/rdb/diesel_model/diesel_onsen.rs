@@ -8,7 +8,7 @@ use diesel::{Associations, Identifiable, Insertable, Queryable, Selectable};
 #[diesel(belongs_to(Hotel))]
 #[diesel(table_name=crate::schema::onsen)]
 pub struct Onsen {
-    pub id: u32,
+    pub id: i32,
     pub name: String,
     pub spring_quality: String,
     pub liquid: Option<String>,
@@ -19,9 +19,10 @@ pub struct Onsen {
     pub url: String,
     pub img_url: Option<String>,
     pub description: String,
-    pub hotel_id: Option<u32>,
-    pub chemical_id: Option<u32>,
-    pub area_id: Option<u32>,
+    pub hotel_id: Option<i32>,
+    pub chemical_id: Option<i32>,
+    pub area_id: Option<i32>,
+    pub ph: Option<f64>,
 }
 
 impl OnsenEntity {
@@ -32,11 +33,12 @@ impl OnsenEntity {
             .and_then(|v| SpringLiquid::from_str(&v).ok());
         let onsen_quality = diesel_chemical.map(|v| v.create(liquid));
         OnsenEntity::new(
-            onsen.id,
+            onsen.id as u32,
             &onsen.name,
             onsen_quality,
             &onsen.spring_quality,
             onsen.liquid.as_deref(),
+            onsen.ph,
             onsen.osmotic_pressure.as_deref(),
             onsen.temperature.as_deref(),
             &onsen.category,
@@ -44,7 +46,7 @@ impl OnsenEntity {
             &onsen.url,
             onsen.img_url.as_deref(),
             &onsen.description,
-            onsen.area_id,
+            onsen.area_id.map(|v| v as u32),
         )
         .expect("Saved data violates OnsenEntity")
     }
@@ -53,7 +55,7 @@ impl OnsenEntity {
 impl From<OnsenEntity> for Onsen {
     fn from(value: OnsenEntity) -> Self {
         Self {
-            id: value.id,
+            id: value.id as i32,
             name: value.name,
             spring_quality: value.spring_quality,
             liquid: value.liquid.map(|v| v.to_string()),
@@ -66,7 +68,8 @@ impl From<OnsenEntity> for Onsen {
             description: value.description,
             hotel_id: None,
             chemical_id: None,
-            area_id: value.area_id,
+            area_id: value.area_id.map(|v| v as i32),
+            ph: value.ph,
         }
     }
 }
@@ -0,0 +1,41 @@
+use crate::domain::photo_entity::{ImageFormat, PhotoEntity, PhotoTarget, ThumbnailStatus};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::photo)]
+pub struct Photo {
+    pub id: i32,
+    pub target: String,
+    pub target_id: i32,
+    pub user_id: i32,
+    pub blob_key: String,
+    pub content_type: String,
+    pub byte_size: i32,
+    pub caption: String,
+    pub position: i32,
+    pub is_cover: bool,
+    pub created_at: NaiveDateTime,
+    pub thumbnail_status: String,
+}
+
+impl From<Photo> for PhotoEntity {
+    fn from(value: Photo) -> Self {
+        PhotoEntity {
+            id: value.id as u32,
+            target: PhotoTarget::from_str(&value.target).expect("Saved data violates PhotoTarget"),
+            target_id: value.target_id as u32,
+            user_id: value.user_id as u32,
+            blob_key: value.blob_key,
+            format: ImageFormat::from_str(&value.content_type)
+                .expect("Saved data violates ImageFormat"),
+            byte_size: value.byte_size as u32,
+            caption: value.caption,
+            position: value.position as u32,
+            is_cover: value.is_cover,
+            thumbnail_status: ThumbnailStatus::from_str(&value.thumbnail_status)
+                .expect("Saved data violates ThumbnailStatus"),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::domain::review_entity::{HotelReviewEntity, OnsenReviewEntity, ReviewStatus};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::onsen_review)]
+pub struct OnsenReview {
+    pub id: i32,
+    pub user_id: i32,
+    pub onsen_id: i32,
+    pub overall: i32,
+    pub water_quality: i32,
+    pub cleanliness: i32,
+    pub view: i32,
+    pub crowding: i32,
+    pub comment: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<OnsenReview> for OnsenReviewEntity {
+    fn from(value: OnsenReview) -> Self {
+        OnsenReviewEntity::new(
+            value.id as u32,
+            value.user_id as u32,
+            value.onsen_id as u32,
+            value.overall as u8,
+            value.water_quality as u8,
+            value.cleanliness as u8,
+            value.view as u8,
+            value.crowding as u8,
+            &value.comment,
+            ReviewStatus::from_str(&value.status).expect("Saved data violates ReviewStatus"),
+        )
+        .expect("Saved data violates OnsenReviewEntity")
+    }
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::hotel_review)]
+pub struct HotelReview {
+    pub id: i32,
+    pub user_id: i32,
+    pub hotel_id: i32,
+    pub room: i32,
+    pub food: i32,
+    pub bath: i32,
+    pub comment: String,
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<HotelReview> for HotelReviewEntity {
+    fn from(value: HotelReview) -> Self {
+        HotelReviewEntity::new(
+            value.id as u32,
+            value.user_id as u32,
+            value.hotel_id as u32,
+            value.room as u8,
+            value.food as u8,
+            value.bath as u8,
+            &value.comment,
+            ReviewStatus::from_str(&value.status).expect("Saved data violates ReviewStatus"),
+        )
+        .expect("Saved data violates HotelReviewEntity")
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::trip)]
+pub struct Trip {
+    pub id: i32,
+    pub user_id: i32,
+    pub title: String,
+    pub start_on: NaiveDate,
+    pub end_on: NaiveDate,
+    pub note: String,
+    pub is_public: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::trip_day)]
+pub struct TripDay {
+    pub id: i32,
+    pub trip_id: i32,
+    /// 1日目が1
+    pub day_number: i32,
+    pub hotel_id: Option<i32>,
+    pub note: String,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::trip_day_visit)]
+pub struct TripDayVisit {
+    pub id: i32,
+    pub trip_day_id: i32,
+    pub visit_id: i32,
+    pub position: i32,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::trip_day_area)]
+pub struct TripDayArea {
+    pub id: i32,
+    pub trip_day_id: i32,
+    pub area_id: i32,
+    pub position: i32,
+}
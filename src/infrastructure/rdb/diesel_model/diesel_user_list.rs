@@ -0,0 +1,25 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::user_list)]
+pub struct UserList {
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: String,
+    pub name: String,
+    pub share_token: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::user_list_item)]
+pub struct UserListItem {
+    pub id: i32,
+    pub user_list_id: i32,
+    /// `onsen`・`hotel`・`area`
+    pub target: String,
+    pub target_id: i32,
+    pub position: i32,
+}
@@ -0,0 +1,42 @@
+use crate::domain::profile_entity::{PrivacySettings, ProfileEntity, Visibility};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use std::str::FromStr;
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::user_profile)]
+pub struct UserProfile {
+    pub id: i32,
+    pub user_id: i32,
+    pub handle: String,
+    pub display_name: String,
+    pub bio: String,
+    pub profile_visibility: String,
+    pub visits_visibility: String,
+    pub trips_visibility: String,
+    pub reviews_visibility: String,
+    pub approve_followers: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<UserProfile> for ProfileEntity {
+    fn from(value: UserProfile) -> Self {
+        let visibility = |v: &str| Visibility::from_str(v).expect("Saved data violates Visibility");
+        let privacy = PrivacySettings {
+            profile: visibility(&value.profile_visibility),
+            visits: visibility(&value.visits_visibility),
+            trips: visibility(&value.trips_visibility),
+            reviews: visibility(&value.reviews_visibility),
+            approve_followers: value.approve_followers,
+        };
+        ProfileEntity::new(
+            value.user_id as u32,
+            &value.handle,
+            &value.display_name,
+            &value.bio,
+            privacy,
+        )
+        .expect("Saved data violates ProfileEntity")
+    }
+}
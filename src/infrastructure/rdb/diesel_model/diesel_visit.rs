@@ -0,0 +1,38 @@
+use crate::domain::visit_entity::VisitEntity;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{Identifiable, Queryable, Selectable};
+
+#[derive(Queryable, Selectable, Identifiable, Debug, Clone)]
+#[diesel(table_name=crate::schema::visit)]
+pub struct Visit {
+    pub id: i32,
+    pub user_id: i32,
+    pub onsen_id: i32,
+    pub visited_on: NaiveDate,
+    pub time_of_day: Option<String>,
+    pub weather: Option<String>,
+    pub crowd_level: Option<String>,
+    pub water_temperature: Option<String>,
+    pub note: String,
+    pub is_public: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Visit> for VisitEntity {
+    fn from(value: Visit) -> Self {
+        VisitEntity::new(
+            value.id as u32,
+            value.user_id as u32,
+            value.onsen_id as u32,
+            value.visited_on,
+            value.time_of_day.as_deref(),
+            value.weather.as_deref(),
+            value.crowd_level.as_deref(),
+            value.water_temperature.as_deref(),
+            &value.note,
+            value.is_public,
+        )
+        .expect("Saved data violates VisitEntity")
+    }
+}
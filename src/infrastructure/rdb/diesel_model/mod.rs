@@ -0,0 +1,28 @@
+pub mod diesel_achievement;
+pub mod diesel_api_key;
+pub mod diesel_area;
+pub mod diesel_chemical;
+pub mod diesel_comment;
+pub mod diesel_email_verification_token;
+pub mod diesel_follow;
+pub mod diesel_hotel;
+pub mod diesel_notification;
+pub mod diesel_onsen;
+pub mod diesel_password_reset_token;
+pub mod diesel_photo;
+pub mod diesel_refresh_token;
+pub mod diesel_review;
+pub mod diesel_trip;
+pub mod diesel_two_factor;
+pub mod diesel_user;
+pub mod diesel_user_list;
+pub mod diesel_user_profile;
+pub mod diesel_visit;
+
+use diesel::{sql_types::Bigint, QueryableByName};
+
+#[derive(QueryableByName)]
+pub struct Sequence {
+    #[diesel(sql_type = Bigint)]
+    pub id: i64,
+}
@@ -0,0 +1,131 @@
+use super::diesel_connection::{DbConnection, MultiBackend};
+use diesel::backend::Backend;
+use diesel::mysql::Mysql;
+use diesel::pg::Pg;
+use diesel::query_builder::{QueryBuilder, QueryFragment};
+use diesel::query_dsl::methods::{ExecuteDsl, LoadQuery};
+use diesel::{Connection, QueryResult, RunQueryDsl};
+
+type MultiQueryBuilder = <MultiBackend as Backend>::QueryBuilder;
+
+/// ログに出すSQL。パスワードなどが含まれるのでバインド値はプレースホルダのままにする
+pub fn debug_sql<T>(connection: &DbConnection, query: &T) -> String
+where
+    T: QueryFragment<MultiBackend>,
+{
+    match connection {
+        DbConnection::Mysql(_) => build_sql(query, MultiBackend::Mysql(Mysql)),
+        DbConnection::Postgresql(_) => build_sql(query, MultiBackend::Postgresql(Pg)),
+    }
+}
+
+fn build_sql<T>(query: &T, backend: MultiBackend) -> String
+where
+    T: QueryFragment<MultiBackend>,
+{
+    let mut query_builder = match backend {
+        MultiBackend::Mysql(_) => MultiQueryBuilder::Mysql(Default::default()),
+        MultiBackend::Postgresql(_) => MultiQueryBuilder::Postgresql(Default::default()),
+    };
+    match query.to_sql(&mut query_builder, &backend) {
+        Ok(()) => query_builder.finish(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// MySQLかPostgreSQLのどちらかでしか組み立てられないステートメントのSQL
+fn backend_sql<DB, T>(query: &T) -> String
+where
+    DB: Backend + Default,
+    DB::QueryBuilder: Default,
+    T: QueryFragment<DB>,
+{
+    let mut query_builder = DB::QueryBuilder::default();
+    match query.to_sql(&mut query_builder, &DB::default()) {
+        Ok(()) => query_builder.finish(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// 実行したSQLと行数をトレースに記録するRunQueryDsl
+pub trait TracedRunQueryDsl: RunQueryDsl<DbConnection> + Sized {
+    fn traced_load<'query, U>(self, connection: &mut DbConnection) -> QueryResult<Vec<U>>
+    where
+        Self: LoadQuery<'query, DbConnection, U> + QueryFragment<MultiBackend>,
+    {
+        let sql = debug_sql(connection, &self);
+        let results = self.load(connection);
+        if let Ok(results) = &results {
+            tracing::debug!(sql, rows = results.len(), "load");
+        }
+        results
+    }
+
+    fn traced_execute(self, connection: &mut DbConnection) -> QueryResult<usize>
+    where
+        Self: ExecuteDsl<DbConnection> + QueryFragment<MultiBackend>,
+    {
+        let sql = debug_sql(connection, &self);
+        let rows = self.execute(connection);
+        if let Ok(rows) = &rows {
+            tracing::debug!(sql, rows, "execute");
+        }
+        rows
+    }
+
+    /// INSERT IGNOREのようにMultiBackendでは組み立てられないステートメントを、
+    /// DbConnectionの中のコネクションで実行する
+    fn traced_execute_on<C>(self, connection: &mut C) -> QueryResult<usize>
+    where
+        C: Connection,
+        C::Backend: Default,
+        <C::Backend as Backend>::QueryBuilder: Default,
+        Self: ExecuteDsl<C> + QueryFragment<C::Backend>,
+    {
+        let sql = backend_sql::<C::Backend, _>(&self);
+        let rows = ExecuteDsl::execute(self, connection);
+        if let Ok(rows) = &rows {
+            tracing::debug!(sql, rows, "execute");
+        }
+        rows
+    }
+}
+
+impl<T: RunQueryDsl<DbConnection>> TracedRunQueryDsl for T {}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::rdb::diesel_connection::MultiBackend;
+    use crate::infrastructure::rdb::diesel_model::diesel_user::User;
+    use crate::infrastructure::rdb::diesel_trace::build_sql;
+    use crate::schema::user;
+    use diesel::mysql::Mysql;
+    use diesel::pg::Pg;
+    use diesel::*;
+
+    #[test]
+    fn test_build_sql_mysql() {
+        let query = user::table
+            .select(User::as_select())
+            .filter(user::dsl::email.eq("secret@example.com"));
+        let sql = build_sql(&query, MultiBackend::Mysql(Mysql));
+        assert_eq!(
+            sql,
+            "SELECT `user`.`id`, `user`.`email`, `user`.`hashed_password`, `user`.`role`, `user`.`verified_at` \
+             FROM `user` WHERE (`user`.`email` = ?)"
+        );
+    }
+
+    #[test]
+    fn test_build_sql_postgresql() {
+        let query = user::table
+            .select(User::as_select())
+            .filter(user::dsl::email.eq("secret@example.com"));
+        let sql = build_sql(&query, MultiBackend::Postgresql(Pg));
+        assert_eq!(
+            sql,
+            "SELECT \"user\".\"id\", \"user\".\"email\", \"user\".\"hashed_password\", \"user\".\"role\", \"user\".\"verified_at\" \
+             FROM \"user\" WHERE (\"user\".\"email\" = $1)"
+        );
+    }
+}
@@ -1,2 +1,4 @@
 pub mod diesel_connection;
+pub mod diesel_migration;
 pub mod diesel_model;
+pub mod diesel_trace;
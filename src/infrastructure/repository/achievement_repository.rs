@@ -0,0 +1,141 @@
+use crate::domain::achievement_entity::{AchievementEntity, UserAchievementEntity};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_or_ignore, insert_returning_id},
+    diesel_model::diesel_achievement::{Achievement, UserAchievement},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{achievement, user_achievement};
+use chrono::{NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+/// 作った順
+#[instrument(skip_all)]
+pub fn get_achievements() -> Vec<AchievementEntity> {
+    let _timer = db_timer("get_achievements");
+    let connection = &mut establish_connection();
+    let results: Vec<Achievement> = achievement::table
+        .select(Achievement::as_select())
+        .order(achievement::dsl::id)
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(AchievementEntity::from).collect()
+}
+
+#[instrument]
+pub fn get_achievement(id: u32) -> Option<AchievementEntity> {
+    let _timer = db_timer("get_achievement");
+    let connection = &mut establish_connection();
+    let results: Vec<Achievement> = achievement::table
+        .select(Achievement::as_select())
+        .filter(achievement::dsl::id.eq(id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().next().map(AchievementEntity::from)
+}
+
+#[instrument(skip_all)]
+pub fn post_achievement(achievement_entity: AchievementEntity) -> AchievementEntity {
+    let _timer = db_timer("post_achievement");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let condition = &achievement_entity.condition;
+    let id = insert_returning_id!(
+        connection,
+        achievement::table,
+        (
+            achievement::dsl::name.eq(&achievement_entity.name),
+            achievement::dsl::description.eq(&achievement_entity.description),
+            achievement::dsl::condition_kind.eq(condition.kind()),
+            achievement::dsl::condition_target.eq(condition.target()),
+            achievement::dsl::condition_value.eq(condition.value()),
+            achievement::dsl::created_at.eq(now),
+            achievement::dsl::updated_at.eq(now),
+        )
+    );
+    AchievementEntity {
+        id: id as u32,
+        ..achievement_entity
+    }
+}
+
+/// 条件を変えても獲得済みのバッジはそのまま。存在しなければfalse
+#[instrument(skip_all)]
+pub fn put_achievement(achievement_entity: &AchievementEntity) -> bool {
+    let _timer = db_timer("put_achievement");
+    let connection = &mut establish_connection();
+    let condition = &achievement_entity.condition;
+    let updated = diesel::update(achievement::table.find(achievement_entity.id as i32))
+        .set((
+            achievement::dsl::name.eq(&achievement_entity.name),
+            achievement::dsl::description.eq(&achievement_entity.description),
+            achievement::dsl::condition_kind.eq(condition.kind()),
+            achievement::dsl::condition_target.eq(condition.target()),
+            achievement::dsl::condition_value.eq(condition.value()),
+            achievement::dsl::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+/// 獲得済みのバッジも削除する
+#[instrument]
+pub fn delete_achievement(id: u32) -> bool {
+    let _timer = db_timer("delete_achievement");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            diesel::delete(user_achievement::table)
+                .filter(user_achievement::dsl::achievement_id.eq(id as i32))
+                .traced_execute(connection)?;
+            let deleted =
+                diesel::delete(achievement::table.find(id as i32)).traced_execute(connection)?;
+            QueryResult::Ok(deleted > 0)
+        })
+        .expect("DB error")
+}
+
+/// 獲得した順
+#[instrument]
+pub fn get_user_achievements(user_id: u32) -> Vec<UserAchievementEntity> {
+    let _timer = db_timer("get_user_achievements");
+    let connection = &mut establish_connection();
+    let results: Vec<UserAchievement> = user_achievement::table
+        .select(UserAchievement::as_select())
+        .filter(user_achievement::dsl::user_id.eq(user_id as i32))
+        .order((
+            user_achievement::dsl::achieved_at,
+            user_achievement::dsl::id,
+        ))
+        .traced_load(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .map(UserAchievementEntity::from)
+        .collect()
+}
+
+/// 同時に判定して獲得済みのバッジと重なったものは、先に記録したほうを残す
+#[instrument(skip(achieved_at))]
+pub fn post_user_achievements(user_id: u32, achievement_ids: &[u32], achieved_at: NaiveDateTime) {
+    let _timer = db_timer("post_user_achievements");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            for achievement_id in achievement_ids {
+                insert_or_ignore!(
+                    connection,
+                    user_achievement::table,
+                    (
+                        user_achievement::dsl::user_id.eq(user_id as i32),
+                        user_achievement::dsl::achievement_id.eq(*achievement_id as i32),
+                        user_achievement::dsl::achieved_at.eq(achieved_at),
+                    )
+                )?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
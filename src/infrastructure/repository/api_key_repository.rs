@@ -0,0 +1,116 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_returning_id},
+    diesel_model::diesel_api_key::ApiKey,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::api_key;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+/// 最終使用日時を更新する間隔。リクエストのたびに書き込まないようにする
+const LAST_USED_UPDATE_INTERVAL_SECONDS: i64 = 60;
+
+#[instrument(skip_all)]
+pub fn post_api_key(
+    user_id: i32,
+    name: &str,
+    key_prefix: &str,
+    key_hash: &str,
+    scopes: &str,
+    mfa: bool,
+    expires_at: Option<NaiveDateTime>,
+) -> ApiKey {
+    let _timer = db_timer("post_api_key");
+    let connection = &mut establish_connection();
+    let id = connection
+        .transaction(|connection| {
+            let id = insert_returning_id!(
+                connection,
+                api_key::table,
+                (
+                    api_key::dsl::user_id.eq(user_id),
+                    api_key::dsl::name.eq(name),
+                    api_key::dsl::key_prefix.eq(key_prefix),
+                    api_key::dsl::key_hash.eq(key_hash),
+                    api_key::dsl::scopes.eq(scopes),
+                    api_key::dsl::mfa.eq(mfa),
+                    api_key::dsl::created_at.eq(Utc::now().naive_utc()),
+                )
+            );
+            // MultiConnectionでNULLをバインドするとPostgreSQLで型が合わないので、期限があるときだけ更新する
+            if let Some(expires_at) = expires_at {
+                diesel::update(api_key::table.find(id))
+                    .set(api_key::dsl::expires_at.eq(expires_at))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(id)
+        })
+        .expect("DB error");
+    let results: Vec<ApiKey> = api_key::table
+        .select(ApiKey::as_select())
+        .filter(api_key::dsl::id.eq(id))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().expect("DB error")
+}
+
+/// 失効していないAPIキーの一覧
+#[instrument]
+pub fn get_api_keys(user_id: i32) -> Vec<ApiKey> {
+    let _timer = db_timer("get_api_keys");
+    let connection = &mut establish_connection();
+    api_key::table
+        .select(ApiKey::as_select())
+        .filter(api_key::dsl::user_id.eq(user_id))
+        .filter(api_key::dsl::revoked_at.is_null())
+        .order(api_key::dsl::id.asc())
+        .traced_load(connection)
+        .expect("DB error")
+}
+
+/// 失効していないAPIキーをハッシュで探す。期限切れかどうかは呼び出し側で確認する
+#[instrument(skip_all)]
+pub fn get_api_key_by_hash(key_hash: &str) -> Option<ApiKey> {
+    let _timer = db_timer("get_api_key_by_hash");
+    let connection = &mut establish_connection();
+    let results: Vec<ApiKey> = api_key::table
+        .select(ApiKey::as_select())
+        .filter(api_key::dsl::key_hash.eq(key_hash))
+        .filter(api_key::dsl::revoked_at.is_null())
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned()
+}
+
+#[instrument]
+pub fn put_api_key_last_used(id: i32) {
+    let _timer = db_timer("put_api_key_last_used");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    diesel::update(api_key::table.find(id))
+        .filter(
+            api_key::dsl::last_used_at
+                .is_null()
+                .or(api_key::dsl::last_used_at
+                    .lt(now - Duration::seconds(LAST_USED_UPDATE_INTERVAL_SECONDS))),
+        )
+        .set(api_key::dsl::last_used_at.eq(now))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// ユーザーのAPIキーを失効させる。存在しないか失効済みならfalse
+#[instrument]
+pub fn revoke_api_key(user_id: i32, id: i32) -> bool {
+    let _timer = db_timer("revoke_api_key");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(api_key::table.find(id))
+        .filter(api_key::dsl::user_id.eq(user_id))
+        .filter(api_key::dsl::revoked_at.is_null())
+        .set(api_key::dsl::revoked_at.eq(Utc::now().naive_utc()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
@@ -1,24 +1,28 @@
-use crate::infrastructure::mysql::{
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
     diesel_connection::establish_connection, diesel_model::diesel_area::Area,
-    diesel_model::diesel_onsen::Onsen,
+    diesel_model::diesel_onsen::Onsen, diesel_trace::TracedRunQueryDsl,
 };
 use crate::{
     domain::area_entity::AreaEntity, domain::onsen::onsen_entity::OnsenEntity, schema::area,
     schema::onsen,
 };
 use diesel::*;
+use tracing::instrument;
 
+#[instrument(skip_all)]
 pub fn get_areas_with_onsen() -> Vec<AreaEntity> {
+    let _timer = db_timer("get_areas_with_onsen");
     let connection = &mut establish_connection();
     let areas_onsens: Vec<(Area, Option<Onsen>)> = area::table
         .left_join(onsen::table)
         .select((Area::as_select(), Option::<Onsen>::as_select()))
-        .load(connection)
+        .traced_load(connection)
         .expect("DB error");
     let mut area_entities: Vec<AreaEntity> = vec![];
     for area_onsen in areas_onsens {
         let (area, onsen) = area_onsen;
-        let got_area = area_entities.iter_mut().find(|v| v.id == area.id);
+        let got_area = area_entities.iter_mut().find(|v| v.id == area.id as u32);
         if let Some(area_entity) = got_area {
             if let Some(onsen) = onsen {
                 area_entity.onsens.push(OnsenEntity::create(onsen, None));
@@ -34,12 +38,14 @@ pub fn get_areas_with_onsen() -> Vec<AreaEntity> {
     area_entities
 }
 
+#[instrument]
 pub fn get_area(id: u32) -> Option<AreaEntity> {
+    let _timer = db_timer("get_area");
     let connection = &mut establish_connection();
     let results: Vec<Area> = area::table
         .select(Area::as_select())
-        .filter(area::dsl::id.eq(id))
-        .load(connection)
+        .filter(area::dsl::id.eq(id as i32))
+        .traced_load(connection)
         .expect("error");
     if results.len() == 0 {
         return None;
@@ -48,7 +54,34 @@ pub fn get_area(id: u32) -> Option<AreaEntity> {
     Some(AreaEntity::from(area.clone()))
 }
 
+#[instrument(skip_all)]
+pub fn get_areas_by_ids(ids: &[u32]) -> Vec<AreaEntity> {
+    let _timer = db_timer("get_areas_by_ids");
+    let connection = &mut establish_connection();
+    let ids: Vec<i32> = ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Area> = area::table
+        .select(Area::as_select())
+        .filter(area::dsl::id.eq_any(ids))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(AreaEntity::from).collect()
+}
+
+#[instrument(skip_all)]
+pub fn get_areas_by_prefectures(prefectures: &[String]) -> Vec<AreaEntity> {
+    let _timer = db_timer("get_areas_by_prefectures");
+    let connection = &mut establish_connection();
+    let results: Vec<Area> = area::table
+        .select(Area::as_select())
+        .filter(area::dsl::prefecture.eq_any(prefectures))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(AreaEntity::from).collect()
+}
+
+#[instrument(skip_all)]
 pub fn put_area(area_entity: AreaEntity) -> () {
+    let _timer = db_timer("put_area");
     let updated_area = Area::from(area_entity);
     let connection = &mut establish_connection();
     let _ = diesel::update(area::table.find(updated_area.id))
@@ -62,16 +95,27 @@ pub fn put_area(area_entity: AreaEntity) -> () {
             area::dsl::description.eq(updated_area.description),
             area::dsl::access.eq(updated_area.access),
         ))
-        .execute(connection)
+        .traced_execute(connection)
         .expect("DB error");
 }
 
+#[instrument(skip_all)]
 pub fn post_area(area_entity: AreaEntity) -> AreaEntity {
+    let _timer = db_timer("post_area");
     let new_area = Area::from(area_entity);
     let connection = &mut establish_connection();
     diesel::insert_into(area::table)
-        .values(&new_area)
-        .execute(connection)
+        .values((
+            area::dsl::name.eq(&new_area.name),
+            area::dsl::kana.eq(&new_area.kana),
+            area::dsl::prefecture.eq(&new_area.prefecture),
+            area::dsl::national_resort.eq(new_area.national_resort),
+            area::dsl::village.eq(&new_area.village),
+            area::dsl::url.eq(&new_area.url),
+            area::dsl::description.eq(&new_area.description),
+            area::dsl::access.eq(&new_area.access),
+        ))
+        .traced_execute(connection)
         .expect("DB error");
     AreaEntity::from(new_area)
 }
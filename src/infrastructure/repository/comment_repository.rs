@@ -0,0 +1,227 @@
+use crate::domain::comment_entity::{
+    CommentEntity, CommentReportEntity, CommentStatus, CommentTarget,
+};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_returning_id},
+    diesel_model::diesel_comment::{Comment, CommentReport},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{comment, comment_report};
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 返信も含めて古い順
+#[instrument]
+pub fn get_comments(target: CommentTarget, target_id: u32) -> Vec<CommentEntity> {
+    let _timer = db_timer("get_comments");
+    let connection = &mut establish_connection();
+    let results: Vec<Comment> = comment::table
+        .select(Comment::as_select())
+        .filter(comment::dsl::target.eq(target.to_string()))
+        .filter(comment::dsl::target_id.eq(target_id as i32))
+        .order(comment::dsl::id)
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(CommentEntity::from).collect()
+}
+
+#[instrument]
+pub fn get_comment(id: u32) -> Option<CommentEntity> {
+    let _timer = db_timer("get_comment");
+    let connection = &mut establish_connection();
+    let results: Vec<Comment> = comment::table
+        .select(Comment::as_select())
+        .filter(comment::dsl::id.eq(id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().next().map(CommentEntity::from)
+}
+
+#[instrument(skip_all)]
+pub fn get_comments_by_ids(ids: &[u32]) -> Vec<CommentEntity> {
+    let _timer = db_timer("get_comments_by_ids");
+    let connection = &mut establish_connection();
+    let ids: Vec<i32> = ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Comment> = comment::table
+        .select(Comment::as_select())
+        .filter(comment::dsl::id.eq_any(ids))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(CommentEntity::from).collect()
+}
+
+#[instrument(skip_all)]
+pub fn post_comment(comment_entity: CommentEntity) -> CommentEntity {
+    let _timer = db_timer("post_comment");
+    let connection = &mut establish_connection();
+    let id = connection
+        .transaction(|connection| {
+            let id = insert_returning_id!(
+                connection,
+                comment::table,
+                (
+                    comment::dsl::target.eq(comment_entity.target.to_string()),
+                    comment::dsl::target_id.eq(comment_entity.target_id as i32),
+                    comment::dsl::user_id.eq(comment_entity.user_id as i32),
+                    comment::dsl::body.eq(&comment_entity.body),
+                    comment::dsl::status.eq(comment_entity.status.to_string()),
+                    comment::dsl::created_at.eq(comment_entity.created_at),
+                    comment::dsl::updated_at.eq(comment_entity.updated_at),
+                )
+            );
+            // MultiConnectionでNULLをバインドするとPostgreSQLで型が合わないので、返信のときだけ更新する
+            if let Some(parent_id) = comment_entity.parent_id {
+                diesel::update(comment::table.find(id))
+                    .set(comment::dsl::parent_id.eq(parent_id as i32))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(id)
+        })
+        .expect("DB error");
+    CommentEntity {
+        id: id as u32,
+        ..comment_entity
+    }
+}
+
+/// 書いた人の表示中のコメントだけ書き換える。なければfalse
+#[instrument(skip(body))]
+pub fn put_comment_body(id: u32, user_id: u32, body: &str) -> bool {
+    let _timer = db_timer("put_comment_body");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(comment::table.find(id as i32))
+        .filter(comment::dsl::user_id.eq(user_id as i32))
+        .filter(comment::dsl::status.eq(CommentStatus::Visible.to_string()))
+        .set((
+            comment::dsl::body.eq(body),
+            comment::dsl::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+/// 返信があれば本文だけ消して`deleted`にする。返信を消して、削除済みの返信先に返信が残らなければ返信先も消す
+#[instrument]
+pub fn delete_comment(id: u32, user_id: u32) -> bool {
+    let _timer = db_timer("delete_comment");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let results: Vec<Comment> = comment::table
+                .select(Comment::as_select())
+                .filter(comment::dsl::id.eq(id as i32))
+                .filter(comment::dsl::user_id.eq(user_id as i32))
+                .traced_load(connection)?;
+            let Some(target) = results.into_iter().next() else {
+                return QueryResult::Ok(false);
+            };
+            let replies: i64 = comment::table
+                .filter(comment::dsl::parent_id.eq(id as i32))
+                .count()
+                .get_result(connection)?;
+            if replies > 0 {
+                diesel::update(comment::table.find(id as i32))
+                    .set((
+                        comment::dsl::body.eq(""),
+                        comment::dsl::status.eq(CommentStatus::Deleted.to_string()),
+                    ))
+                    .traced_execute(connection)?;
+                return QueryResult::Ok(true);
+            }
+            diesel::delete(comment::table.find(id as i32)).traced_execute(connection)?;
+            if let Some(parent_id) = target.parent_id {
+                let siblings: i64 = comment::table
+                    .filter(comment::dsl::parent_id.eq(parent_id))
+                    .count()
+                    .get_result(connection)?;
+                if siblings == 0 {
+                    diesel::delete(comment::table.find(parent_id))
+                        .filter(comment::dsl::status.eq(CommentStatus::Deleted.to_string()))
+                        .traced_execute(connection)?;
+                }
+            }
+            QueryResult::Ok(true)
+        })
+        .expect("DB error")
+}
+
+/// 旅行・入浴記録を削除したときにコメントもまとめて削除する
+#[instrument]
+pub fn delete_comments(target: CommentTarget, target_id: u32) {
+    let _timer = db_timer("delete_comments");
+    let connection = &mut establish_connection();
+    diesel::delete(comment::table)
+        .filter(comment::dsl::target.eq(target.to_string()))
+        .filter(comment::dsl::target_id.eq(target_id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// 管理者がコメントを表示・非表示にする。対応した通報は消す。コメントがなければfalse
+#[instrument]
+pub fn put_comment_status(id: u32, status: CommentStatus) -> bool {
+    let _timer = db_timer("put_comment_status");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let updated = diesel::update(comment::table.find(id as i32))
+                .filter(comment::dsl::status.ne(CommentStatus::Deleted.to_string()))
+                .set(comment::dsl::status.eq(status.to_string()))
+                .traced_execute(connection)?;
+            if updated == 0 {
+                return QueryResult::Ok(false);
+            }
+            diesel::delete(comment_report::table)
+                .filter(comment_report::dsl::comment_id.eq(id as i32))
+                .traced_execute(connection)?;
+            QueryResult::Ok(true)
+        })
+        .expect("DB error")
+}
+
+/// 同じ人が同じコメントをもう一度通報したら内容を更新する
+#[instrument(skip_all)]
+pub fn put_comment_report(report: &CommentReportEntity) {
+    let _timer = db_timer("put_comment_report");
+    let connection = &mut establish_connection();
+    let values = (
+        comment_report::dsl::reason.eq(report.reason.to_string()),
+        comment_report::dsl::note.eq(&report.note),
+    );
+    connection
+        .transaction(|connection| {
+            let updated = diesel::update(comment_report::table)
+                .filter(comment_report::dsl::comment_id.eq(report.comment_id as i32))
+                .filter(comment_report::dsl::user_id.eq(report.user_id as i32))
+                .set(values.clone())
+                .traced_execute(connection)?;
+            if updated == 0 {
+                diesel::insert_into(comment_report::table)
+                    .values((
+                        comment_report::dsl::comment_id.eq(report.comment_id as i32),
+                        comment_report::dsl::user_id.eq(report.user_id as i32),
+                        values,
+                        comment_report::dsl::created_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+/// 未対応の通報。古い順
+#[instrument]
+pub fn get_comment_reports() -> Vec<CommentReportEntity> {
+    let _timer = db_timer("get_comment_reports");
+    let connection = &mut establish_connection();
+    let results: Vec<CommentReport> = comment_report::table
+        .select(CommentReport::as_select())
+        .order(comment_report::dsl::id)
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(CommentReportEntity::from).collect()
+}
@@ -0,0 +1,63 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection,
+    diesel_model::diesel_email_verification_token::EmailVerificationToken,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{email_verification_token, user};
+use chrono::{NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+#[instrument(skip_all)]
+pub fn post_email_verification_token(user_id: i32, token_hash: &str, expires_at: NaiveDateTime) {
+    let _timer = db_timer("post_email_verification_token");
+    let connection = &mut establish_connection();
+    diesel::insert_into(email_verification_token::table)
+        .values((
+            email_verification_token::dsl::user_id.eq(user_id),
+            email_verification_token::dsl::token_hash.eq(token_hash),
+            email_verification_token::dsl::expires_at.eq(expires_at),
+            email_verification_token::dsl::created_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// トークンを使用済みにしてユーザーを確認済みにする。
+/// トークンが存在しない、使用済み、期限切れならfalse
+#[instrument(skip_all)]
+pub fn verify_email(token_hash: &str) -> bool {
+    let _timer = db_timer("verify_email");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let results: Vec<EmailVerificationToken> = email_verification_token::table
+                .select(EmailVerificationToken::as_select())
+                .filter(email_verification_token::dsl::token_hash.eq(token_hash))
+                .traced_load(connection)
+                .expect("DB error");
+            let Some(current) = results.first() else {
+                return QueryResult::Ok(false);
+            };
+            let now = Utc::now().naive_utc();
+            if current.expires_at < now {
+                return Ok(false);
+            }
+            let updated = diesel::update(email_verification_token::table.find(current.id))
+                .filter(email_verification_token::dsl::used_at.is_null())
+                .set(email_verification_token::dsl::used_at.eq(now))
+                .traced_execute(connection)
+                .expect("DB error");
+            if updated == 0 {
+                return Ok(false);
+            }
+            diesel::update(user::table.find(current.user_id))
+                .filter(user::dsl::verified_at.is_null())
+                .set(user::dsl::verified_at.eq(now))
+                .traced_execute(connection)
+                .expect("DB error");
+            Ok(true)
+        })
+        .expect("DB error")
+}
@@ -0,0 +1,133 @@
+use crate::domain::profile_entity::{FollowEntity, FollowStatus};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_or_ignore},
+    diesel_model::diesel_follow::Follow,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::follow;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+#[instrument]
+pub fn get_follow(follower_id: u32, followee_id: u32) -> Option<FollowEntity> {
+    let _timer = db_timer("get_follow");
+    let connection = &mut establish_connection();
+    let results: Vec<Follow> = follow::table
+        .select(Follow::as_select())
+        .filter(follow::dsl::follower_id.eq(follower_id as i32))
+        .filter(follow::dsl::followee_id.eq(followee_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(FollowEntity::from)
+}
+
+/// フォローしている人。新しい順
+#[instrument]
+pub fn get_followees(follower_id: u32, status: FollowStatus) -> Vec<FollowEntity> {
+    let _timer = db_timer("get_followees");
+    let connection = &mut establish_connection();
+    let results: Vec<Follow> = follow::table
+        .select(Follow::as_select())
+        .filter(follow::dsl::follower_id.eq(follower_id as i32))
+        .filter(follow::dsl::status.eq(status.to_string()))
+        .order(follow::dsl::id.desc())
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(FollowEntity::from).collect()
+}
+
+/// フォローされている人。新しい順
+#[instrument]
+pub fn get_followers(followee_id: u32, status: FollowStatus) -> Vec<FollowEntity> {
+    let _timer = db_timer("get_followers");
+    let connection = &mut establish_connection();
+    let results: Vec<Follow> = follow::table
+        .select(Follow::as_select())
+        .filter(follow::dsl::followee_id.eq(followee_id as i32))
+        .filter(follow::dsl::status.eq(status.to_string()))
+        .order(follow::dsl::id.desc())
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(FollowEntity::from).collect()
+}
+
+/// 承認済みのフォロワー数とフォロー数
+#[instrument]
+pub fn count_follows(user_id: u32) -> (u32, u32) {
+    let _timer = db_timer("count_follows");
+    let connection = &mut establish_connection();
+    let accepted = FollowStatus::Accepted.to_string();
+    let followers: i64 = follow::table
+        .filter(follow::dsl::followee_id.eq(user_id as i32))
+        .filter(follow::dsl::status.eq(&accepted))
+        .count()
+        .get_result(connection)
+        .expect("DB error");
+    let following: i64 = follow::table
+        .filter(follow::dsl::follower_id.eq(user_id as i32))
+        .filter(follow::dsl::status.eq(&accepted))
+        .count()
+        .get_result(connection)
+        .expect("DB error");
+    (followers as u32, following as u32)
+}
+
+/// すでにフォローしていればそのまま返す。承認済みのフォローを申請中に戻さないため、
+/// 同時に申請されても一意制約に引っかかった方は挿入しない
+#[instrument(skip_all)]
+pub fn post_follow(follow_entity: FollowEntity) -> FollowEntity {
+    let _timer = db_timer("post_follow");
+    let connection = &mut establish_connection();
+    insert_or_ignore!(
+        connection,
+        follow::table,
+        (
+            follow::dsl::follower_id.eq(follow_entity.follower_id as i32),
+            follow::dsl::followee_id.eq(follow_entity.followee_id as i32),
+            follow::dsl::status.eq(follow_entity.status.to_string()),
+            follow::dsl::created_at.eq(Utc::now().naive_utc()),
+        )
+    )
+    .expect("DB error");
+    let results: Vec<Follow> = follow::table
+        .select(Follow::as_select())
+        .filter(follow::dsl::follower_id.eq(follow_entity.follower_id as i32))
+        .filter(follow::dsl::followee_id.eq(follow_entity.followee_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .next()
+        .map(FollowEntity::from)
+        .expect("follow was inserted or already exists")
+}
+
+/// 申請中のフォローを承認する。申請がなければfalse
+#[instrument]
+pub fn accept_follow(follower_id: u32, followee_id: u32) -> bool {
+    let _timer = db_timer("accept_follow");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(follow::table)
+        .filter(follow::dsl::follower_id.eq(follower_id as i32))
+        .filter(follow::dsl::followee_id.eq(followee_id as i32))
+        .filter(follow::dsl::status.eq(FollowStatus::Pending.to_string()))
+        .set(follow::dsl::status.eq(FollowStatus::Accepted.to_string()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+/// フォローの解除、申請の取り下げ・却下、フォロワーの削除に使う
+#[instrument]
+pub fn delete_follow(follower_id: u32, followee_id: u32) -> bool {
+    let _timer = db_timer("delete_follow");
+    let connection = &mut establish_connection();
+    let deleted = diesel::delete(follow::table)
+        .filter(follow::dsl::follower_id.eq(follower_id as i32))
+        .filter(follow::dsl::followee_id.eq(followee_id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+    deleted > 0
+}
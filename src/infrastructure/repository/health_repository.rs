@@ -0,0 +1,26 @@
+use super::super::metrics::db_timer;
+use super::super::rdb::diesel_connection::try_establish_connection;
+use super::super::rdb::diesel_migration::has_pending_migration;
+use tracing::instrument;
+
+pub struct Readiness {
+    pub database: bool,
+    pub migrations: bool,
+}
+
+#[instrument]
+pub fn get_readiness() -> Readiness {
+    let _timer = db_timer("get_readiness");
+    let Ok(mut connection) = try_establish_connection() else {
+        return Readiness {
+            database: false,
+            migrations: false,
+        };
+    };
+    let migrations = has_pending_migration(&mut connection) == Ok(false);
+    tracing::debug!(migrations, "checked pending migrations");
+    Readiness {
+        database: true,
+        migrations,
+    }
+}
@@ -1,8 +1,10 @@
+use crate::infrastructure::metrics::db_timer;
 use crate::{
     domain::{hotel_entity::HotelEntity, onsen::onsen_entity::OnsenEntity},
-    infrastructure::mysql::{
+    infrastructure::rdb::{
         diesel_connection::establish_connection,
         diesel_model::{diesel_hotel::Hotel, diesel_onsen::Onsen},
+        diesel_trace::TracedRunQueryDsl,
     },
     schema::{
         hotel::{self},
@@ -10,16 +12,19 @@ use crate::{
     },
 };
 use diesel::*;
+use tracing::instrument;
 
+#[instrument]
 pub fn get_hotels(area_id: Option<u32>) -> Vec<HotelEntity> {
+    let _timer = db_timer("get_hotels");
     let connection = &mut establish_connection();
     let mut query = hotel::table.into_boxed();
     if let Some(area_id) = area_id {
-        query = query.filter(hotel::dsl::area_id.eq(area_id));
+        query = query.filter(hotel::dsl::area_id.eq(area_id as i32));
     }
     let results: Vec<Hotel> = query
         .select(Hotel::as_select())
-        .load(connection)
+        .traced_load(connection)
         .expect("DB error");
     return results
         .iter()
@@ -27,13 +32,15 @@ pub fn get_hotels(area_id: Option<u32>) -> Vec<HotelEntity> {
         .collect();
 }
 
+#[instrument]
 pub fn get_hotel_with_onsen(id: u32) -> Option<HotelEntity> {
+    let _timer = db_timer("get_hotel_with_onsen");
     let connection = &mut establish_connection();
     let hotels_onsens: Vec<(Hotel, Option<Onsen>)> = hotel::table
         .left_join(onsen::table)
         .select((Hotel::as_select(), Option::<Onsen>::as_select()))
-        .filter(hotel::dsl::id.eq(id))
-        .load(connection)
+        .filter(hotel::dsl::id.eq(id as i32))
+        .traced_load(connection)
         .expect("DB error");
     let hotel = &hotels_onsens.first()?.0;
     let related_onsens: Vec<&Option<Onsen>> = hotels_onsens.iter().map(|r| &r.1).collect();
@@ -46,17 +53,40 @@ pub fn get_hotel_with_onsen(id: u32) -> Option<HotelEntity> {
     Some(HotelEntity::from(hotel.clone()))
 }
 
+#[instrument(skip_all)]
+pub fn get_hotels_by_ids(ids: &[u32]) -> Vec<HotelEntity> {
+    let _timer = db_timer("get_hotels_by_ids");
+    let connection = &mut establish_connection();
+    let ids: Vec<i32> = ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Hotel> = hotel::table
+        .select(Hotel::as_select())
+        .filter(hotel::dsl::id.eq_any(ids))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(HotelEntity::from).collect()
+}
+
+#[instrument(skip_all)]
 pub fn post_hotel(hotel_entity: HotelEntity) -> HotelEntity {
+    let _timer = db_timer("post_hotel");
     let new_hotel = Hotel::from(hotel_entity);
     let connection = &mut establish_connection();
     diesel::insert_into(hotel::table)
-        .values(&new_hotel)
-        .execute(connection)
+        .values((
+            hotel::dsl::name.eq(&new_hotel.name),
+            hotel::dsl::has_washitsu.eq(new_hotel.has_washitsu),
+            hotel::dsl::solo_available.eq(new_hotel.solo_available),
+            hotel::dsl::description.eq(&new_hotel.description),
+            hotel::dsl::url.eq(&new_hotel.url),
+        ))
+        .traced_execute(connection)
         .expect("DB error");
     HotelEntity::from(new_hotel)
 }
 
+#[instrument(skip_all)]
 pub fn put_hotel(hotel_entity: HotelEntity) -> () {
+    let _timer = db_timer("put_hotel");
     let updated_hotel = Hotel::from(hotel_entity);
     let connection = &mut establish_connection();
     let _ = diesel::update(hotel::dsl::hotel.find(updated_hotel.id))
@@ -67,6 +97,6 @@ pub fn put_hotel(hotel_entity: HotelEntity) -> () {
             hotel::dsl::description.eq(updated_hotel.description),
             hotel::dsl::url.eq(updated_hotel.url),
         ))
-        .execute(connection)
+        .traced_execute(connection)
         .expect("DB error");
 }
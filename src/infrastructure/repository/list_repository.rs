@@ -0,0 +1,204 @@
+use crate::domain::list_entity::{ListEntity, ListItemEntity, ListItemTarget, ListKind};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{
+        establish_connection, insert_or_ignore, insert_returning_id, DbConnection,
+    },
+    diesel_model::diesel_user_list::{UserList, UserListItem},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{user_list, user_list_item};
+use chrono::Utc;
+use diesel::*;
+use std::str::FromStr;
+use tracing::instrument;
+
+/// 作った順
+#[instrument]
+pub fn get_lists(user_id: u32) -> Vec<ListEntity> {
+    let _timer = db_timer("get_lists");
+    let connection = &mut establish_connection();
+    let lists: Vec<UserList> = user_list::table
+        .select(UserList::as_select())
+        .filter(user_list::dsl::user_id.eq(user_id as i32))
+        .order(user_list::dsl::id)
+        .traced_load(connection)
+        .expect("DB error");
+    load_list_entities(connection, lists)
+}
+
+/// 持ち主かどうかは呼び出し側で確認する
+#[instrument]
+pub fn get_list(id: u32) -> Option<ListEntity> {
+    let _timer = db_timer("get_list");
+    let connection = &mut establish_connection();
+    let lists: Vec<UserList> = user_list::table
+        .select(UserList::as_select())
+        .filter(user_list::dsl::id.eq(id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    load_list_entities(connection, lists).into_iter().next()
+}
+
+#[instrument(skip_all)]
+pub fn get_list_by_share_token(share_token: &str) -> Option<ListEntity> {
+    let _timer = db_timer("get_list_by_share_token");
+    let connection = &mut establish_connection();
+    let lists: Vec<UserList> = user_list::table
+        .select(UserList::as_select())
+        .filter(user_list::dsl::share_token.eq(share_token))
+        .traced_load(connection)
+        .expect("DB error");
+    load_list_entities(connection, lists).into_iter().next()
+}
+
+#[instrument(skip_all)]
+pub fn post_list(list_entity: ListEntity) -> ListEntity {
+    let _timer = db_timer("post_list");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let id = connection
+        .transaction(|connection| {
+            let id = insert_returning_id!(
+                connection,
+                user_list::table,
+                (
+                    user_list::dsl::user_id.eq(list_entity.user_id as i32),
+                    user_list::dsl::kind.eq(list_entity.kind.to_string()),
+                    user_list::dsl::name.eq(&list_entity.name),
+                    user_list::dsl::share_token.eq(&list_entity.share_token),
+                    user_list::dsl::created_at.eq(now),
+                    user_list::dsl::updated_at.eq(now),
+                )
+            );
+            insert_list_items(connection, id, &list_entity.items)?;
+            QueryResult::Ok(id)
+        })
+        .expect("DB error");
+    ListEntity {
+        id: id as u32,
+        ..list_entity
+    }
+}
+
+/// お気に入りと行きたいのリストを作る。ユーザーごとに1つだけなので、すでにあれば何もしない。
+/// ユーザーの登録と同じトランザクションで呼ぶ
+pub fn insert_default_lists(connection: &mut DbConnection, user_id: i32) -> QueryResult<()> {
+    let now = Utc::now().naive_utc();
+    for kind in [ListKind::Favorite, ListKind::WantToGo] {
+        insert_or_ignore!(
+            connection,
+            user_list::table,
+            (
+                user_list::dsl::user_id.eq(user_id),
+                user_list::dsl::kind.eq(kind.to_string()),
+                user_list::dsl::name.eq(kind.default_name().unwrap_or_default()),
+                user_list::dsl::created_at.eq(now),
+                user_list::dsl::updated_at.eq(now),
+            )
+        )?;
+    }
+    Ok(())
+}
+
+/// 名前・共有リンク・項目をまとめて保存する。ユーザーのリストが存在しなければfalse
+#[instrument(skip_all)]
+pub fn put_list(list_entity: &ListEntity) -> bool {
+    let _timer = db_timer("put_list");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let id = list_entity.id as i32;
+            let updated = diesel::update(user_list::table.find(id))
+                .filter(user_list::dsl::user_id.eq(list_entity.user_id as i32))
+                .set((
+                    user_list::dsl::name.eq(&list_entity.name),
+                    user_list::dsl::share_token.eq(&list_entity.share_token),
+                    user_list::dsl::updated_at.eq(Utc::now().naive_utc()),
+                ))
+                .traced_execute(connection)?;
+            if updated == 0 {
+                return QueryResult::Ok(false);
+            }
+            diesel::delete(user_list_item::table)
+                .filter(user_list_item::dsl::user_list_id.eq(id))
+                .traced_execute(connection)?;
+            insert_list_items(connection, id, &list_entity.items)?;
+            Ok(true)
+        })
+        .expect("DB error")
+}
+
+#[instrument]
+pub fn delete_list(user_id: u32, id: u32) -> bool {
+    let _timer = db_timer("delete_list");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let owned: i64 = user_list::table
+                .filter(user_list::dsl::id.eq(id as i32))
+                .filter(user_list::dsl::user_id.eq(user_id as i32))
+                .count()
+                .get_result(connection)?;
+            if owned == 0 {
+                return QueryResult::Ok(false);
+            }
+            diesel::delete(user_list_item::table)
+                .filter(user_list_item::dsl::user_list_id.eq(id as i32))
+                .traced_execute(connection)?;
+            diesel::delete(user_list::table.find(id as i32)).traced_execute(connection)?;
+            Ok(true)
+        })
+        .expect("DB error")
+}
+
+fn insert_list_items(
+    connection: &mut DbConnection,
+    user_list_id: i32,
+    items: &[ListItemEntity],
+) -> QueryResult<()> {
+    for (position, item) in items.iter().enumerate() {
+        diesel::insert_into(user_list_item::table)
+            .values((
+                user_list_item::dsl::user_list_id.eq(user_list_id),
+                user_list_item::dsl::target.eq(item.target.to_string()),
+                user_list_item::dsl::target_id.eq(item.target_id as i32),
+                user_list_item::dsl::position.eq(position as i32),
+            ))
+            .traced_execute(connection)?;
+    }
+    Ok(())
+}
+
+/// リストごとに項目をまとめて読み込む
+fn load_list_entities(connection: &mut DbConnection, lists: Vec<UserList>) -> Vec<ListEntity> {
+    let list_ids: Vec<i32> = lists.iter().map(|v| v.id).collect();
+    let items: Vec<UserListItem> = user_list_item::table
+        .select(UserListItem::as_select())
+        .filter(user_list_item::dsl::user_list_id.eq_any(&list_ids))
+        .order(user_list_item::dsl::position)
+        .traced_load(connection)
+        .expect("DB error");
+    lists
+        .into_iter()
+        .map(|list| {
+            ListEntity::new(
+                list.id as u32,
+                list.user_id as u32,
+                ListKind::from_str(&list.kind).expect("Saved data violates ListKind"),
+                &list.name,
+                list.share_token,
+                items
+                    .iter()
+                    .filter(|v| v.user_list_id == list.id)
+                    .map(|v| ListItemEntity {
+                        target: ListItemTarget::from_str(&v.target)
+                            .expect("Saved data violates ListItemTarget"),
+                        target_id: v.target_id as u32,
+                    })
+                    .collect(),
+            )
+            .expect("Saved data violates ListEntity")
+        })
+        .collect()
+}
@@ -1,4 +1,21 @@
+pub mod achievement_repository;
+pub mod api_key_repository;
 pub mod area_repository;
+pub mod comment_repository;
+pub mod email_verification_token_repository;
+pub mod follow_repository;
+pub mod health_repository;
 pub mod hotel_repository;
+pub mod list_repository;
+pub mod notification_repository;
 pub mod onsen_repository;
+pub mod password_reset_token_repository;
+pub mod photo_repository;
+pub mod profile_repository;
+pub mod refresh_token_repository;
+pub mod review_repository;
+pub mod signin_audit_repository;
+pub mod trip_repository;
+pub mod two_factor_repository;
 pub mod user_repository;
+pub mod visit_repository;
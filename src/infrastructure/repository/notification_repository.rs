@@ -0,0 +1,65 @@
+use crate::domain::notification_entity::NotificationEntity;
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection, diesel_model::diesel_notification::Notification,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::notification;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 新しい順に`limit`件まで
+#[instrument]
+pub fn get_notifications(user_id: u32, unread_only: bool, limit: u32) -> Vec<NotificationEntity> {
+    let _timer = db_timer("get_notifications");
+    let connection = &mut establish_connection();
+    let mut query = notification::table.into_boxed();
+    if unread_only {
+        query = query.filter(notification::dsl::read_at.is_null());
+    }
+    let results: Vec<Notification> = query
+        .select(Notification::as_select())
+        .filter(notification::dsl::user_id.eq(user_id as i32))
+        .order(notification::dsl::id.desc())
+        .limit(limit as i64)
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(NotificationEntity::from).collect()
+}
+
+#[instrument(skip_all)]
+pub fn post_notifications(notifications: &[NotificationEntity]) {
+    let _timer = db_timer("post_notifications");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    connection
+        .transaction(|connection| {
+            for v in notifications {
+                diesel::insert_into(notification::table)
+                    .values((
+                        notification::dsl::user_id.eq(v.user_id as i32),
+                        notification::dsl::kind.eq(v.kind.to_string()),
+                        notification::dsl::actor_id.eq(v.actor_id as i32),
+                        notification::dsl::comment_id.eq(v.comment_id as i32),
+                        notification::dsl::created_at.eq(now),
+                    ))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+/// すべて既読にする
+#[instrument]
+pub fn put_notifications_read(user_id: u32) {
+    let _timer = db_timer("put_notifications_read");
+    let connection = &mut establish_connection();
+    diesel::update(notification::table)
+        .filter(notification::dsl::user_id.eq(user_id as i32))
+        .filter(notification::dsl::read_at.is_null())
+        .set(notification::dsl::read_at.eq(Utc::now().naive_utc()))
+        .traced_execute(connection)
+        .expect("DB error");
+}
@@ -1,26 +1,56 @@
+use crate::infrastructure::metrics::db_timer;
 use crate::{
     domain::onsen::onsen_entity::OnsenEntity,
-    infrastructure::mysql::{
-        diesel_connection::establish_connection,
-        diesel_model::{diesel_chemical::DieselChemical, diesel_onsen::Onsen, Sequence},
+    infrastructure::rdb::{
+        diesel_connection::{establish_connection, insert_returning_id},
+        diesel_model::{diesel_chemical::DieselChemical, diesel_onsen::Onsen},
+        diesel_trace::TracedRunQueryDsl,
     },
     schema::{chemicals, onsen},
 };
 use diesel::*;
+use tracing::instrument;
 
+// INSERTとUPDATEで共通のカラムと値の組
+macro_rules! chemical_values {
+    ($chemicals:expr) => {
+        (
+            chemicals::dsl::na_ion.eq($chemicals.na_ion),
+            chemicals::dsl::ca_ion.eq($chemicals.ca_ion),
+            chemicals::dsl::mg_ion.eq($chemicals.mg_ion),
+            chemicals::dsl::cl_ion.eq($chemicals.cl_ion),
+            chemicals::dsl::hco3_ion.eq($chemicals.hco3_ion),
+            chemicals::dsl::so4_ion.eq($chemicals.so4_ion),
+            chemicals::dsl::co2_ion.eq($chemicals.co2_ion),
+            chemicals::dsl::fe_ion.eq($chemicals.fe_ion),
+            chemicals::dsl::al_ion.eq($chemicals.al_ion),
+            chemicals::dsl::cu_ion.eq($chemicals.cu_ion),
+            chemicals::dsl::h_ion.eq($chemicals.h_ion),
+            chemicals::dsl::i_ion.eq($chemicals.i_ion),
+            chemicals::dsl::s.eq($chemicals.s),
+            chemicals::dsl::rn.eq($chemicals.rn),
+            chemicals::dsl::strong_na_cl.eq($chemicals.strong_na_cl),
+            chemicals::dsl::fe_type.eq($chemicals.fe_type),
+            chemicals::dsl::weak_rn.eq($chemicals.weak_rn),
+        )
+    };
+}
+
+#[instrument]
 pub fn get_onsens(area_id: Option<u32>, hotel_id: Option<u32>) -> Vec<OnsenEntity> {
+    let _timer = db_timer("get_onsens");
     let connection = &mut establish_connection();
     let mut query = onsen::table.into_boxed();
     if let Some(area_id) = area_id {
-        query = query.filter(onsen::dsl::area_id.eq(area_id));
+        query = query.filter(onsen::dsl::area_id.eq(area_id as i32));
     }
     if let Some(hotel_id) = hotel_id {
-        query = query.filter(onsen::dsl::hotel_id.eq(hotel_id));
+        query = query.filter(onsen::dsl::hotel_id.eq(hotel_id as i32));
     }
     let results: Vec<(Onsen, Option<DieselChemical>)> = query
         .left_join(chemicals::table)
         .select((Onsen::as_select(), Option::<DieselChemical>::as_select()))
-        .load::<(Onsen, Option<DieselChemical>)>(connection)
+        .traced_load::<(Onsen, Option<DieselChemical>)>(connection)
         .expect("DB error");
     let onsen_entities = results
         .iter()
@@ -32,19 +62,40 @@ pub fn get_onsens(area_id: Option<u32>, hotel_id: Option<u32>) -> Vec<OnsenEntit
     onsen_entities
 }
 
+#[instrument]
 pub fn get_onsen(id: u32) -> Option<OnsenEntity> {
+    let _timer = db_timer("get_onsen");
     let connection = &mut establish_connection();
     let results: Vec<(Onsen, Option<DieselChemical>)> = onsen::table
         .left_join(chemicals::table)
         .select((Onsen::as_select(), Option::<DieselChemical>::as_select()))
-        .filter(onsen::dsl::id.eq(id))
-        .load::<(Onsen, Option<DieselChemical>)>(connection)
+        .filter(onsen::dsl::id.eq(id as i32))
+        .traced_load::<(Onsen, Option<DieselChemical>)>(connection)
         .expect("DB error");
     let result = results.first()?;
     Some(OnsenEntity::create(result.0.clone(), result.1.clone()))
 }
 
+#[instrument(skip_all)]
+pub fn get_onsens_by_ids(ids: &[u32]) -> Vec<OnsenEntity> {
+    let _timer = db_timer("get_onsens_by_ids");
+    let connection = &mut establish_connection();
+    let ids: Vec<i32> = ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<(Onsen, Option<DieselChemical>)> = onsen::table
+        .left_join(chemicals::table)
+        .select((Onsen::as_select(), Option::<DieselChemical>::as_select()))
+        .filter(onsen::dsl::id.eq_any(ids))
+        .traced_load::<(Onsen, Option<DieselChemical>)>(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .map(|(onsen, chemical)| OnsenEntity::create(onsen, chemical))
+        .collect()
+}
+
+#[instrument(skip_all)]
 pub fn put_onsen(onsen_entity: OnsenEntity) -> () {
+    let _timer = db_timer("put_onsen");
     let updated_onsen = Onsen::from(onsen_entity.clone());
     let updated_chemicals = onsen_entity
         .clone()
@@ -54,60 +105,35 @@ pub fn put_onsen(onsen_entity: OnsenEntity) -> () {
     let _ = connection.transaction(|connection| {
         let target_onsen_record: Vec<Onsen> = onsen::table
             .select(Onsen::as_select())
-            .filter(onsen::dsl::id.eq(onsen_entity.id))
-            .load::<Onsen>(connection)
+            .filter(onsen::dsl::id.eq(onsen_entity.id as i32))
+            .traced_load::<Onsen>(connection)
             .expect("DB error");
         let chemical_id = target_onsen_record.first().and_then(|v| v.chemical_id);
         if let Some(current_chemical_id) = chemical_id {
             if let Some(updated_chemicals) = updated_chemicals.clone() {
                 let _ = diesel::update(chemicals::table.find(current_chemical_id))
-                    .set((
-                        chemicals::dsl::na_ion.eq(updated_chemicals.na_ion),
-                        chemicals::dsl::ca_ion.eq(updated_chemicals.ca_ion),
-                        chemicals::dsl::mg_ion.eq(updated_chemicals.mg_ion),
-                        chemicals::dsl::cl_ion.eq(updated_chemicals.cl_ion),
-                        chemicals::dsl::hco3_ion.eq(updated_chemicals.hco3_ion),
-                        chemicals::dsl::so4_ion.eq(updated_chemicals.so4_ion),
-                        chemicals::dsl::co2_ion.eq(updated_chemicals.co2_ion),
-                        chemicals::dsl::fe_ion.eq(updated_chemicals.fe_ion),
-                        chemicals::dsl::al_ion.eq(updated_chemicals.al_ion),
-                        chemicals::dsl::cu_ion.eq(updated_chemicals.cu_ion),
-                        chemicals::dsl::h_ion.eq(updated_chemicals.h_ion),
-                        chemicals::dsl::i_ion.eq(updated_chemicals.i_ion),
-                        chemicals::dsl::s.eq(updated_chemicals.s),
-                        chemicals::dsl::rn.eq(updated_chemicals.rn),
-                        chemicals::dsl::strong_na_cl.eq(updated_chemicals.strong_na_cl),
-                        chemicals::dsl::fe_type.eq(updated_chemicals.fe_type),
-                        chemicals::dsl::weak_rn.eq(updated_chemicals.weak_rn),
-                    ))
-                    .execute(connection)
+                    .set(chemical_values!(updated_chemicals))
+                    .traced_execute(connection)
                     .expect("DB error");
             } else {
                 let _ = diesel::update(onsen::table.find(updated_onsen.id))
-                    .set(onsen::dsl::chemical_id.eq(None::<u32>))
-                    .execute(connection)
+                    .set(onsen::dsl::chemical_id.eq(None::<i32>))
+                    .traced_execute(connection)
                     .expect("DB error");
                 diesel::delete(chemicals::table.find(current_chemical_id))
-                    .execute(connection)
+                    .traced_execute(connection)
                     .expect("DB error");
             }
         } else {
             if let Some(updated_chemicals) = updated_chemicals.clone() {
-                diesel::insert_into(chemicals::table)
-                    .values(updated_chemicals)
-                    .execute(connection)
-                    .expect("DB error");
-                let new_chemical_id = Some(
-                    diesel::sql_query("select LAST_INSERT_ID() as id")
-                        .load::<Sequence>(connection)
-                        .expect("get_id_error")
-                        .first()
-                        .unwrap()
-                        .id as u32,
-                );
+                let new_chemical_id = Some(insert_returning_id!(
+                    connection,
+                    chemicals::table,
+                    chemical_values!(updated_chemicals)
+                ));
                 let _ = diesel::update(onsen::table.find(updated_onsen.id))
                     .set(onsen::dsl::chemical_id.eq(new_chemical_id))
-                    .execute(connection)
+                    .traced_execute(connection)
                     .expect("DB error");
             }
         }
@@ -116,6 +142,7 @@ pub fn put_onsen(onsen_entity: OnsenEntity) -> () {
                 onsen::dsl::name.eq(updated_onsen.name),
                 onsen::dsl::spring_quality.eq(updated_onsen.spring_quality),
                 onsen::dsl::liquid.eq(updated_onsen.liquid),
+                onsen::dsl::ph.eq(updated_onsen.ph),
                 onsen::dsl::osmotic_pressure.eq(updated_onsen.osmotic_pressure),
                 onsen::dsl::temperature.eq(updated_onsen.temperature),
                 onsen::dsl::category.eq(updated_onsen.category),
@@ -126,14 +153,16 @@ pub fn put_onsen(onsen_entity: OnsenEntity) -> () {
                 onsen::dsl::hotel_id.eq(updated_onsen.hotel_id),
                 onsen::dsl::area_id.eq(updated_onsen.area_id),
             ))
-            .execute(connection)
+            .traced_execute(connection)
             .expect("DB error");
 
         diesel::result::QueryResult::Ok(())
     });
 }
 
+#[instrument(skip_all)]
 pub fn post_onsen(onsen_entity: OnsenEntity) -> OnsenEntity {
+    let _timer = db_timer("post_onsen");
     let mut new_onsen = Onsen::from(onsen_entity.clone());
     let new_chemicals = onsen_entity
         .clone()
@@ -141,26 +170,33 @@ pub fn post_onsen(onsen_entity: OnsenEntity) -> OnsenEntity {
         .map(|v| DieselChemical::from(v));
     let connection = &mut establish_connection();
     let _ = connection.transaction(|connection| {
-        let mut generated_id: Option<u32> = None;
+        let mut generated_id: Option<i32> = None;
         if let Some(new_chemicals) = new_chemicals.clone() {
-            diesel::insert_into(chemicals::table)
-                .values(&new_chemicals)
-                .execute(connection)
-                .expect("DB error");
-
-            generated_id = Some(
-                diesel::sql_query("select LAST_INSERT_ID() as id")
-                    .load::<Sequence>(connection)
-                    .expect("get_id_error")
-                    .first()
-                    .unwrap()
-                    .id as u32,
-            );
+            generated_id = Some(insert_returning_id!(
+                connection,
+                chemicals::table,
+                chemical_values!(new_chemicals)
+            ));
         }
         new_onsen.chemical_id = generated_id;
         diesel::insert_into(onsen::table)
-            .values(&new_onsen)
-            .execute(connection)
+            .values((
+                onsen::dsl::name.eq(&new_onsen.name),
+                onsen::dsl::spring_quality.eq(&new_onsen.spring_quality),
+                onsen::dsl::liquid.eq(&new_onsen.liquid),
+                onsen::dsl::ph.eq(new_onsen.ph),
+                onsen::dsl::osmotic_pressure.eq(&new_onsen.osmotic_pressure),
+                onsen::dsl::temperature.eq(&new_onsen.temperature),
+                onsen::dsl::category.eq(&new_onsen.category),
+                onsen::dsl::day_use.eq(new_onsen.day_use),
+                onsen::dsl::url.eq(&new_onsen.url),
+                onsen::dsl::img_url.eq(&new_onsen.img_url),
+                onsen::dsl::description.eq(&new_onsen.description),
+                onsen::dsl::chemical_id.eq(new_onsen.chemical_id),
+                onsen::dsl::hotel_id.eq(new_onsen.hotel_id),
+                onsen::dsl::area_id.eq(new_onsen.area_id),
+            ))
+            .traced_execute(connection)
             .expect("DB error");
 
         diesel::result::QueryResult::Ok(())
@@ -0,0 +1,68 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection,
+    diesel_model::diesel_password_reset_token::PasswordResetToken, diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{password_reset_token, refresh_token, user};
+use chrono::{NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+#[instrument(skip_all)]
+pub fn post_password_reset_token(user_id: i32, token_hash: &str, expires_at: NaiveDateTime) {
+    let _timer = db_timer("post_password_reset_token");
+    let connection = &mut establish_connection();
+    diesel::insert_into(password_reset_token::table)
+        .values((
+            password_reset_token::dsl::user_id.eq(user_id),
+            password_reset_token::dsl::token_hash.eq(token_hash),
+            password_reset_token::dsl::expires_at.eq(expires_at),
+            password_reset_token::dsl::created_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// トークンを使用済みにしてパスワードを更新する。
+/// パスワードが漏れている可能性があるので、発行済みのリフレッシュトークンはすべて失効させる。
+/// トークンが存在しない、使用済み、期限切れならfalse
+#[instrument(skip_all)]
+pub fn reset_password(token_hash: &str, hashed_password: &str) -> bool {
+    let _timer = db_timer("reset_password");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let results: Vec<PasswordResetToken> = password_reset_token::table
+                .select(PasswordResetToken::as_select())
+                .filter(password_reset_token::dsl::token_hash.eq(token_hash))
+                .traced_load(connection)
+                .expect("DB error");
+            let Some(current) = results.first() else {
+                return QueryResult::Ok(false);
+            };
+            let now = Utc::now().naive_utc();
+            if current.expires_at < now {
+                return Ok(false);
+            }
+            let updated = diesel::update(password_reset_token::table.find(current.id))
+                .filter(password_reset_token::dsl::used_at.is_null())
+                .set(password_reset_token::dsl::used_at.eq(now))
+                .traced_execute(connection)
+                .expect("DB error");
+            if updated == 0 {
+                return Ok(false);
+            }
+            diesel::update(user::table.find(current.user_id))
+                .set(user::dsl::hashed_password.eq(hashed_password))
+                .traced_execute(connection)
+                .expect("DB error");
+            diesel::update(refresh_token::table)
+                .filter(refresh_token::dsl::user_id.eq(current.user_id))
+                .filter(refresh_token::dsl::revoked_at.is_null())
+                .set(refresh_token::dsl::revoked_at.eq(now))
+                .traced_execute(connection)
+                .expect("DB error");
+            Ok(true)
+        })
+        .expect("DB error")
+}
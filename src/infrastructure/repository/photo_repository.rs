@@ -0,0 +1,218 @@
+use crate::domain::photo_entity::{move_photo, PhotoEntity, PhotoTarget, ThumbnailStatus};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_returning_id, DbConnection},
+    diesel_model::diesel_photo::Photo,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::photo;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 並び順
+#[instrument]
+pub fn get_photos(target: PhotoTarget, target_id: u32) -> Vec<PhotoEntity> {
+    let _timer = db_timer("get_photos");
+    let connection = &mut establish_connection();
+    let results: Vec<Photo> = photo::table
+        .select(Photo::as_select())
+        .filter(photo::dsl::target.eq(target.to_string()))
+        .filter(photo::dsl::target_id.eq(target_id as i32))
+        .order((photo::dsl::position, photo::dsl::id))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(PhotoEntity::from).collect()
+}
+
+/// 複数の対象の写真をまとめて読み込む。並び順
+#[instrument(skip(target_ids))]
+pub fn get_photos_by_target_ids(target: PhotoTarget, target_ids: &[u32]) -> Vec<PhotoEntity> {
+    let _timer = db_timer("get_photos_by_target_ids");
+    let connection = &mut establish_connection();
+    let target_ids: Vec<i32> = target_ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Photo> = photo::table
+        .select(Photo::as_select())
+        .filter(photo::dsl::target.eq(target.to_string()))
+        .filter(photo::dsl::target_id.eq_any(target_ids))
+        .order((photo::dsl::position, photo::dsl::id))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(PhotoEntity::from).collect()
+}
+
+/// 複数の対象のカバー写真をまとめて読み込む。カバーのない対象は含めない
+#[instrument(skip(target_ids))]
+pub fn get_cover_photos(target: PhotoTarget, target_ids: &[u32]) -> Vec<PhotoEntity> {
+    let _timer = db_timer("get_cover_photos");
+    let connection = &mut establish_connection();
+    let target_ids: Vec<i32> = target_ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Photo> = photo::table
+        .select(Photo::as_select())
+        .filter(photo::dsl::target.eq(target.to_string()))
+        .filter(photo::dsl::target_id.eq_any(target_ids))
+        .filter(photo::dsl::is_cover.eq(true))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(PhotoEntity::from).collect()
+}
+
+#[instrument]
+pub fn get_photo(id: u32) -> Option<PhotoEntity> {
+    let _timer = db_timer("get_photo");
+    let connection = &mut establish_connection();
+    let results: Vec<Photo> = photo::table
+        .select(Photo::as_select())
+        .filter(photo::dsl::id.eq(id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().next().map(PhotoEntity::from)
+}
+
+/// 末尾に追加する。カバーにするときはほかの写真のカバーを外す
+#[instrument(skip_all)]
+pub fn post_photo(photo_entity: PhotoEntity) -> PhotoEntity {
+    let _timer = db_timer("post_photo");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let positions: Vec<i32> = photo::table
+                .select(photo::dsl::position)
+                .filter(photo::dsl::target.eq(photo_entity.target.to_string()))
+                .filter(photo::dsl::target_id.eq(photo_entity.target_id as i32))
+                .traced_load(connection)?;
+            let position = positions.into_iter().max().map_or(0, |v| v + 1);
+            if photo_entity.is_cover {
+                clear_cover(connection, photo_entity.target, photo_entity.target_id)?;
+            }
+            let id = insert_returning_id!(
+                connection,
+                photo::table,
+                (
+                    photo::dsl::target.eq(photo_entity.target.to_string()),
+                    photo::dsl::target_id.eq(photo_entity.target_id as i32),
+                    photo::dsl::user_id.eq(photo_entity.user_id as i32),
+                    photo::dsl::blob_key.eq(&photo_entity.blob_key),
+                    photo::dsl::content_type.eq(photo_entity.format.to_string()),
+                    photo::dsl::byte_size.eq(photo_entity.byte_size as i32),
+                    photo::dsl::caption.eq(&photo_entity.caption),
+                    photo::dsl::position.eq(position),
+                    photo::dsl::is_cover.eq(photo_entity.is_cover),
+                    photo::dsl::created_at.eq(Utc::now().naive_utc()),
+                    photo::dsl::thumbnail_status.eq(photo_entity.thumbnail_status.to_string()),
+                )
+            );
+            QueryResult::Ok(PhotoEntity {
+                id: id as u32,
+                position: position as u32,
+                ..photo_entity
+            })
+        })
+        .expect("DB error")
+}
+
+/// キャプションとカバーを保存し、`position`番目(0始まり)に移して同じ対象の写真を並べ直す
+#[instrument(skip_all)]
+pub fn put_photo(photo_entity: &PhotoEntity) -> bool {
+    let _timer = db_timer("put_photo");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let ids: Vec<i32> = photo::table
+                .select(photo::dsl::id)
+                .filter(photo::dsl::target.eq(photo_entity.target.to_string()))
+                .filter(photo::dsl::target_id.eq(photo_entity.target_id as i32))
+                .order((photo::dsl::position, photo::dsl::id))
+                .traced_load(connection)?;
+            let mut ids: Vec<u32> = ids.into_iter().map(|v| v as u32).collect();
+            if !move_photo(&mut ids, photo_entity.id, photo_entity.position as usize) {
+                return QueryResult::Ok(false);
+            }
+            if photo_entity.is_cover {
+                clear_cover(connection, photo_entity.target, photo_entity.target_id)?;
+            }
+            for (position, id) in ids.iter().enumerate() {
+                diesel::update(photo::table.find(*id as i32))
+                    .set(photo::dsl::position.eq(position as i32))
+                    .traced_execute(connection)?;
+            }
+            diesel::update(photo::table.find(photo_entity.id as i32))
+                .set((
+                    photo::dsl::caption.eq(&photo_entity.caption),
+                    photo::dsl::is_cover.eq(photo_entity.is_cover),
+                ))
+                .traced_execute(connection)?;
+            QueryResult::Ok(true)
+        })
+        .expect("DB error")
+}
+
+#[instrument]
+pub fn delete_photo(id: u32) -> bool {
+    let _timer = db_timer("delete_photo");
+    let connection = &mut establish_connection();
+    let deleted = diesel::delete(photo::table.find(id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+    deleted > 0
+}
+
+/// 対象を削除したときに写真もまとめて削除する。`BlobStore`から消すために削除した写真を返す
+#[instrument]
+pub fn delete_photos(target: PhotoTarget, target_id: u32) -> Vec<PhotoEntity> {
+    let _timer = db_timer("delete_photos");
+    let connection = &mut establish_connection();
+    let results: Vec<Photo> = connection
+        .transaction(|connection| {
+            let filter = photo::dsl::target
+                .eq(target.to_string())
+                .and(photo::dsl::target_id.eq(target_id as i32));
+            let results: Vec<Photo> = photo::table
+                .select(Photo::as_select())
+                .filter(filter.clone())
+                .traced_load(connection)?;
+            diesel::delete(photo::table.filter(filter)).traced_execute(connection)?;
+            QueryResult::Ok(results)
+        })
+        .expect("DB error");
+    results.into_iter().map(PhotoEntity::from).collect()
+}
+
+/// サムネイルをまだ作っていない写真。古い順
+#[instrument]
+pub fn get_pending_thumbnail_photos(limit: u32) -> Vec<PhotoEntity> {
+    let _timer = db_timer("get_pending_thumbnail_photos");
+    let connection = &mut establish_connection();
+    let results: Vec<Photo> = photo::table
+        .select(Photo::as_select())
+        .filter(photo::dsl::thumbnail_status.eq(ThumbnailStatus::Pending.to_string()))
+        .order(photo::dsl::id)
+        .limit(limit as i64)
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(PhotoEntity::from).collect()
+}
+
+/// 作っている間に写真が削除されていればfalse
+#[instrument]
+pub fn put_thumbnail_status(id: u32, thumbnail_status: ThumbnailStatus) -> bool {
+    let _timer = db_timer("put_thumbnail_status");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(photo::table.find(id as i32))
+        .set(photo::dsl::thumbnail_status.eq(thumbnail_status.to_string()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+fn clear_cover(
+    connection: &mut DbConnection,
+    target: PhotoTarget,
+    target_id: u32,
+) -> QueryResult<usize> {
+    diesel::update(photo::table)
+        .filter(photo::dsl::target.eq(target.to_string()))
+        .filter(photo::dsl::target_id.eq(target_id as i32))
+        .set(photo::dsl::is_cover.eq(false))
+        .traced_execute(connection)
+}
@@ -0,0 +1,98 @@
+use crate::domain::profile_entity::ProfileEntity;
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection, diesel_model::diesel_user_profile::UserProfile,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::user_profile;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+#[instrument]
+pub fn get_profile_by_user(user_id: u32) -> Option<ProfileEntity> {
+    let _timer = db_timer("get_profile_by_user");
+    let connection = &mut establish_connection();
+    let results: Vec<UserProfile> = user_profile::table
+        .select(UserProfile::as_select())
+        .filter(user_profile::dsl::user_id.eq(user_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(ProfileEntity::from)
+}
+
+/// 公開範囲は呼び出し側で確認する
+#[instrument]
+pub fn get_profile(handle: &str) -> Option<ProfileEntity> {
+    let _timer = db_timer("get_profile");
+    let connection = &mut establish_connection();
+    let results: Vec<UserProfile> = user_profile::table
+        .select(UserProfile::as_select())
+        .filter(user_profile::dsl::handle.eq(handle))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(ProfileEntity::from)
+}
+
+/// プロフィールを作っていないユーザーは含めない
+#[instrument(skip_all)]
+pub fn get_profiles_by_user_ids(user_ids: &[u32]) -> Vec<ProfileEntity> {
+    let _timer = db_timer("get_profiles_by_user_ids");
+    let connection = &mut establish_connection();
+    let user_ids: Vec<i32> = user_ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<UserProfile> = user_profile::table
+        .select(UserProfile::as_select())
+        .filter(user_profile::dsl::user_id.eq_any(user_ids))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(ProfileEntity::from).collect()
+}
+
+/// ユーザーのプロフィールがあれば更新し、なければ作る。ハンドルをほかの人が使っていればfalse。
+/// 同時に同じハンドルで保存されたときは一意制約に引っかかった方がfalse
+#[instrument(skip_all)]
+pub fn put_profile(profile: &ProfileEntity) -> bool {
+    let _timer = db_timer("put_profile");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let values = (
+        user_profile::dsl::handle.eq(&profile.handle),
+        user_profile::dsl::display_name.eq(&profile.display_name),
+        user_profile::dsl::bio.eq(&profile.bio),
+        user_profile::dsl::profile_visibility.eq(profile.privacy.profile.to_string()),
+        user_profile::dsl::visits_visibility.eq(profile.privacy.visits.to_string()),
+        user_profile::dsl::trips_visibility.eq(profile.privacy.trips.to_string()),
+        user_profile::dsl::reviews_visibility.eq(profile.privacy.reviews.to_string()),
+        user_profile::dsl::approve_followers.eq(profile.privacy.approve_followers),
+        user_profile::dsl::updated_at.eq(now),
+    );
+    let result = connection.transaction(|connection| {
+        let taken: i64 = user_profile::table
+            .filter(user_profile::dsl::handle.eq(&profile.handle))
+            .filter(user_profile::dsl::user_id.ne(profile.user_id as i32))
+            .count()
+            .get_result(connection)?;
+        if taken > 0 {
+            return QueryResult::Ok(false);
+        }
+        let updated = diesel::update(user_profile::table)
+            .filter(user_profile::dsl::user_id.eq(profile.user_id as i32))
+            .set(values.clone())
+            .traced_execute(connection)?;
+        if updated == 0 {
+            diesel::insert_into(user_profile::table)
+                .values((
+                    user_profile::dsl::user_id.eq(profile.user_id as i32),
+                    values,
+                    user_profile::dsl::created_at.eq(now),
+                ))
+                .traced_execute(connection)?;
+        }
+        QueryResult::Ok(true)
+    });
+    match result {
+        Ok(saved) => saved,
+        Err(result::Error::DatabaseError(result::DatabaseErrorKind::UniqueViolation, _)) => false,
+        Err(e) => panic!("DB error: {}", e),
+    }
+}
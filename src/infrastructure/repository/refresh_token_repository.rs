@@ -0,0 +1,135 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, DbConnection},
+    diesel_model::diesel_refresh_token::RefreshToken,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::refresh_token;
+use chrono::{NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+pub enum RefreshTokenRotation {
+    /// 新しいリフレッシュトークンを発行した
+    Rotated { user_id: i32, mfa: bool },
+    /// ローテーション済みのトークンが再利用されたのでファミリーごと失効させた
+    Reused,
+    /// 存在しない、失効済み、期限切れ
+    Invalid,
+}
+
+#[instrument(skip_all)]
+pub fn post_refresh_token(
+    user_id: i32,
+    family_id: &str,
+    token_hash: &str,
+    expires_at: NaiveDateTime,
+    mfa: bool,
+) {
+    let _timer = db_timer("post_refresh_token");
+    let connection = &mut establish_connection();
+    insert_refresh_token(connection, user_id, family_id, token_hash, expires_at, mfa);
+}
+
+/// リフレッシュトークンを使用済みにして、同じファミリーで新しいトークンを発行する
+#[instrument(skip_all)]
+pub fn rotate_refresh_token(
+    token_hash: &str,
+    new_token_hash: &str,
+    new_expires_at: NaiveDateTime,
+) -> RefreshTokenRotation {
+    let _timer = db_timer("rotate_refresh_token");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let Some(current) = find_refresh_token(connection, token_hash) else {
+                return QueryResult::Ok(RefreshTokenRotation::Invalid);
+            };
+            let now = Utc::now().naive_utc();
+            if current.revoked_at.is_some() {
+                return Ok(RefreshTokenRotation::Invalid);
+            }
+            if current.used_at.is_some() {
+                revoke_family(connection, &current.family_id, now);
+                return Ok(RefreshTokenRotation::Reused);
+            }
+            if current.expires_at < now {
+                return Ok(RefreshTokenRotation::Invalid);
+            }
+            // 同時に同じトークンでリフレッシュされた場合は片方だけを成功させて、もう片方は再利用として扱う
+            let updated = diesel::update(refresh_token::table.find(current.id))
+                .filter(refresh_token::dsl::used_at.is_null())
+                .filter(refresh_token::dsl::revoked_at.is_null())
+                .set(refresh_token::dsl::used_at.eq(now))
+                .traced_execute(connection)
+                .expect("DB error");
+            if updated == 0 {
+                revoke_family(connection, &current.family_id, now);
+                return Ok(RefreshTokenRotation::Reused);
+            }
+            insert_refresh_token(
+                connection,
+                current.user_id,
+                &current.family_id,
+                new_token_hash,
+                new_expires_at,
+                current.mfa,
+            );
+            Ok(RefreshTokenRotation::Rotated {
+                user_id: current.user_id,
+                mfa: current.mfa,
+            })
+        })
+        .expect("DB error")
+}
+
+/// トークンが属するファミリーをすべて失効させる。トークンが見つからなければfalse
+#[instrument(skip_all)]
+pub fn revoke_refresh_token_family(token_hash: &str) -> bool {
+    let _timer = db_timer("revoke_refresh_token_family");
+    let connection = &mut establish_connection();
+    let Some(current) = find_refresh_token(connection, token_hash) else {
+        return false;
+    };
+    revoke_family(connection, &current.family_id, Utc::now().naive_utc());
+    true
+}
+
+fn find_refresh_token(connection: &mut DbConnection, token_hash: &str) -> Option<RefreshToken> {
+    let results: Vec<RefreshToken> = refresh_token::table
+        .select(RefreshToken::as_select())
+        .filter(refresh_token::dsl::token_hash.eq(token_hash))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned()
+}
+
+fn insert_refresh_token(
+    connection: &mut DbConnection,
+    user_id: i32,
+    family_id: &str,
+    token_hash: &str,
+    expires_at: NaiveDateTime,
+    mfa: bool,
+) {
+    diesel::insert_into(refresh_token::table)
+        .values((
+            refresh_token::dsl::user_id.eq(user_id),
+            refresh_token::dsl::family_id.eq(family_id),
+            refresh_token::dsl::token_hash.eq(token_hash),
+            refresh_token::dsl::expires_at.eq(expires_at),
+            refresh_token::dsl::created_at.eq(Utc::now().naive_utc()),
+            refresh_token::dsl::mfa.eq(mfa),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+fn revoke_family(connection: &mut DbConnection, family_id: &str, now: NaiveDateTime) {
+    diesel::update(refresh_token::table)
+        .filter(refresh_token::dsl::family_id.eq(family_id))
+        .filter(refresh_token::dsl::revoked_at.is_null())
+        .set(refresh_token::dsl::revoked_at.eq(now))
+        .traced_execute(connection)
+        .expect("DB error");
+}
@@ -0,0 +1,335 @@
+use crate::domain::feed::{FeedBound, FeedContent, FeedItem};
+use crate::domain::review_entity::{
+    HotelRatingSummary, HotelReviewEntity, OnsenRatingSummary, OnsenReviewEntity, ReviewStatus,
+};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection,
+    diesel_model::diesel_review::{HotelReview, OnsenReview},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{hotel_review, onsen_review};
+use chrono::Utc;
+use diesel::*;
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// 温泉ごとの評価の集計。評価のない温泉は含めない。却下された口コミは数えない
+#[instrument(skip_all)]
+pub fn get_onsen_rating_summaries(onsen_ids: &[u32]) -> HashMap<u32, OnsenRatingSummary> {
+    let _timer = db_timer("get_onsen_rating_summaries");
+    let connection = &mut establish_connection();
+    let onsen_ids: Vec<i32> = onsen_ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<OnsenReview> = onsen_review::table
+        .select(OnsenReview::as_select())
+        .filter(onsen_review::dsl::onsen_id.eq_any(onsen_ids))
+        .filter(onsen_review::dsl::status.ne(ReviewStatus::Rejected.to_string()))
+        .traced_load(connection)
+        .expect("DB error");
+    let mut reviews: HashMap<u32, Vec<OnsenReviewEntity>> = HashMap::new();
+    for review in results {
+        let review = OnsenReviewEntity::from(review);
+        reviews.entry(review.onsen_id).or_default().push(review);
+    }
+    reviews
+        .into_iter()
+        .map(|(onsen_id, v)| (onsen_id, OnsenRatingSummary::aggregate(&v)))
+        .collect()
+}
+
+/// 宿ごとの評価の集計。評価のない宿は含めない。却下された口コミは数えない
+#[instrument(skip_all)]
+pub fn get_hotel_rating_summaries(hotel_ids: &[u32]) -> HashMap<u32, HotelRatingSummary> {
+    let _timer = db_timer("get_hotel_rating_summaries");
+    let connection = &mut establish_connection();
+    let hotel_ids: Vec<i32> = hotel_ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<HotelReview> = hotel_review::table
+        .select(HotelReview::as_select())
+        .filter(hotel_review::dsl::hotel_id.eq_any(hotel_ids))
+        .filter(hotel_review::dsl::status.ne(ReviewStatus::Rejected.to_string()))
+        .traced_load(connection)
+        .expect("DB error");
+    let mut reviews: HashMap<u32, Vec<HotelReviewEntity>> = HashMap::new();
+    for review in results {
+        let review = HotelReviewEntity::from(review);
+        reviews.entry(review.hotel_id).or_default().push(review);
+    }
+    reviews
+        .into_iter()
+        .map(|(hotel_id, v)| (hotel_id, HotelRatingSummary::aggregate(&v)))
+        .collect()
+}
+
+/// `onsen_id`を指定しなければすべての温泉の口コミ。新しい順
+#[instrument]
+pub fn get_onsen_reviews(onsen_id: Option<u32>, status: ReviewStatus) -> Vec<OnsenReviewEntity> {
+    let _timer = db_timer("get_onsen_reviews");
+    let connection = &mut establish_connection();
+    let mut query = onsen_review::table.into_boxed();
+    if let Some(onsen_id) = onsen_id {
+        query = query.filter(onsen_review::dsl::onsen_id.eq(onsen_id as i32));
+    }
+    let results: Vec<OnsenReview> = query
+        .select(OnsenReview::as_select())
+        .filter(onsen_review::dsl::status.eq(status.to_string()))
+        .order(onsen_review::dsl::updated_at.desc())
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(OnsenReviewEntity::from).collect()
+}
+
+/// `hotel_id`を指定しなければすべての宿の口コミ。新しい順
+#[instrument]
+pub fn get_hotel_reviews(hotel_id: Option<u32>, status: ReviewStatus) -> Vec<HotelReviewEntity> {
+    let _timer = db_timer("get_hotel_reviews");
+    let connection = &mut establish_connection();
+    let mut query = hotel_review::table.into_boxed();
+    if let Some(hotel_id) = hotel_id {
+        query = query.filter(hotel_review::dsl::hotel_id.eq(hotel_id as i32));
+    }
+    let results: Vec<HotelReview> = query
+        .select(HotelReview::as_select())
+        .filter(hotel_review::dsl::status.eq(status.to_string()))
+        .order(hotel_review::dsl::updated_at.desc())
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(HotelReviewEntity::from).collect()
+}
+
+/// 指定したユーザーの承認済みの口コミを更新の新しい順に読み込む。`before`より前のものだけ
+#[instrument(skip(user_ids))]
+pub fn get_recent_onsen_reviews(
+    user_ids: &[u32],
+    before: Option<FeedBound>,
+    limit: u32,
+) -> Vec<FeedItem> {
+    let _timer = db_timer("get_recent_onsen_reviews");
+    let connection = &mut establish_connection();
+    let user_ids: Vec<i32> = user_ids.iter().map(|v| *v as i32).collect();
+    let mut query = onsen_review::table.into_boxed();
+    if let Some(before) = before {
+        query = match before.id {
+            Some(id) => query.filter(
+                onsen_review::dsl::updated_at.lt(before.posted_at).or(
+                    onsen_review::dsl::updated_at
+                        .eq(before.posted_at)
+                        .and(onsen_review::dsl::id.lt(id as i32)),
+                ),
+            ),
+            None => query.filter(onsen_review::dsl::updated_at.le(before.posted_at)),
+        };
+    }
+    let results: Vec<OnsenReview> = query
+        .select(OnsenReview::as_select())
+        .filter(onsen_review::dsl::user_id.eq_any(user_ids))
+        .filter(onsen_review::dsl::status.eq(ReviewStatus::Approved.to_string()))
+        .order((
+            onsen_review::dsl::updated_at.desc(),
+            onsen_review::dsl::id.desc(),
+        ))
+        .limit(limit as i64)
+        .traced_load(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .map(|v| {
+            FeedItem::new(
+                v.updated_at,
+                FeedContent::OnsenReview(OnsenReviewEntity::from(v)),
+            )
+        })
+        .collect()
+}
+
+/// 指定したユーザーの承認済みの口コミを更新の新しい順に読み込む。`before`より前のものだけ
+#[instrument(skip(user_ids))]
+pub fn get_recent_hotel_reviews(
+    user_ids: &[u32],
+    before: Option<FeedBound>,
+    limit: u32,
+) -> Vec<FeedItem> {
+    let _timer = db_timer("get_recent_hotel_reviews");
+    let connection = &mut establish_connection();
+    let user_ids: Vec<i32> = user_ids.iter().map(|v| *v as i32).collect();
+    let mut query = hotel_review::table.into_boxed();
+    if let Some(before) = before {
+        query = match before.id {
+            Some(id) => query.filter(
+                hotel_review::dsl::updated_at.lt(before.posted_at).or(
+                    hotel_review::dsl::updated_at
+                        .eq(before.posted_at)
+                        .and(hotel_review::dsl::id.lt(id as i32)),
+                ),
+            ),
+            None => query.filter(hotel_review::dsl::updated_at.le(before.posted_at)),
+        };
+    }
+    let results: Vec<HotelReview> = query
+        .select(HotelReview::as_select())
+        .filter(hotel_review::dsl::user_id.eq_any(user_ids))
+        .filter(hotel_review::dsl::status.eq(ReviewStatus::Approved.to_string()))
+        .order((
+            hotel_review::dsl::updated_at.desc(),
+            hotel_review::dsl::id.desc(),
+        ))
+        .limit(limit as i64)
+        .traced_load(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .map(|v| {
+            FeedItem::new(
+                v.updated_at,
+                FeedContent::HotelReview(HotelReviewEntity::from(v)),
+            )
+        })
+        .collect()
+}
+
+#[instrument]
+pub fn get_onsen_review(user_id: u32, onsen_id: u32) -> Option<OnsenReviewEntity> {
+    let _timer = db_timer("get_onsen_review");
+    let connection = &mut establish_connection();
+    let results: Vec<OnsenReview> = onsen_review::table
+        .select(OnsenReview::as_select())
+        .filter(onsen_review::dsl::user_id.eq(user_id as i32))
+        .filter(onsen_review::dsl::onsen_id.eq(onsen_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(OnsenReviewEntity::from)
+}
+
+#[instrument]
+pub fn get_hotel_review(user_id: u32, hotel_id: u32) -> Option<HotelReviewEntity> {
+    let _timer = db_timer("get_hotel_review");
+    let connection = &mut establish_connection();
+    let results: Vec<HotelReview> = hotel_review::table
+        .select(HotelReview::as_select())
+        .filter(hotel_review::dsl::user_id.eq(user_id as i32))
+        .filter(hotel_review::dsl::hotel_id.eq(hotel_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(HotelReviewEntity::from)
+}
+
+/// ユーザーの口コミがあれば更新し、なければ登録する
+#[instrument(skip_all)]
+pub fn put_onsen_review(review: &OnsenReviewEntity) {
+    let _timer = db_timer("put_onsen_review");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let values = (
+        onsen_review::dsl::overall.eq(review.overall as i32),
+        onsen_review::dsl::water_quality.eq(review.water_quality as i32),
+        onsen_review::dsl::cleanliness.eq(review.cleanliness as i32),
+        onsen_review::dsl::view.eq(review.view as i32),
+        onsen_review::dsl::crowding.eq(review.crowding as i32),
+        onsen_review::dsl::comment.eq(&review.comment),
+        onsen_review::dsl::status.eq(review.status.to_string()),
+        onsen_review::dsl::updated_at.eq(now),
+    );
+    connection
+        .transaction(|connection| {
+            let updated = diesel::update(onsen_review::table)
+                .filter(onsen_review::dsl::user_id.eq(review.user_id as i32))
+                .filter(onsen_review::dsl::onsen_id.eq(review.onsen_id as i32))
+                .set(values.clone())
+                .traced_execute(connection)?;
+            if updated == 0 {
+                diesel::insert_into(onsen_review::table)
+                    .values((
+                        onsen_review::dsl::user_id.eq(review.user_id as i32),
+                        onsen_review::dsl::onsen_id.eq(review.onsen_id as i32),
+                        values,
+                        onsen_review::dsl::created_at.eq(now),
+                    ))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+/// ユーザーの口コミがあれば更新し、なければ登録する
+#[instrument(skip_all)]
+pub fn put_hotel_review(review: &HotelReviewEntity) {
+    let _timer = db_timer("put_hotel_review");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let values = (
+        hotel_review::dsl::room.eq(review.room as i32),
+        hotel_review::dsl::food.eq(review.food as i32),
+        hotel_review::dsl::bath.eq(review.bath as i32),
+        hotel_review::dsl::comment.eq(&review.comment),
+        hotel_review::dsl::status.eq(review.status.to_string()),
+        hotel_review::dsl::updated_at.eq(now),
+    );
+    connection
+        .transaction(|connection| {
+            let updated = diesel::update(hotel_review::table)
+                .filter(hotel_review::dsl::user_id.eq(review.user_id as i32))
+                .filter(hotel_review::dsl::hotel_id.eq(review.hotel_id as i32))
+                .set(values.clone())
+                .traced_execute(connection)?;
+            if updated == 0 {
+                diesel::insert_into(hotel_review::table)
+                    .values((
+                        hotel_review::dsl::user_id.eq(review.user_id as i32),
+                        hotel_review::dsl::hotel_id.eq(review.hotel_id as i32),
+                        values,
+                        hotel_review::dsl::created_at.eq(now),
+                    ))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+#[instrument]
+pub fn delete_onsen_review(user_id: u32, onsen_id: u32) -> bool {
+    let _timer = db_timer("delete_onsen_review");
+    let connection = &mut establish_connection();
+    let deleted = diesel::delete(onsen_review::table)
+        .filter(onsen_review::dsl::user_id.eq(user_id as i32))
+        .filter(onsen_review::dsl::onsen_id.eq(onsen_id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+    deleted > 0
+}
+
+#[instrument]
+pub fn delete_hotel_review(user_id: u32, hotel_id: u32) -> bool {
+    let _timer = db_timer("delete_hotel_review");
+    let connection = &mut establish_connection();
+    let deleted = diesel::delete(hotel_review::table)
+        .filter(hotel_review::dsl::user_id.eq(user_id as i32))
+        .filter(hotel_review::dsl::hotel_id.eq(hotel_id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+    deleted > 0
+}
+
+/// 口コミの公開状態を変える。口コミが存在しなければfalse
+#[instrument]
+pub fn put_onsen_review_status(id: u32, status: ReviewStatus) -> bool {
+    let _timer = db_timer("put_onsen_review_status");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(onsen_review::table.find(id as i32))
+        .set(onsen_review::dsl::status.eq(status.to_string()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+/// 口コミの公開状態を変える。口コミが存在しなければfalse
+#[instrument]
+pub fn put_hotel_review_status(id: u32, status: ReviewStatus) -> bool {
+    let _timer = db_timer("put_hotel_review_status");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(hotel_review::table.find(id as i32))
+        .set(hotel_review::dsl::status.eq(status.to_string()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
@@ -0,0 +1,24 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection, diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::signin_audit_log;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 失敗したサインインを記録する
+#[instrument(skip_all)]
+pub fn post_signin_failure(email: &str, ip_address: Option<String>, reason: &str) {
+    let _timer = db_timer("post_signin_failure");
+    let connection = &mut establish_connection();
+    diesel::insert_into(signin_audit_log::table)
+        .values((
+            signin_audit_log::dsl::email.eq(email),
+            signin_audit_log::dsl::ip_address.eq(ip_address),
+            signin_audit_log::dsl::reason.eq(reason),
+            signin_audit_log::dsl::created_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+}
@@ -0,0 +1,227 @@
+use crate::domain::trip_entity::{TripDayEntity, TripEntity};
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_returning_id, DbConnection},
+    diesel_model::diesel_trip::{Trip, TripDay, TripDayArea, TripDayVisit},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{trip, trip_day, trip_day_area, trip_day_visit};
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 新しい順
+#[instrument]
+pub fn get_trips(user_id: u32) -> Vec<TripEntity> {
+    let _timer = db_timer("get_trips");
+    let connection = &mut establish_connection();
+    let trips: Vec<Trip> = trip::table
+        .select(Trip::as_select())
+        .filter(trip::dsl::user_id.eq(user_id as i32))
+        .order((trip::dsl::start_on.desc(), trip::dsl::id.desc()))
+        .traced_load(connection)
+        .expect("DB error");
+    load_trip_entities(connection, trips)
+}
+
+/// 公開・非公開にかかわらず返すので、見せてよいかは呼び出し側で確認する
+#[instrument]
+pub fn get_trip(id: u32) -> Option<TripEntity> {
+    let _timer = db_timer("get_trip");
+    let connection = &mut establish_connection();
+    let trips: Vec<Trip> = trip::table
+        .select(Trip::as_select())
+        .filter(trip::dsl::id.eq(id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    load_trip_entities(connection, trips).into_iter().next()
+}
+
+#[instrument(skip_all)]
+pub fn post_trip(trip_entity: TripEntity) -> TripEntity {
+    let _timer = db_timer("post_trip");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let id = connection
+        .transaction(|connection| {
+            let id = insert_returning_id!(
+                connection,
+                trip::table,
+                (
+                    trip::dsl::user_id.eq(trip_entity.user_id as i32),
+                    trip::dsl::title.eq(&trip_entity.title),
+                    trip::dsl::start_on.eq(trip_entity.start_on),
+                    trip::dsl::end_on.eq(trip_entity.end_on),
+                    trip::dsl::note.eq(&trip_entity.note),
+                    trip::dsl::is_public.eq(trip_entity.is_public),
+                    trip::dsl::created_at.eq(now),
+                    trip::dsl::updated_at.eq(now),
+                )
+            );
+            insert_trip_days(connection, id, &trip_entity.days)?;
+            QueryResult::Ok(id)
+        })
+        .expect("DB error");
+    TripEntity {
+        id: id as u32,
+        ..trip_entity
+    }
+}
+
+/// 日ごとの内容は作り直す。ユーザーの旅行が存在しなければfalse
+#[instrument(skip_all)]
+pub fn put_trip(trip_entity: TripEntity) -> bool {
+    let _timer = db_timer("put_trip");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let id = trip_entity.id as i32;
+            let updated = diesel::update(trip::table.find(id))
+                .filter(trip::dsl::user_id.eq(trip_entity.user_id as i32))
+                .set((
+                    trip::dsl::title.eq(&trip_entity.title),
+                    trip::dsl::start_on.eq(trip_entity.start_on),
+                    trip::dsl::end_on.eq(trip_entity.end_on),
+                    trip::dsl::note.eq(&trip_entity.note),
+                    trip::dsl::is_public.eq(trip_entity.is_public),
+                    trip::dsl::updated_at.eq(Utc::now().naive_utc()),
+                ))
+                .traced_execute(connection)?;
+            if updated == 0 {
+                return QueryResult::Ok(false);
+            }
+            delete_trip_days(connection, id)?;
+            insert_trip_days(connection, id, &trip_entity.days)?;
+            Ok(true)
+        })
+        .expect("DB error")
+}
+
+#[instrument]
+pub fn delete_trip(user_id: u32, id: u32) -> bool {
+    let _timer = db_timer("delete_trip");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            let owned: i64 = trip::table
+                .filter(trip::dsl::id.eq(id as i32))
+                .filter(trip::dsl::user_id.eq(user_id as i32))
+                .count()
+                .get_result(connection)?;
+            if owned == 0 {
+                return QueryResult::Ok(false);
+            }
+            delete_trip_days(connection, id as i32)?;
+            diesel::delete(trip::table.find(id as i32)).traced_execute(connection)?;
+            Ok(true)
+        })
+        .expect("DB error")
+}
+
+fn insert_trip_days(
+    connection: &mut DbConnection,
+    trip_id: i32,
+    days: &[TripDayEntity],
+) -> QueryResult<()> {
+    for (index, day) in days.iter().enumerate() {
+        let trip_day_id = insert_returning_id!(
+            connection,
+            trip_day::table,
+            (
+                trip_day::dsl::trip_id.eq(trip_id),
+                trip_day::dsl::day_number.eq(index as i32 + 1),
+                trip_day::dsl::hotel_id.eq(day.hotel_id.map(|v| v as i32)),
+                trip_day::dsl::note.eq(&day.note),
+            )
+        );
+        for (position, visit_id) in day.visit_ids.iter().enumerate() {
+            diesel::insert_into(trip_day_visit::table)
+                .values((
+                    trip_day_visit::dsl::trip_day_id.eq(trip_day_id),
+                    trip_day_visit::dsl::visit_id.eq(*visit_id as i32),
+                    trip_day_visit::dsl::position.eq(position as i32),
+                ))
+                .traced_execute(connection)?;
+        }
+        for (position, area_id) in day.area_ids.iter().enumerate() {
+            diesel::insert_into(trip_day_area::table)
+                .values((
+                    trip_day_area::dsl::trip_day_id.eq(trip_day_id),
+                    trip_day_area::dsl::area_id.eq(*area_id as i32),
+                    trip_day_area::dsl::position.eq(position as i32),
+                ))
+                .traced_execute(connection)?;
+        }
+    }
+    Ok(())
+}
+
+fn delete_trip_days(connection: &mut DbConnection, trip_id: i32) -> QueryResult<()> {
+    let trip_day_ids = trip_day::table
+        .select(trip_day::dsl::id)
+        .filter(trip_day::dsl::trip_id.eq(trip_id));
+    diesel::delete(trip_day_visit::table)
+        .filter(trip_day_visit::dsl::trip_day_id.eq_any(trip_day_ids))
+        .traced_execute(connection)?;
+    diesel::delete(trip_day_area::table)
+        .filter(trip_day_area::dsl::trip_day_id.eq_any(trip_day_ids))
+        .traced_execute(connection)?;
+    diesel::delete(trip_day::table)
+        .filter(trip_day::dsl::trip_id.eq(trip_id))
+        .traced_execute(connection)?;
+    Ok(())
+}
+
+/// 旅行ごとに日と、日ごとの入浴記録・温泉地をまとめて読み込む
+fn load_trip_entities(connection: &mut DbConnection, trips: Vec<Trip>) -> Vec<TripEntity> {
+    let trip_ids: Vec<i32> = trips.iter().map(|v| v.id).collect();
+    let days: Vec<TripDay> = trip_day::table
+        .select(TripDay::as_select())
+        .filter(trip_day::dsl::trip_id.eq_any(&trip_ids))
+        .order((trip_day::dsl::trip_id, trip_day::dsl::day_number))
+        .traced_load(connection)
+        .expect("DB error");
+    let trip_day_ids: Vec<i32> = days.iter().map(|v| v.id).collect();
+    let day_visits: Vec<TripDayVisit> = trip_day_visit::table
+        .select(TripDayVisit::as_select())
+        .filter(trip_day_visit::dsl::trip_day_id.eq_any(&trip_day_ids))
+        .order(trip_day_visit::dsl::position)
+        .traced_load(connection)
+        .expect("DB error");
+    let day_areas: Vec<TripDayArea> = trip_day_area::table
+        .select(TripDayArea::as_select())
+        .filter(trip_day_area::dsl::trip_day_id.eq_any(&trip_day_ids))
+        .order(trip_day_area::dsl::position)
+        .traced_load(connection)
+        .expect("DB error");
+    trips
+        .into_iter()
+        .map(|trip| TripEntity {
+            id: trip.id as u32,
+            user_id: trip.user_id as u32,
+            title: trip.title,
+            start_on: trip.start_on,
+            end_on: trip.end_on,
+            note: trip.note,
+            is_public: trip.is_public,
+            days: days
+                .iter()
+                .filter(|day| day.trip_id == trip.id)
+                .map(|day| TripDayEntity {
+                    hotel_id: day.hotel_id.map(|v| v as u32),
+                    visit_ids: day_visits
+                        .iter()
+                        .filter(|v| v.trip_day_id == day.id)
+                        .map(|v| v.visit_id as u32)
+                        .collect(),
+                    area_ids: day_areas
+                        .iter()
+                        .filter(|v| v.trip_day_id == day.id)
+                        .map(|v| v.area_id as u32)
+                        .collect(),
+                    note: day.note.clone(),
+                })
+                .collect(),
+        })
+        .collect()
+}
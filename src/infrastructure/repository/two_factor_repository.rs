@@ -0,0 +1,184 @@
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::establish_connection,
+    diesel_model::diesel_two_factor::{TwoFactorChallenge, UserTotp},
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::{totp_backup_code, two_factor_challenge, user_totp};
+use chrono::{NaiveDateTime, Utc};
+use diesel::*;
+use tracing::instrument;
+
+/// 2段階認証の確認コードを間違えられる回数
+const MAX_CHALLENGE_ATTEMPTS: i32 = 5;
+
+#[instrument]
+pub fn get_user_totp(user_id: i32) -> Option<UserTotp> {
+    let _timer = db_timer("get_user_totp");
+    let connection = &mut establish_connection();
+    let results: Vec<UserTotp> = user_totp::table
+        .select(UserTotp::as_select())
+        .filter(user_totp::dsl::user_id.eq(user_id))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned()
+}
+
+/// 有効化前の秘密鍵を登録する。有効化前の秘密鍵があれば置き換える
+#[instrument(skip_all)]
+pub fn put_pending_user_totp(user_id: i32, secret: &str) {
+    let _timer = db_timer("put_pending_user_totp");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            diesel::delete(user_totp::table)
+                .filter(user_totp::dsl::user_id.eq(user_id))
+                .filter(user_totp::dsl::enabled_at.is_null())
+                .traced_execute(connection)?;
+            diesel::insert_into(user_totp::table)
+                .values((
+                    user_totp::dsl::user_id.eq(user_id),
+                    user_totp::dsl::secret.eq(secret),
+                    user_totp::dsl::created_at.eq(Utc::now().naive_utc()),
+                ))
+                .traced_execute(connection)?;
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+/// TOTPを有効にして、バックアップコードを作り直す
+#[instrument(skip_all)]
+pub fn enable_user_totp(user_id: i32, last_used_step: i64, backup_code_hashes: &[String]) {
+    let _timer = db_timer("enable_user_totp");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    connection
+        .transaction(|connection| {
+            diesel::update(user_totp::table)
+                .filter(user_totp::dsl::user_id.eq(user_id))
+                .set((
+                    user_totp::dsl::enabled_at.eq(now),
+                    user_totp::dsl::last_used_step.eq(last_used_step),
+                ))
+                .traced_execute(connection)?;
+            diesel::delete(totp_backup_code::table)
+                .filter(totp_backup_code::dsl::user_id.eq(user_id))
+                .traced_execute(connection)?;
+            for code_hash in backup_code_hashes {
+                diesel::insert_into(totp_backup_code::table)
+                    .values((
+                        totp_backup_code::dsl::user_id.eq(user_id),
+                        totp_backup_code::dsl::code_hash.eq(code_hash),
+                        totp_backup_code::dsl::created_at.eq(now),
+                    ))
+                    .traced_execute(connection)?;
+            }
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+#[instrument]
+pub fn delete_user_totp(user_id: i32) {
+    let _timer = db_timer("delete_user_totp");
+    let connection = &mut establish_connection();
+    connection
+        .transaction(|connection| {
+            diesel::delete(totp_backup_code::table)
+                .filter(totp_backup_code::dsl::user_id.eq(user_id))
+                .traced_execute(connection)?;
+            diesel::delete(user_totp::table)
+                .filter(user_totp::dsl::user_id.eq(user_id))
+                .traced_execute(connection)?;
+            QueryResult::Ok(())
+        })
+        .expect("DB error");
+}
+
+/// 使ったタイムステップを記録する。同時に同じコードが使われた場合は片方だけ成功する
+#[instrument]
+pub fn put_totp_last_used_step(user_id: i32, step: i64) -> bool {
+    let _timer = db_timer("put_totp_last_used_step");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(user_totp::table)
+        .filter(user_totp::dsl::user_id.eq(user_id))
+        .filter(
+            user_totp::dsl::last_used_step
+                .is_null()
+                .or(user_totp::dsl::last_used_step.lt(step)),
+        )
+        .set(user_totp::dsl::last_used_step.eq(step))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+/// 未使用のバックアップコードなら使用済みにしてtrue
+#[instrument(skip_all)]
+pub fn use_totp_backup_code(user_id: i32, code_hash: &str) -> bool {
+    let _timer = db_timer("use_totp_backup_code");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(totp_backup_code::table)
+        .filter(totp_backup_code::dsl::user_id.eq(user_id))
+        .filter(totp_backup_code::dsl::code_hash.eq(code_hash))
+        .filter(totp_backup_code::dsl::used_at.is_null())
+        .set(totp_backup_code::dsl::used_at.eq(Utc::now().naive_utc()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+#[instrument(skip_all)]
+pub fn post_two_factor_challenge(user_id: i32, token_hash: &str, expires_at: NaiveDateTime) {
+    let _timer = db_timer("post_two_factor_challenge");
+    let connection = &mut establish_connection();
+    diesel::insert_into(two_factor_challenge::table)
+        .values((
+            two_factor_challenge::dsl::user_id.eq(user_id),
+            two_factor_challenge::dsl::token_hash.eq(token_hash),
+            two_factor_challenge::dsl::expires_at.eq(expires_at),
+            two_factor_challenge::dsl::created_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// 未使用、期限内で、間違えた回数が上限に達していないチャレンジ
+#[instrument(skip_all)]
+pub fn get_two_factor_challenge(token_hash: &str) -> Option<TwoFactorChallenge> {
+    let _timer = db_timer("get_two_factor_challenge");
+    let connection = &mut establish_connection();
+    let results: Vec<TwoFactorChallenge> = two_factor_challenge::table
+        .select(TwoFactorChallenge::as_select())
+        .filter(two_factor_challenge::dsl::token_hash.eq(token_hash))
+        .filter(two_factor_challenge::dsl::used_at.is_null())
+        .filter(two_factor_challenge::dsl::expires_at.gt(Utc::now().naive_utc()))
+        .filter(two_factor_challenge::dsl::attempts.lt(MAX_CHALLENGE_ATTEMPTS))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned()
+}
+
+#[instrument]
+pub fn put_two_factor_challenge_failure(id: i32) {
+    let _timer = db_timer("put_two_factor_challenge_failure");
+    let connection = &mut establish_connection();
+    diesel::update(two_factor_challenge::table.find(id))
+        .set(two_factor_challenge::dsl::attempts.eq(two_factor_challenge::dsl::attempts + 1))
+        .traced_execute(connection)
+        .expect("DB error");
+}
+
+/// チャレンジを使用済みにする。すでに使われていればfalse
+#[instrument]
+pub fn use_two_factor_challenge(id: i32) -> bool {
+    let _timer = db_timer("use_two_factor_challenge");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(two_factor_challenge::table.find(id))
+        .filter(two_factor_challenge::dsl::used_at.is_null())
+        .set(two_factor_challenge::dsl::used_at.eq(Utc::now().naive_utc()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
@@ -1,37 +1,94 @@
-use super::super::mysql::diesel_connection::establish_connection;
-use crate::{infrastructure::mysql::diesel_model::diesel_user::User, schema::user};
+use super::super::rdb::diesel_connection::{establish_connection, insert_returning_id};
+use super::super::rdb::diesel_trace::TracedRunQueryDsl;
+use crate::domain::role::Role;
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::repository::list_repository;
+use crate::{infrastructure::rdb::diesel_model::diesel_user::User, schema::user};
 use diesel::*;
+use tracing::instrument;
 
+#[instrument(skip_all)]
 pub fn exists_user(email: &str) -> bool {
+    let _timer = db_timer("exists_user");
     let connection = &mut establish_connection();
     let results: Vec<User> = user::table
         .select(User::as_select())
         .filter(user::dsl::email.eq(email))
-        .load(connection)
+        .traced_load(connection)
         .expect("DB Error");
     !results.is_empty()
 }
 
+#[instrument(skip_all)]
 pub fn get_user(email: &str) -> Option<User> {
+    let _timer = db_timer("get_user");
     let connection = &mut establish_connection();
     let results: Vec<User> = user::table
         .select(User::as_select())
         .filter(user::dsl::email.eq(email))
-        .load(connection)
+        .traced_load(connection)
         .expect("DB Error");
     results.first().map(|v| v.clone())
 }
 
+/// 既定のリストも同じトランザクションで作る
+#[instrument(skip_all)]
 pub fn post_user(email: &str, hashed_password: &str) {
+    let _timer = db_timer("post_user");
     let new_user = User {
         id: 0,
         email: email.to_string(),
         hashed_password: hashed_password.to_string(),
-        role: "user".to_string(),
+        role: Role::Viewer.to_string(),
+        verified_at: None,
     };
     let connection = &mut establish_connection();
-    diesel::insert_into(user::table)
-        .values(&new_user)
-        .execute(connection)
+    connection
+        .transaction(|connection| {
+            let user_id = insert_returning_id!(
+                connection,
+                user::table,
+                (
+                    user::dsl::email.eq(&new_user.email),
+                    user::dsl::hashed_password.eq(&new_user.hashed_password),
+                    user::dsl::role.eq(&new_user.role),
+                )
+            );
+            list_repository::insert_default_lists(connection, user_id)
+        })
+        .expect("DB error");
+}
+
+#[instrument]
+pub fn get_user_by_id(id: i32) -> Option<User> {
+    let _timer = db_timer("get_user_by_id");
+    let connection = &mut establish_connection();
+    let results: Vec<User> = user::table
+        .select(User::as_select())
+        .filter(user::dsl::id.eq(id))
+        .traced_load(connection)
+        .expect("DB Error");
+    results.first().cloned()
+}
+
+/// ユーザーのロールを更新する。対象のユーザーが存在しなければfalse
+#[instrument(skip_all)]
+pub fn put_user_role(id: i32, role: Role) -> bool {
+    let _timer = db_timer("put_user_role");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(user::table.filter(user::dsl::id.eq(id)))
+        .set(user::dsl::role.eq(role.to_string()))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+#[instrument(skip_all)]
+pub fn put_user_password(id: i32, hashed_password: &str) {
+    let _timer = db_timer("put_user_password");
+    let connection = &mut establish_connection();
+    diesel::update(user::table.filter(user::dsl::id.eq(id)))
+        .set(user::dsl::hashed_password.eq(hashed_password))
+        .traced_execute(connection)
         .expect("DB error");
 }
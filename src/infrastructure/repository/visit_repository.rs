@@ -0,0 +1,159 @@
+use crate::domain::feed::{FeedBound, FeedContent, FeedItem};
+use crate::domain::visit_entity::VisitEntity;
+use crate::infrastructure::metrics::db_timer;
+use crate::infrastructure::rdb::{
+    diesel_connection::{establish_connection, insert_returning_id},
+    diesel_model::diesel_visit::Visit,
+    diesel_trace::TracedRunQueryDsl,
+};
+use crate::schema::visit;
+use chrono::Utc;
+use diesel::*;
+use tracing::instrument;
+
+/// 新しい順
+#[instrument]
+pub fn get_visits(user_id: u32) -> Vec<VisitEntity> {
+    let _timer = db_timer("get_visits");
+    let connection = &mut establish_connection();
+    let results: Vec<Visit> = visit::table
+        .select(Visit::as_select())
+        .filter(visit::dsl::user_id.eq(user_id as i32))
+        .order((visit::dsl::visited_on.desc(), visit::dsl::id.desc()))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(VisitEntity::from).collect()
+}
+
+/// ユーザー自身の記録だけを返す
+#[instrument]
+pub fn get_visit(user_id: u32, id: u32) -> Option<VisitEntity> {
+    let _timer = db_timer("get_visit");
+    let connection = &mut establish_connection();
+    let results: Vec<Visit> = visit::table
+        .select(Visit::as_select())
+        .filter(visit::dsl::id.eq(id as i32))
+        .filter(visit::dsl::user_id.eq(user_id as i32))
+        .traced_load(connection)
+        .expect("DB error");
+    results.first().cloned().map(VisitEntity::from)
+}
+
+/// 旅行に含まれる記録をまとめて読み込む。持ち主は確認しない
+#[instrument(skip_all)]
+pub fn get_visits_by_ids(ids: &[u32]) -> Vec<VisitEntity> {
+    let _timer = db_timer("get_visits_by_ids");
+    let connection = &mut establish_connection();
+    let ids: Vec<i32> = ids.iter().map(|v| *v as i32).collect();
+    let results: Vec<Visit> = visit::table
+        .select(Visit::as_select())
+        .filter(visit::dsl::id.eq_any(ids))
+        .traced_load(connection)
+        .expect("DB error");
+    results.into_iter().map(VisitEntity::from).collect()
+}
+
+/// 指定したユーザーの公開している記録を登録の新しい順に読み込む。`before`より前のものだけ
+#[instrument(skip(user_ids))]
+pub fn get_recent_public_visits(
+    user_ids: &[u32],
+    before: Option<FeedBound>,
+    limit: u32,
+) -> Vec<FeedItem> {
+    let _timer = db_timer("get_recent_public_visits");
+    let connection = &mut establish_connection();
+    let user_ids: Vec<i32> = user_ids.iter().map(|v| *v as i32).collect();
+    let mut query = visit::table.into_boxed();
+    if let Some(before) = before {
+        query = match before.id {
+            Some(id) => query.filter(
+                visit::dsl::created_at
+                    .lt(before.posted_at)
+                    .or(visit::dsl::created_at
+                        .eq(before.posted_at)
+                        .and(visit::dsl::id.lt(id as i32))),
+            ),
+            None => query.filter(visit::dsl::created_at.le(before.posted_at)),
+        };
+    }
+    let results: Vec<Visit> = query
+        .select(Visit::as_select())
+        .filter(visit::dsl::user_id.eq_any(user_ids))
+        .filter(visit::dsl::is_public.eq(true))
+        .order((visit::dsl::created_at.desc(), visit::dsl::id.desc()))
+        .limit(limit as i64)
+        .traced_load(connection)
+        .expect("DB error");
+    results
+        .into_iter()
+        .map(|v| FeedItem::new(v.created_at, FeedContent::Visit(VisitEntity::from(v))))
+        .collect()
+}
+
+#[instrument(skip_all)]
+pub fn post_visit(visit_entity: VisitEntity) -> VisitEntity {
+    let _timer = db_timer("post_visit");
+    let connection = &mut establish_connection();
+    let now = Utc::now().naive_utc();
+    let id = insert_returning_id!(
+        connection,
+        visit::table,
+        (
+            visit::dsl::user_id.eq(visit_entity.user_id as i32),
+            visit::dsl::onsen_id.eq(visit_entity.onsen_id as i32),
+            visit::dsl::visited_on.eq(visit_entity.visited_on),
+            visit::dsl::time_of_day.eq(visit_entity.time_of_day.as_ref().map(|v| v.to_string())),
+            visit::dsl::weather.eq(visit_entity.weather.as_ref().map(|v| v.to_string())),
+            visit::dsl::crowd_level.eq(visit_entity.crowd_level.as_ref().map(|v| v.to_string())),
+            visit::dsl::water_temperature.eq(visit_entity
+                .water_temperature
+                .as_ref()
+                .map(|v| v.to_string())),
+            visit::dsl::note.eq(&visit_entity.note),
+            visit::dsl::is_public.eq(visit_entity.is_public),
+            visit::dsl::created_at.eq(now),
+            visit::dsl::updated_at.eq(now),
+        )
+    );
+    VisitEntity {
+        id: id as u32,
+        ..visit_entity
+    }
+}
+
+/// ユーザー自身の記録を更新する。存在しなければfalse
+#[instrument(skip_all)]
+pub fn put_visit(visit_entity: VisitEntity) -> bool {
+    let _timer = db_timer("put_visit");
+    let connection = &mut establish_connection();
+    let updated = diesel::update(visit::table.find(visit_entity.id as i32))
+        .filter(visit::dsl::user_id.eq(visit_entity.user_id as i32))
+        .set((
+            visit::dsl::onsen_id.eq(visit_entity.onsen_id as i32),
+            visit::dsl::visited_on.eq(visit_entity.visited_on),
+            visit::dsl::time_of_day.eq(visit_entity.time_of_day.as_ref().map(|v| v.to_string())),
+            visit::dsl::weather.eq(visit_entity.weather.as_ref().map(|v| v.to_string())),
+            visit::dsl::crowd_level.eq(visit_entity.crowd_level.as_ref().map(|v| v.to_string())),
+            visit::dsl::water_temperature.eq(visit_entity
+                .water_temperature
+                .as_ref()
+                .map(|v| v.to_string())),
+            visit::dsl::note.eq(&visit_entity.note),
+            visit::dsl::is_public.eq(visit_entity.is_public),
+            visit::dsl::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .traced_execute(connection)
+        .expect("DB error");
+    updated > 0
+}
+
+#[instrument]
+pub fn delete_visit(user_id: u32, id: u32) -> bool {
+    let _timer = db_timer("delete_visit");
+    let connection = &mut establish_connection();
+    let deleted = diesel::delete(visit::table.find(id as i32))
+        .filter(visit::dsl::user_id.eq(user_id as i32))
+        .traced_execute(connection)
+        .expect("DB error");
+    deleted > 0
+}
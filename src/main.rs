@@ -6,78 +6,66 @@ mod domain;
 mod infrastructure;
 mod schema;
 
+use application::controller::achievement_controller::*;
+use application::controller::api_key_controller::*;
 use application::controller::area_controller::*;
+use application::controller::comment_controller::*;
+use application::controller::health_controller::*;
 use application::controller::hotel_controller::*;
+use application::controller::jwks_controller::*;
+use application::controller::list_controller::*;
+use application::controller::metrics_controller::*;
 use application::controller::onsen_controller::*;
+use application::controller::photo_controller::*;
+use application::controller::profile_controller::*;
+use application::controller::review_controller::*;
+use application::controller::stats_controller::*;
+use application::controller::trip_controller::*;
+use application::controller::two_factor_controller::*;
 use application::controller::user_controller::*;
-use rocket::fairing::Fairing;
-use rocket::fairing::Info;
-use rocket::fairing::Kind;
-use rocket::http::Header;
-use rocket::http::Method;
-use rocket::http::Status;
-use rocket::response;
-use rocket::Request;
-use rocket::Response;
+use application::controller::visit_controller::*;
+use application::fairing::cors::cors_fairing;
+use application::fairing::jwt::jwt_fairing;
+use application::fairing::mailer::mailer_fairing;
+use application::fairing::password::password_fairing;
+use application::fairing::rate_limit::rate_limit_fairing;
+use application::fairing::request_metrics::RequestMetrics;
+use application::fairing::request_tracing::RequestTracing;
+use application::fairing::storage::storage_fairing;
+use application::fairing::thumbnail::ThumbnailWorker;
+use application::fairing::two_factor::two_factor_fairing;
+use application::fairing::www_authenticate::WwwAuthenticate;
 
 #[get("/")]
 fn index() -> &'static str {
     "Hello, world!"
 }
 
-pub struct PfResponse;
-
-impl<'r> response::Responder<'r, 'static> for PfResponse {
-    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
-        response::Response::build()
-            .header(Header::new("Access-Control-Allow-Origin", "*"))
-            .header(Header::new(
-                "Access-Control-Allow-Methods",
-                "POST, PUT, PATCH, GET, DELETE",
-            ))
-            .header(Header::new("Access-Control-Allow-Headers", "*"))
-            .header(Header::new("Access-Control-Allow-Credentials", "true"))
-            .ok()
-    }
-}
-
-#[options("/<_p..>")]
-pub fn preflight(_p: std::path::PathBuf) -> PfResponse {
-    PfResponse
-}
-
-pub struct CORS;
-
-#[rocket::async_trait]
-impl Fairing for CORS {
-    fn info(&self) -> Info {
-        Info {
-            name: "Add CORS headers to responses",
-            kind: Kind::Response,
-        }
-    }
-
-    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        if request.method() == Method::Options {
-            response.set_status(Status::NoContent);
-            response.set_header(Header::new(
-                "Access-Control-Allow-Methods",
-                "POST, PUT, PATCH, GET, DELETE",
-            ));
-            response.set_header(Header::new("Access-Control-Allow-Headers", "*"));
-        }
-        response.set_header(Header::new("Access-Control-Allow-Origin", "*"));
-        response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
-    }
+/// ログはJSONで標準出力に出す。レベルはRUST_LOGで変更できる
+fn init_tracing() {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_env_filter(filter)
+        .init();
 }
 
 #[launch]
 fn rocket() -> _ {
+    init_tracing();
     rocket::build()
         .mount(
             "/",
             routes![
                 index,
+                get_healthz,
+                get_readyz,
+                get_version,
+                get_metrics,
+                get_jwks,
                 get_hotels,
                 get_hotel,
                 post_hotel,
@@ -92,8 +80,104 @@ fn rocket() -> _ {
                 put_area,
                 post_signup,
                 post_signin,
-                preflight,
+                post_token_refresh,
+                post_signout,
+                put_user_role,
+                post_password_forgot,
+                post_password_reset,
+                get_verify,
+                post_verify_resend,
+                post_signin_two_factor,
+                post_totp,
+                post_totp_confirm,
+                delete_totp,
+                post_api_key,
+                get_api_keys,
+                delete_api_key,
+                get_visits,
+                get_visit,
+                post_visit,
+                put_visit,
+                delete_visit,
+                get_trips,
+                get_trip,
+                post_trip,
+                put_trip,
+                delete_trip,
+                get_trip_detail,
+                get_onsen_reviews,
+                put_onsen_review,
+                delete_onsen_review,
+                get_hotel_reviews,
+                put_hotel_review,
+                delete_hotel_review,
+                get_review_queue,
+                put_review_status,
+                get_lists,
+                post_list,
+                get_list,
+                put_list,
+                delete_list,
+                post_list_item,
+                put_list_items,
+                delete_list_item,
+                post_list_share,
+                delete_list_share,
+                get_shared_list,
+                get_stats,
+                get_achievements,
+                get_my_achievements,
+                post_achievement,
+                put_achievement,
+                delete_achievement,
+                get_onsen_photos,
+                post_onsen_photo,
+                put_onsen_photo,
+                delete_onsen_photo,
+                get_area_photos,
+                post_area_photo,
+                put_area_photo,
+                delete_area_photo,
+                get_visit_photos,
+                post_visit_photo,
+                put_visit_photo,
+                delete_visit_photo,
+                get_image,
+                get_image_thumbnail,
+                post_avatar,
+                delete_avatar,
+                get_my_profile,
+                put_my_profile,
+                get_profile,
+                put_follow,
+                delete_follow,
+                get_following,
+                get_followers,
+                put_follower,
+                delete_follower,
+                get_feed,
+                get_trip_comments,
+                post_trip_comment,
+                get_visit_comments,
+                post_visit_comment,
+                put_comment,
+                delete_comment,
+                post_comment_report,
+                get_comment_reports,
+                put_comment_status,
+                get_notifications,
+                put_notifications_read,
             ],
         )
-        .attach(CORS)
+        .attach(cors_fairing())
+        .attach(jwt_fairing())
+        .attach(mailer_fairing())
+        .attach(password_fairing())
+        .attach(rate_limit_fairing())
+        .attach(storage_fairing())
+        .attach(two_factor_fairing())
+        .attach(RequestTracing)
+        .attach(RequestMetrics)
+        .attach(ThumbnailWorker)
+        .attach(WwwAuthenticate)
 }
@@ -1,8 +1,44 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    achievement (id) {
+        id -> Integer,
+        #[max_length = 255]
+        name -> Varchar,
+        description -> Text,
+        #[max_length = 255]
+        condition_kind -> Varchar,
+        #[max_length = 255]
+        condition_target -> Varchar,
+        condition_value -> Double,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_key (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        key_prefix -> Varchar,
+        #[max_length = 255]
+        key_hash -> Varchar,
+        #[max_length = 1024]
+        scopes -> Varchar,
+        mfa -> Bool,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     area (id) {
-        id -> Unsigned<Integer>,
+        id -> Integer,
         #[max_length = 255]
         name -> Varchar,
         #[max_length = 255]
@@ -21,21 +57,21 @@ diesel::table! {
 
 diesel::table! {
     chemicals (id) {
-        id -> Unsigned<Integer>,
-        na_ion -> Unsigned<Integer>,
-        ca_ion -> Unsigned<Integer>,
-        mg_ion -> Unsigned<Integer>,
-        cl_ion -> Unsigned<Integer>,
-        hco3_ion -> Unsigned<Integer>,
-        so4_ion -> Unsigned<Integer>,
-        co2_ion -> Unsigned<Integer>,
-        fe_ion -> Unsigned<Integer>,
-        al_ion -> Unsigned<Integer>,
-        cu_ion -> Unsigned<Integer>,
-        h_ion -> Unsigned<Integer>,
-        i_ion -> Unsigned<Integer>,
-        s -> Unsigned<Integer>,
-        rn -> Unsigned<Integer>,
+        id -> Integer,
+        na_ion -> Integer,
+        ca_ion -> Integer,
+        mg_ion -> Integer,
+        cl_ion -> Integer,
+        hco3_ion -> Integer,
+        so4_ion -> Integer,
+        co2_ion -> Integer,
+        fe_ion -> Integer,
+        al_ion -> Integer,
+        cu_ion -> Integer,
+        h_ion -> Integer,
+        i_ion -> Integer,
+        s -> Integer,
+        rn -> Integer,
         strong_na_cl -> Bool,
         #[max_length = 255]
         fe_type -> Varchar,
@@ -43,9 +79,60 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment (id) {
+        id -> Integer,
+        #[max_length = 255]
+        target -> Varchar,
+        target_id -> Integer,
+        user_id -> Integer,
+        parent_id -> Nullable<Integer>,
+        body -> Text,
+        #[max_length = 255]
+        status -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    comment_report (id) {
+        id -> Integer,
+        comment_id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        reason -> Varchar,
+        note -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    email_verification_token (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    follow (id) {
+        id -> Integer,
+        follower_id -> Integer,
+        followee_id -> Integer,
+        #[max_length = 255]
+        status -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     hotel (id) {
-        id -> Unsigned<Integer>,
+        id -> Integer,
         #[max_length = 255]
         name -> Varchar,
         has_washitsu -> Bool,
@@ -53,13 +140,42 @@ diesel::table! {
         #[max_length = 255]
         url -> Varchar,
         description -> Text,
-        area_id -> Nullable<Unsigned<Integer>>,
+        area_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    hotel_review (id) {
+        id -> Integer,
+        user_id -> Integer,
+        hotel_id -> Integer,
+        room -> Integer,
+        food -> Integer,
+        bath -> Integer,
+        comment -> Text,
+        #[max_length = 255]
+        status -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    notification (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        kind -> Varchar,
+        actor_id -> Integer,
+        comment_id -> Integer,
+        read_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::table! {
     onsen (id) {
-        id -> Unsigned<Integer>,
+        id -> Integer,
         #[max_length = 255]
         name -> Varchar,
         #[max_length = 255]
@@ -78,33 +194,331 @@ diesel::table! {
         #[max_length = 255]
         img_url -> Nullable<Varchar>,
         description -> Text,
-        chemical_id -> Nullable<Unsigned<Integer>>,
-        hotel_id -> Nullable<Unsigned<Integer>>,
-        area_id -> Nullable<Unsigned<Integer>>,
+        chemical_id -> Nullable<Integer>,
+        hotel_id -> Nullable<Integer>,
+        area_id -> Nullable<Integer>,
+        ph -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    onsen_review (id) {
+        id -> Integer,
+        user_id -> Integer,
+        onsen_id -> Integer,
+        overall -> Integer,
+        water_quality -> Integer,
+        cleanliness -> Integer,
+        view -> Integer,
+        crowding -> Integer,
+        comment -> Text,
+        #[max_length = 255]
+        status -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    password_reset_token (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    photo (id) {
+        id -> Integer,
+        #[max_length = 255]
+        target -> Varchar,
+        target_id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        blob_key -> Varchar,
+        #[max_length = 255]
+        content_type -> Varchar,
+        byte_size -> Integer,
+        #[max_length = 255]
+        caption -> Varchar,
+        position -> Integer,
+        is_cover -> Bool,
+        created_at -> Timestamp,
+        #[max_length = 255]
+        thumbnail_status -> Varchar,
+    }
+}
+
+diesel::table! {
+    refresh_token (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        family_id -> Varchar,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        mfa -> Bool,
+    }
+}
+
+diesel::table! {
+    signin_audit_log (id) {
+        id -> Integer,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        #[max_length = 255]
+        reason -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    totp_backup_code (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    trip (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        title -> Varchar,
+        start_on -> Date,
+        end_on -> Date,
+        note -> Text,
+        is_public -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    trip_day (id) {
+        id -> Integer,
+        trip_id -> Integer,
+        day_number -> Integer,
+        hotel_id -> Nullable<Integer>,
+        note -> Text,
+    }
+}
+
+diesel::table! {
+    trip_day_area (id) {
+        id -> Integer,
+        trip_day_id -> Integer,
+        area_id -> Integer,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    trip_day_visit (id) {
+        id -> Integer,
+        trip_day_id -> Integer,
+        visit_id -> Integer,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    two_factor_challenge (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        attempts -> Integer,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
 diesel::table! {
     user (id) {
-        id -> Unsigned<Integer>,
+        id -> Integer,
         #[max_length = 255]
         email -> Varchar,
         #[max_length = 255]
         hashed_password -> Varchar,
         #[max_length = 255]
         role -> Varchar,
+        verified_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    user_achievement (id) {
+        id -> Integer,
+        user_id -> Integer,
+        achievement_id -> Integer,
+        achieved_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_list (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        kind -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        share_token -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        #[max_length = 255]
+        default_kind -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    user_list_item (id) {
+        id -> Integer,
+        user_list_id -> Integer,
+        #[max_length = 255]
+        target -> Varchar,
+        target_id -> Integer,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    user_profile (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 30]
+        handle -> Varchar,
+        #[max_length = 255]
+        display_name -> Varchar,
+        bio -> Text,
+        #[max_length = 255]
+        profile_visibility -> Varchar,
+        #[max_length = 255]
+        visits_visibility -> Varchar,
+        #[max_length = 255]
+        trips_visibility -> Varchar,
+        #[max_length = 255]
+        reviews_visibility -> Varchar,
+        approve_followers -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_totp (id) {
+        id -> Integer,
+        user_id -> Integer,
+        #[max_length = 255]
+        secret -> Varchar,
+        enabled_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Bigint>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    visit (id) {
+        id -> Integer,
+        user_id -> Integer,
+        onsen_id -> Integer,
+        visited_on -> Date,
+        #[max_length = 255]
+        time_of_day -> Nullable<Varchar>,
+        #[max_length = 255]
+        weather -> Nullable<Varchar>,
+        #[max_length = 255]
+        crowd_level -> Nullable<Varchar>,
+        #[max_length = 255]
+        water_temperature -> Nullable<Varchar>,
+        note -> Text,
+        is_public -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
+diesel::joinable!(api_key -> user (user_id));
+diesel::joinable!(comment -> user (user_id));
+diesel::joinable!(comment_report -> comment (comment_id));
+diesel::joinable!(comment_report -> user (user_id));
+diesel::joinable!(email_verification_token -> user (user_id));
 diesel::joinable!(hotel -> area (area_id));
+diesel::joinable!(hotel_review -> hotel (hotel_id));
+diesel::joinable!(hotel_review -> user (user_id));
+diesel::joinable!(notification -> comment (comment_id));
 diesel::joinable!(onsen -> area (area_id));
 diesel::joinable!(onsen -> chemicals (chemical_id));
 diesel::joinable!(onsen -> hotel (hotel_id));
+diesel::joinable!(onsen_review -> onsen (onsen_id));
+diesel::joinable!(onsen_review -> user (user_id));
+diesel::joinable!(password_reset_token -> user (user_id));
+diesel::joinable!(photo -> user (user_id));
+diesel::joinable!(refresh_token -> user (user_id));
+diesel::joinable!(totp_backup_code -> user (user_id));
+diesel::joinable!(trip -> user (user_id));
+diesel::joinable!(trip_day -> hotel (hotel_id));
+diesel::joinable!(trip_day -> trip (trip_id));
+diesel::joinable!(trip_day_area -> area (area_id));
+diesel::joinable!(trip_day_area -> trip_day (trip_day_id));
+diesel::joinable!(trip_day_visit -> trip_day (trip_day_id));
+diesel::joinable!(trip_day_visit -> visit (visit_id));
+diesel::joinable!(two_factor_challenge -> user (user_id));
+diesel::joinable!(user_achievement -> achievement (achievement_id));
+diesel::joinable!(user_achievement -> user (user_id));
+diesel::joinable!(user_list -> user (user_id));
+diesel::joinable!(user_list_item -> user_list (user_list_id));
+diesel::joinable!(user_profile -> user (user_id));
+diesel::joinable!(user_totp -> user (user_id));
+diesel::joinable!(visit -> onsen (onsen_id));
+diesel::joinable!(visit -> user (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    achievement,
+    api_key,
     area,
     chemicals,
+    comment,
+    comment_report,
+    email_verification_token,
+    follow,
     hotel,
+    hotel_review,
+    notification,
     onsen,
+    onsen_review,
+    password_reset_token,
+    photo,
+    refresh_token,
+    signin_audit_log,
+    totp_backup_code,
+    trip,
+    trip_day,
+    trip_day_area,
+    trip_day_visit,
+    two_factor_challenge,
     user,
+    user_achievement,
+    user_list,
+    user_list_item,
+    user_profile,
+    user_totp,
+    visit,
 );
@@ -1,3 +1,10 @@
 #!/bin/sh
-$HOME/.cargo/bin/diesel migration run
+case "$DATABASE_URL" in
+  postgres://*|postgresql://*)
+    $HOME/.cargo/bin/diesel migration run --config-file /diesel_postgres.toml
+    ;;
+  *)
+    $HOME/.cargo/bin/diesel migration run
+    ;;
+esac
 /onsen_tabi